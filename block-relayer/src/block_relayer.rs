@@ -1,24 +1,29 @@
+use crate::metrics::BLOCK_RELAYER_METRICS;
 use actix::prelude::*;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use bus::Bus;
 use bus::{Broadcast, BusActor, Subscription};
 use crypto::HashValue;
 use logger::prelude::*;
-use network_rpc::{gen_client::NetworkRpcClient, GetTxns};
+use lru::LruCache;
+use network_api::{reputation, NetworkService};
+use network_rpc::{gen_client::NetworkRpcClient, GetBlockTxns};
 use starcoin_block_relayer_api::{NetCmpctBlockMessage, PeerCmpctBlockEvent};
+use starcoin_canonical_serialization::SCSCodec;
 use starcoin_network::network::NetworkAsyncService;
-use starcoin_sync::helper::get_txns;
 use starcoin_sync_api::PeerNewBlock;
-use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
 use starcoin_types::{
-    block::{Block, BlockBody},
-    cmpact_block::{CompactBlock, PrefiledTxn, ShortId},
+    block::{Block, BlockBody, BlockHeader},
+    cmpact_block::{CompactBlock, ShortId},
     peer_info::PeerId,
     system_events::NewHeadBlock,
-    transaction::{SignedUserTransaction, Transaction},
+    transaction::{SignedUserTransaction, Transaction, TxStatus},
 };
 use std::collections::{HashMap, HashSet};
-use std::iter::FromIterator;
+
+/// Size of the txns cache which are seen by local txpool.
+const SEEN_TXNS_CACHE_SIZE: usize = 4096;
 
 pub struct BlockRelayer<P>
 where
@@ -26,7 +31,9 @@ where
 {
     bus: Addr<BusActor>,
     txpool: P,
-    rpc_client: NetworkRpcClient<NetworkAsyncService>,
+    network: NetworkAsyncService,
+    /// Txns which have been added to local txpool, and propagated to peers.
+    seen_txns: LruCache<HashValue, ()>,
 }
 
 impl<P> BlockRelayer<P>
@@ -41,108 +48,196 @@ where
         let block_relayer = BlockRelayer {
             bus,
             txpool,
-            rpc_client: NetworkRpcClient::new(network),
+            network,
+            seen_txns: LruCache::new(SEEN_TXNS_CACHE_SIZE),
         };
         Ok(block_relayer.start())
     }
 
-    async fn fill_compact_block(
+    /// Fill the compact block by prefilled txns, txpool and the missing txns fetched from peer,
+    /// fallback to fetch the full block if short ids collide, or the peer can not provide the
+    /// missing txns, e.g. the txns are evicted.
+    /// Return None if the peer can not provide the block either, and Err if the compact block is
+    /// malformed, the peer is reported for the malformed compact block or block txns.
+    async fn fill_compact_block<N>(
         txpool: P,
-        rpc_client: NetworkRpcClient<NetworkAsyncService>,
+        network: N,
         compact_block: CompactBlock,
         peer_id: PeerId,
-    ) -> Result<Block> {
-        let txns_pool_vec = txpool.get_pending_txns(None, None);
-        let txns_pool_map: HashMap<ShortId, &SignedUserTransaction> = {
-            let pool_id_txn_iter = txns_pool_vec
-                .iter()
-                .map(|txn| (Transaction::UserTransaction(txn.clone()).id(), txn))
-                .map(|(id, txn)| (ShortId(id), txn));
-            HashMap::from_iter(pool_id_txn_iter)
-        };
-        let txns = {
-            let mut txns: Vec<Option<SignedUserTransaction>> =
-                vec![None; compact_block.short_ids.len()];
-            let mut missing_txn_short_ids = HashSet::new();
-            // Fill the block txns by tx pool
-            for (index, short_id) in compact_block.short_ids.iter().enumerate() {
-                if let Some(txn) = txns_pool_map.get(short_id) {
-                    txns[index] = Some((*txn).clone());
-                } else {
-                    missing_txn_short_ids.insert(short_id);
-                }
+    ) -> Result<Option<Block>>
+    where
+        N: NetworkService,
+    {
+        let rpc_client = NetworkRpcClient::new(network.clone());
+        let block_id = compact_block.header.id();
+        let txn_len = compact_block.txn_len();
+        let mut txns: Vec<Option<SignedUserTransaction>> = vec![None; txn_len];
+
+        // Fill the block txns by prefilled txn
+        for prefilled_txn in &compact_block.prefilled_txn {
+            let index = prefilled_txn.index as usize;
+            if index >= txn_len || txns[index].is_some() {
+                network.report_peer(peer_id.clone().into(), reputation::INVALID_BLOCK);
+                bail!(
+                    "Invalid prefilled txn index {} of compact block {:?}",
+                    index,
+                    block_id
+                );
             }
+            txns[index] = Some(prefilled_txn.tx.clone());
+        }
+        BLOCK_RELAYER_METRICS
+            .txns_filled
+            .with_label_values(&["prefilled"])
+            .inc_by(compact_block.prefilled_txn.len() as i64);
+
+        let short_id_indexes: Vec<usize> = (0..txn_len).filter(|i| txns[*i].is_none()).collect();
+        let block_short_ids: HashSet<&ShortId> = compact_block.short_ids.iter().collect();
+        let mut collision = block_short_ids.len() != compact_block.short_ids.len();
 
-            // Fill the block txns by prefilled txn
-            for prefilled_txn in compact_block.prefilled_txn {
-                if prefilled_txn.index as usize >= txns.len() {
-                    continue;
+        // Fill the block txns by tx pool
+        let mut missing_indexes = Vec::new();
+        if !collision {
+            let mut txns_pool_map: HashMap<ShortId, SignedUserTransaction> = HashMap::new();
+            let mut collided_short_ids = HashSet::new();
+            for txn in txpool.get_pending_txns(None, None) {
+                let short_id =
+                    compact_block.short_id(&Transaction::UserTransaction(txn.clone()).id());
+                if txns_pool_map.insert(short_id, txn).is_some() {
+                    collided_short_ids.insert(short_id);
                 }
-                txns[prefilled_txn.index as usize] = Some(prefilled_txn.clone().tx);
-                missing_txn_short_ids.remove(&ShortId(
-                    Transaction::UserTransaction(prefilled_txn.tx).id(),
-                ));
             }
-            // Fetch the missing txns from peer
-            let missing_txn_ids: Vec<HashValue> = missing_txn_short_ids
-                .iter()
-                .map(|&short_id| short_id.0)
-                .collect();
-            let fetched_missing_txn = get_txns(
-                &rpc_client,
-                peer_id,
-                GetTxns {
-                    ids: Some(missing_txn_ids),
-                },
-            )
-            .await?
-            .txns;
-            let fetched_missing_txn_map: HashMap<ShortId, &SignedUserTransaction> = {
-                let iter = fetched_missing_txn
-                    .iter()
-                    .map(|txn| (Transaction::UserTransaction(txn.clone()).id(), txn))
-                    .map(|(id, txn)| (ShortId(id), txn));
-                HashMap::from_iter(iter)
+            for (short_id, index) in compact_block.short_ids.iter().zip(short_id_indexes) {
+                if collided_short_ids.contains(short_id) {
+                    collision = true;
+                    break;
+                }
+                match txns_pool_map.remove(short_id) {
+                    Some(txn) => txns[index] = Some(txn),
+                    None => missing_indexes.push((index as u64, short_id)),
+                }
+            }
+        }
+        if collision {
+            debug!("Short ids of compact block {:?} collide.", block_id);
+            return Self::fetch_full_block(&rpc_client, compact_block.header, peer_id).await;
+        }
+        BLOCK_RELAYER_METRICS
+            .txns_filled
+            .with_label_values(&["txpool"])
+            .inc_by((compact_block.short_ids.len() - missing_indexes.len()) as i64);
+
+        // Fetch the missing txns from peer
+        if !missing_indexes.is_empty() {
+            let fetched_txns = match rpc_client
+                .get_block_txns(
+                    peer_id.clone(),
+                    GetBlockTxns {
+                        block_id,
+                        indexes: missing_indexes.iter().map(|(index, _)| *index).collect(),
+                    },
+                )
+                .await
+            {
+                Ok(block_txns) if block_txns.txns.len() == missing_indexes.len() => block_txns.txns,
+                Ok(_) => {
+                    debug!(
+                        "Peer {} can not provide txns of block {:?}.",
+                        peer_id, block_id
+                    );
+                    return Self::fetch_full_block(&rpc_client, compact_block.header, peer_id)
+                        .await;
+                }
+                Err(e) => {
+                    debug!(
+                        "Fetch txns of block {:?} from {} failed: {:?}",
+                        block_id, peer_id, e
+                    );
+                    return Self::fetch_full_block(&rpc_client, compact_block.header, peer_id)
+                        .await;
+                }
             };
-            for (index, short_id) in compact_block.short_ids.iter().enumerate() {
-                if txns[index].is_none() {
-                    if let Some(&txn) = fetched_missing_txn_map.get(short_id) {
-                        txns[index] = Some(txn.clone());
-                    }
+            let fetched_bytes: usize = fetched_txns
+                .iter()
+                .map(|txn| txn.encode().map(|bytes| bytes.len()).unwrap_or(0))
+                .sum();
+            BLOCK_RELAYER_METRICS
+                .relay_bytes
+                .with_label_values(&["block_txns"])
+                .inc_by(fetched_bytes as i64);
+            BLOCK_RELAYER_METRICS
+                .txns_filled
+                .with_label_values(&["fetched"])
+                .inc_by(fetched_txns.len() as i64);
+            for ((index, short_id), txn) in missing_indexes.into_iter().zip(fetched_txns) {
+                if compact_block.short_id(&Transaction::UserTransaction(txn.clone()).id())
+                    != *short_id
+                {
+                    network.report_peer(peer_id.clone().into(), reputation::INVALID_BLOCK);
+                    bail!(
+                        "Txn fetched from {} mismatch short id of block {:?} at index {}",
+                        peer_id,
+                        block_id,
+                        index
+                    );
                 }
+                txns[index as usize] = Some(txn);
             }
-            txns.iter()
-                .filter(|&txn| txn.is_some())
-                .map(|txn| txn.clone().unwrap())
-                .collect()
-        };
+        }
+
+        let txns = txns.into_iter().flatten().collect();
         let body = BlockBody::new(txns, None);
         let block = Block::new(compact_block.header, body);
-        Ok(block)
-    }
-
-    fn block_into_compact(&self, block: Block) -> CompactBlock {
-        let mut prefilled_txn = Vec::new();
-        let txns_pool_map: HashMap<HashValue, SignedUserTransaction> = {
-            let pool_id_txn = self.txpool.get_pending_txns(None, None);
-            HashMap::from_iter(
-                pool_id_txn
-                    .iter()
-                    .map(|txn| (Transaction::UserTransaction(txn.clone()).id(), txn.clone())),
-            )
-        };
-        for (index, txn) in block.transactions().iter().enumerate() {
-            let id = Transaction::UserTransaction(txn.clone()).id();
-            if !txns_pool_map.contains_key(&id) {
-                prefilled_txn.push(PrefiledTxn {
-                    index: index as u64,
-                    tx: txn.clone(),
-                });
+        BLOCK_RELAYER_METRICS
+            .fill_results
+            .with_label_values(&["success"])
+            .inc();
+        Ok(Some(block))
+    }
+
+    async fn fetch_full_block<N>(
+        rpc_client: &NetworkRpcClient<N>,
+        header: BlockHeader,
+        peer_id: PeerId,
+    ) -> Result<Option<Block>>
+    where
+        N: NetworkService,
+    {
+        BLOCK_RELAYER_METRICS
+            .fill_results
+            .with_label_values(&["fallback"])
+            .inc();
+        let body = rpc_client
+            .get_body_by_hash(peer_id, vec![header.id()])
+            .await?
+            .pop();
+        Ok(body.map(|body| {
+            let block = Block::new(header, BlockBody::new(body.transactions, body.uncles));
+            if let Ok(bytes) = block.encode() {
+                BLOCK_RELAYER_METRICS
+                    .relay_bytes
+                    .with_label_values(&["full_block"])
+                    .inc_by(bytes.len() as i64);
             }
-        }
-        // TODO: prefill txns always equal to block.transactions.
-        prefilled_txn.clear();
-        CompactBlock::new(&block, prefilled_txn)
+            block
+        }))
+    }
+
+    /// Txns which are not seen by local txpool are likely not propagated to peers,
+    /// so prefill them in the compact block.
+    fn block_into_compact(&mut self, block: Block) -> CompactBlock {
+        let prefilled_indexes: Vec<usize> = block
+            .transactions()
+            .iter()
+            .enumerate()
+            .filter(|(_, txn)| {
+                !self
+                    .seen_txns
+                    .contains(&Transaction::UserTransaction((*txn).clone()).id())
+            })
+            .map(|(index, _)| index)
+            .collect();
+        CompactBlock::new(&block, rand::random(), prefilled_indexes.as_slice())
     }
 }
 
@@ -168,6 +263,22 @@ where
             .into_actor(self)
             .then(|_res, act, _ctx| async {}.into_actor(act))
             .wait(ctx);
+
+        ctx.add_stream(self.txpool.subscribe_txns());
+    }
+}
+
+/// Record the txns added to local txpool.
+impl<P> StreamHandler<TxnStatusFullEvent> for BlockRelayer<P>
+where
+    P: TxPoolSyncService + Sync + Send + 'static,
+{
+    fn handle(&mut self, item: TxnStatusFullEvent, _ctx: &mut Context<Self>) {
+        for (id, status) in item.iter() {
+            if *status == TxStatus::Added {
+                self.seen_txns.put(*id, ());
+            }
+        }
     }
 }
 
@@ -179,6 +290,12 @@ where
     fn handle(&mut self, event: NewHeadBlock, ctx: &mut Self::Context) -> Self::Result {
         debug!("Handle relay new head block event");
         let compact_block = self.block_into_compact(event.0.get_block().clone());
+        if let Ok(bytes) = compact_block.encode() {
+            BLOCK_RELAYER_METRICS
+                .relay_bytes
+                .with_label_values(&["compact_block"])
+                .inc_by(bytes.len() as i64);
+        }
         let total_difficulty = event.0.get_total_difficulty();
         let net_cmpct_block_msg = NetCmpctBlockMessage {
             compact_block,
@@ -212,21 +329,314 @@ where
         _ctx: &mut Self::Context,
     ) -> Self::Result {
         let bus = self.bus.clone();
-        let rpc_client = self.rpc_client.clone();
+        let network = self.network.clone();
         let txpool = self.txpool.clone();
         let fut = async move {
             let compact_block = cmpct_block_msg.compact_block;
             let peer_id = cmpct_block_msg.peer_id;
             debug!("Receive peer compact block event from peer id:{}", peer_id);
-            if let Ok(block) =
-                BlockRelayer::fill_compact_block(txpool, rpc_client, compact_block, peer_id.clone())
-                    .await
+            match BlockRelayer::fill_compact_block(txpool, network, compact_block, peer_id.clone())
+                .await
             {
-                bus.do_send(Broadcast {
+                Ok(Some(block)) => bus.do_send(Broadcast {
                     msg: PeerNewBlock::new(peer_id, block),
-                });
+                }),
+                Ok(None) => {
+                    BLOCK_RELAYER_METRICS
+                        .fill_results
+                        .with_label_values(&["fail"])
+                        .inc();
+                    // The block may be pruned or not stored by the peer, it's not a misbehavior.
+                    debug!("Peer {} can not provide the compact block.", peer_id);
+                }
+                Err(e) => debug!("Fill compact block from {} failed: {:?}", peer_id, e),
             }
         };
         Arbiter::spawn(fut);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use network_api::{messages::PeerMessage, reputation::ReputationChange};
+    use starcoin_txpool_mock_service::MockTxPoolService;
+    use starcoin_types::peer_info::{PeerInfo, RpcInfo};
+    use std::{
+        borrow::Cow,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// A network which responses rpc requests by the prepared response of the rpc path, and
+    /// records the requests and the reports of peers.
+    #[derive(Clone)]
+    struct MockNetworkService {
+        peer_id: network_api::PeerId,
+        responses: Arc<HashMap<String, Vec<u8>>>,
+        requests: Arc<Mutex<Vec<String>>>,
+        reports: Arc<Mutex<Vec<ReputationChange>>>,
+    }
+
+    impl MockNetworkService {
+        fn new(responses: Vec<(&str, Vec<u8>)>) -> Self {
+            Self {
+                peer_id: network_api::PeerId::random(),
+                responses: Arc::new(
+                    responses
+                        .into_iter()
+                        .map(|(path, response)| (path.to_string(), response))
+                        .collect(),
+                ),
+                requests: Arc::new(Mutex::new(vec![])),
+                reports: Arc::new(Mutex::new(vec![])),
+            }
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn reports(&self) -> Vec<ReputationChange> {
+            self.reports.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl NetworkService for MockNetworkService {
+        async fn send_peer_message(
+            &self,
+            _protocol_name: Cow<'static, [u8]>,
+            _peer_id: network_api::PeerId,
+            _msg: PeerMessage,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn broadcast_new_head_block(
+            &self,
+            _protocol_name: Cow<'static, [u8]>,
+            _event: NewHeadBlock,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn identify(&self) -> &network_api::PeerId {
+            &self.peer_id
+        }
+
+        async fn send_request_bytes(
+            &self,
+            _protocol_name: Cow<'static, [u8]>,
+            _peer_id: network_api::PeerId,
+            rpc_path: String,
+            _message: Vec<u8>,
+            _time_out: Duration,
+        ) -> Result<Vec<u8>> {
+            self.requests.lock().unwrap().push(rpc_path.clone());
+            self.responses
+                .get(&rpc_path)
+                .cloned()
+                .ok_or_else(|| anyhow::format_err!("Rpc {} timeout", rpc_path))
+        }
+
+        async fn peer_set(&self) -> Result<Vec<PeerInfo>> {
+            unimplemented!()
+        }
+
+        async fn best_peer_set(&self) -> Result<Vec<PeerInfo>> {
+            unimplemented!()
+        }
+
+        async fn get_peer(&self, _peer_id: &network_api::PeerId) -> Result<Option<PeerInfo>> {
+            unimplemented!()
+        }
+
+        async fn get_self_peer(&self) -> Result<PeerInfo> {
+            unimplemented!()
+        }
+
+        async fn best_peer(&self) -> Result<Option<PeerInfo>> {
+            unimplemented!()
+        }
+
+        async fn get_peer_set_size(&self) -> Result<usize> {
+            unimplemented!()
+        }
+
+        async fn register_rpc_proto(
+            &self,
+            _proto_name: Cow<'static, [u8]>,
+            _rpc_info: RpcInfo,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn report_peer(&self, _peer_id: network_api::PeerId, change: ReputationChange) {
+            self.reports.lock().unwrap().push(change);
+        }
+    }
+
+    fn mock_block(txn_len: usize) -> Block {
+        let txns = (0..txn_len)
+            .map(|_| SignedUserTransaction::mock())
+            .collect::<Vec<_>>();
+        Block::new(BlockHeader::random(), BlockBody::new(txns, None))
+    }
+
+    async fn fill_compact_block(
+        txpool: MockTxPoolService,
+        network: MockNetworkService,
+        compact_block: CompactBlock,
+    ) -> Result<Option<Block>> {
+        BlockRelayer::fill_compact_block(txpool, network, compact_block, PeerId::random()).await
+    }
+
+    #[stest::test]
+    async fn test_report_invalid_prefilled_index() {
+        let block = mock_block(2);
+        let mut compact_block = CompactBlock::new(&block, 1, &[0]);
+        compact_block.prefilled_txn[0].index = 2;
+        let network = MockNetworkService::new(vec![]);
+
+        let result =
+            fill_compact_block(MockTxPoolService::new(), network.clone(), compact_block).await;
+        assert!(result.is_err());
+        assert_eq!(network.reports(), vec![reputation::INVALID_BLOCK]);
+        assert!(network.requests().is_empty());
+    }
+
+    #[stest::test]
+    async fn test_report_mismatched_block_txns() {
+        let block = mock_block(2);
+        let compact_block = CompactBlock::new(&block, 1, &[]);
+        let block_txns = network_rpc::BlockTxns {
+            block_id: block.id(),
+            txns: vec![
+                block.transactions()[0].clone(),
+                SignedUserTransaction::mock(),
+            ],
+        };
+        let network =
+            MockNetworkService::new(vec![("get_block_txns", block_txns.encode().unwrap())]);
+
+        let result =
+            fill_compact_block(MockTxPoolService::new(), network.clone(), compact_block).await;
+        assert!(result.is_err());
+        assert_eq!(network.reports(), vec![reputation::INVALID_BLOCK]);
+        // Do not fallback to fetch the full block from the misbehaving peer.
+        assert_eq!(network.requests(), vec!["get_block_txns".to_string()]);
+    }
+
+    fn block_body_response(block: &Block) -> Vec<u8> {
+        vec![network_rpc::BlockBody {
+            hash: block.id(),
+            transactions: block.transactions().to_vec(),
+            uncles: None,
+        }]
+        .encode()
+        .unwrap()
+    }
+
+    #[stest::test]
+    async fn test_fill_compact_block_from_txpool() {
+        let block = mock_block(3);
+        let compact_block = CompactBlock::new(&block, 1, &[1]);
+        let txpool = MockTxPoolService::new_with_txns(vec![
+            block.transactions()[2].clone(),
+            SignedUserTransaction::mock(),
+            block.transactions()[0].clone(),
+        ]);
+        let network = MockNetworkService::new(vec![]);
+
+        let filled_block = fill_compact_block(txpool, network.clone(), compact_block)
+            .await
+            .unwrap();
+        assert_eq!(filled_block, Some(block));
+        assert!(network.requests().is_empty());
+    }
+
+    #[stest::test]
+    async fn test_fetch_missing_txns() {
+        let block = mock_block(3);
+        let compact_block = CompactBlock::new(&block, 1, &[]);
+        let txpool = MockTxPoolService::new_with_txns(vec![block.transactions()[0].clone()]);
+        let block_txns = network_rpc::BlockTxns {
+            block_id: block.id(),
+            txns: block.transactions()[1..].to_vec(),
+        };
+        let network =
+            MockNetworkService::new(vec![("get_block_txns", block_txns.encode().unwrap())]);
+
+        let filled_block = fill_compact_block(txpool, network.clone(), compact_block)
+            .await
+            .unwrap();
+        assert_eq!(filled_block, Some(block));
+        assert_eq!(network.requests(), vec!["get_block_txns".to_string()]);
+        assert!(network.reports().is_empty());
+    }
+
+    #[stest::test]
+    async fn test_short_id_collision_fallback() {
+        let block = mock_block(2);
+        let mut compact_block = CompactBlock::new(&block, 1, &[]);
+        compact_block.short_ids[1] = compact_block.short_ids[0];
+        let txpool = MockTxPoolService::new_with_txns(block.transactions().to_vec());
+        let network =
+            MockNetworkService::new(vec![("get_body_by_hash", block_body_response(&block))]);
+
+        let filled_block = fill_compact_block(txpool, network.clone(), compact_block)
+            .await
+            .unwrap();
+        assert_eq!(filled_block, Some(block));
+        assert_eq!(network.requests(), vec!["get_body_by_hash".to_string()]);
+        assert!(network.reports().is_empty());
+    }
+
+    #[stest::test]
+    async fn test_full_block_fallback() {
+        let block = mock_block(2);
+        let evicted_txns = network_rpc::BlockTxns {
+            block_id: block.id(),
+            txns: vec![],
+        };
+        // The peer responses less txns than requested, or fails to response.
+        for responses in vec![
+            vec![
+                ("get_block_txns", evicted_txns.encode().unwrap()),
+                ("get_body_by_hash", block_body_response(&block)),
+            ],
+            vec![("get_body_by_hash", block_body_response(&block))],
+        ] {
+            let compact_block = CompactBlock::new(&block, 1, &[]);
+            let network = MockNetworkService::new(responses);
+
+            let filled_block =
+                fill_compact_block(MockTxPoolService::new(), network.clone(), compact_block)
+                    .await
+                    .unwrap();
+            assert_eq!(filled_block, Some(block.clone()));
+            assert_eq!(
+                network.requests(),
+                vec!["get_block_txns".to_string(), "get_body_by_hash".to_string()]
+            );
+            assert!(network.reports().is_empty());
+        }
+    }
+
+    #[stest::test]
+    async fn test_full_block_unavailable() {
+        let block = mock_block(2);
+        let compact_block = CompactBlock::new(&block, 1, &[]);
+        let bodies: Vec<network_rpc::BlockBody> = vec![];
+        let network = MockNetworkService::new(vec![("get_body_by_hash", bodies.encode().unwrap())]);
+
+        let filled_block =
+            fill_compact_block(MockTxPoolService::new(), network.clone(), compact_block)
+                .await
+                .unwrap();
+        assert!(filled_block.is_none());
+        assert!(network.reports().is_empty());
+    }
+}
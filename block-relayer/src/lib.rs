@@ -1,2 +1,3 @@
 mod block_relayer;
+mod metrics;
 pub use block_relayer::BlockRelayer;
@@ -0,0 +1,49 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use once_cell::sync::Lazy;
+use starcoin_metrics::{register_int_counter_vec, IntCounterVec, Opts, PrometheusError};
+
+pub static BLOCK_RELAYER_METRICS: Lazy<BlockRelayerMetrics> =
+    Lazy::new(|| BlockRelayerMetrics::register().unwrap());
+
+#[derive(Clone)]
+pub struct BlockRelayerMetrics {
+    /// bytes of relay data, labeled by compact_block/block_txns/full_block.
+    pub relay_bytes: IntCounterVec,
+    /// count of compact block txns, labeled by how the txn is filled: prefilled/txpool/fetched.
+    pub txns_filled: IntCounterVec,
+    /// count of compact block fill result, labeled by success/fallback/fail.
+    pub fill_results: IntCounterVec,
+}
+
+impl BlockRelayerMetrics {
+    pub fn register() -> Result<Self, PrometheusError> {
+        let relay_bytes = register_int_counter_vec!(
+            Opts::new("block_relay_bytes", "Bytes of block relay data").namespace("starcoin"),
+            &["type"]
+        )?;
+        let txns_filled = register_int_counter_vec!(
+            Opts::new(
+                "block_relay_txns_filled",
+                "Counters of how compact block txns are filled"
+            )
+            .namespace("starcoin"),
+            &["type"]
+        )?;
+        let fill_results = register_int_counter_vec!(
+            Opts::new(
+                "block_relay_fill_results",
+                "Counters of compact block fill results"
+            )
+            .namespace("starcoin"),
+            &["result"]
+        )?;
+
+        Ok(Self {
+            relay_bytes,
+            txns_filled,
+            fill_results,
+        })
+    }
+}
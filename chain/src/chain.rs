@@ -26,7 +26,7 @@ use types::{
     accumulator_info::AccumulatorInfo,
     block::{
         Block, BlockHeader, BlockInfo, BlockNumber, BlockState, BlockTemplate,
-        ALLOWED_FUTURE_BLOCKTIME,
+        ALLOWED_FUTURE_BLOCKTIME, MEDIAN_TIME_PAST_WINDOW,
     },
     contract_event::ContractEvent,
     error::BlockExecutorError,
@@ -139,13 +139,19 @@ where
         user_txns: Vec<SignedUserTransaction>,
         uncles: Vec<BlockHeader>,
     ) -> Result<(BlockTemplate, ExcludedTxns)> {
+        // The block timestamp must not be older than the parent, and must be newer than the
+        // median time past except on dev network, even if the local clock is behind.
+        let mut timestamp = std::cmp::max(get_current_timestamp(), previous_header.timestamp());
+        if !self.config.net().is_dev() {
+            timestamp = std::cmp::max(timestamp, self.median_time_past(previous_header.id())? + 1);
+        }
         let mut opened_block = OpenedBlock::new(
             self.storage.clone(),
             previous_header,
             self.config.miner.block_gas_limit,
             author,
             auth_key_prefix,
-            get_current_timestamp(),
+            timestamp,
             uncles,
         )?;
         let excluded_txns = opened_block.push_txns(user_txns)?;
@@ -153,6 +159,28 @@ where
         Ok((template, excluded_txns))
     }
 
+    /// Median timestamp of the latest `MEDIAN_TIME_PAST_WINDOW` blocks, from `block_id` backwards.
+    pub fn median_time_past(&self, block_id: HashValue) -> Result<u64> {
+        let mut timestamps = Vec::with_capacity(MEDIAN_TIME_PAST_WINDOW as usize);
+        let mut next_id = Some(block_id);
+        while let Some(id) = next_id {
+            if timestamps.len() as u64 >= MEDIAN_TIME_PAST_WINDOW {
+                break;
+            }
+            let header = self
+                .storage
+                .get_block_header_by_hash(id)?
+                .ok_or_else(|| format_err!("Can not find block header by id {:?}", id))?;
+            timestamps.push(header.timestamp());
+            next_id = if header.number() == 0 {
+                None
+            } else {
+                Some(header.parent_hash())
+            };
+        }
+        Ok(median(timestamps))
+    }
+
     pub fn find_block_by_number(&self, number: u64) -> Result<HashValue> {
         self.block_accumulator
             .get_leaf(number)?
@@ -444,6 +472,16 @@ where
                 header.timestamp() <= ALLOWED_FUTURE_BLOCKTIME + now,
                 "Invalid block: block timestamp too new"
             );
+            // Blocks of dev network may be minted in the same second.
+            if !self.config.net().is_dev() {
+                let median_time_past = self.median_time_past(pre_hash)?;
+                ensure!(
+                    header.timestamp() > median_time_past,
+                    "Invalid block: block timestamp {} is not newer than median time past {}",
+                    header.timestamp(),
+                    median_time_past
+                );
+            }
         }
 
         if header.gas_used > header.gas_limit {
@@ -714,3 +752,12 @@ fn verify_txns(txns: &[Transaction], txn_infos: &[TransactionInfo]) -> Result<Co
     }
     Ok(ConnectBlockResult::SUCCESS)
 }
+
+/// The median of `timestamps`, the upper one if the count is even.
+pub fn median(mut timestamps: Vec<u64>) -> u64 {
+    if timestamps.is_empty() {
+        return 0;
+    }
+    timestamps.sort_unstable();
+    timestamps[timestamps.len() / 2]
+}
@@ -6,7 +6,6 @@ use actix::Addr;
 use anyhow::{ensure, format_err, Error, Result};
 use bus::{Broadcast, BusActor};
 use config::NodeConfig;
-use crypto::hash::PlainCryptoHash;
 use crypto::HashValue;
 use logger::prelude::*;
 use scs::SCSCodec;
@@ -620,8 +619,8 @@ where
                 uncles,
             )?;
             // remove invalid txn from txpool
-            for invalid_txn in excluded_txns.discarded_txns {
-                let _ = self.txpool.remove_txn(invalid_txn.crypto_hash(), true);
+            if !excluded_txns.discarded_txns.is_empty() {
+                self.txpool.discard_txns(excluded_txns.discarded_txns);
             }
 
             Ok(block_template)
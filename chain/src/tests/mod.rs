@@ -1,2 +1,3 @@
 mod test_block_chain;
+mod test_chain_service;
 mod test_opened_block;
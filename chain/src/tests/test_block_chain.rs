@@ -1,7 +1,8 @@
+use crate::chain::median;
 use crate::{test_helper, BlockChain, ChainActor, ChainActorRef, ChainAsyncService};
 use anyhow::Result;
 use bus::BusActor;
-use config::NodeConfig;
+use config::{ChainNetwork, NodeConfig};
 use consensus::dev::DevConsensus;
 use crypto::{ed25519::Ed25519PrivateKey, hash::PlainCryptoHash, Genesis, PrivateKey};
 use futures_timer::Delay;
@@ -14,6 +15,7 @@ use storage::{cache_storage::CacheStorage, storage::StorageInstance, Storage};
 use traits::{ChainReader, ChainWriter, ConnectBlockResult, Consensus};
 use txpool::TxPool;
 use types::account_address;
+use types::block::{Block, ALLOWED_FUTURE_BLOCKTIME};
 use types::transaction::authenticator::AuthenticationKey;
 
 async fn gen_master_chain(
@@ -228,3 +230,90 @@ async fn test_chain_apply() -> Result<()> {
     // assert_ne!(header.state_root(), header1.state_root());
     Ok(())
 }
+
+#[stest::test]
+fn test_median() {
+    assert_eq!(median(vec![]), 0);
+    assert_eq!(median(vec![5]), 5);
+    assert_eq!(median(vec![9, 1, 5]), 5);
+    assert_eq!(median(vec![1, 2, 100, 101]), 100);
+}
+
+#[stest::test(timeout = 480)]
+async fn test_block_timestamp_too_new() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let mut block_chain = test_helper::gen_blockchain_for_test::<DevConsensus>(config)?;
+    let miner_account = WalletAccount::random();
+    let (template, _) = block_chain.create_block_template(
+        *miner_account.address(),
+        Some(miner_account.get_auth_key().prefix().to_vec()),
+        None,
+        vec![],
+        vec![],
+    )?;
+    let mut block = DevConsensus::create_block(&block_chain, template)?;
+    block.header.timestamp = get_current_timestamp() + ALLOWED_FUTURE_BLOCKTIME + 60;
+    assert!(block_chain.apply(block).is_err());
+    Ok(())
+}
+
+/// Apply a block on a halley chain, so the median time past is checked, and return the next block
+/// whose timestamp is `next_timestamp(median_time_past)`.
+fn gen_halley_next_block(
+    next_timestamp: impl Fn(u64) -> u64,
+) -> Result<(BlockChain<DevConsensus>, Block)> {
+    let config = Arc::new(NodeConfig::random_for_test_with_net(ChainNetwork::Halley));
+    let mut block_chain = test_helper::gen_blockchain_for_test::<DevConsensus>(config)?;
+    let miner_account = WalletAccount::random();
+    let auth_key_prefix = Some(miner_account.get_auth_key().prefix().to_vec());
+    let (template, _) = block_chain.create_block_template(
+        *miner_account.address(),
+        auth_key_prefix.clone(),
+        None,
+        vec![],
+        vec![],
+    )?;
+    let block = DevConsensus::create_block(&block_chain, template)?;
+    assert_eq!(ConnectBlockResult::SUCCESS, block_chain.apply(block)?);
+
+    let (template, _) = block_chain.create_block_template(
+        *miner_account.address(),
+        auth_key_prefix,
+        None,
+        vec![],
+        vec![],
+    )?;
+    let median_time_past = block_chain.median_time_past(block_chain.current_header().id())?;
+    assert!(
+        template.timestamp > median_time_past,
+        "block template timestamp should be newer than median time past"
+    );
+    let mut block = template.into_block(0, 1.into());
+    block.header.timestamp = next_timestamp(median_time_past);
+    Ok((block_chain, block))
+}
+
+#[stest::test(timeout = 480)]
+fn test_block_timestamp_not_newer_than_median_time_past() -> Result<()> {
+    let (mut block_chain, block) = gen_halley_next_block(|median_time_past| median_time_past)?;
+    let err = block_chain.apply(block).unwrap_err();
+    assert!(
+        err.to_string().contains("median time past"),
+        "unexpected error: {:?}",
+        err
+    );
+    Ok(())
+}
+
+#[stest::test(timeout = 480)]
+fn test_halley_block_timestamp_too_new() -> Result<()> {
+    let (mut block_chain, block) =
+        gen_halley_next_block(|_| get_current_timestamp() + ALLOWED_FUTURE_BLOCKTIME + 60)?;
+    let err = block_chain.apply(block).unwrap_err();
+    assert!(
+        err.to_string().contains("too new"),
+        "unexpected error: {:?}",
+        err
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::ChainServiceImpl;
+use anyhow::Result;
+use bus::BusActor;
+use config::NodeConfig;
+use consensus::dev::DevConsensus;
+use crypto::hash::PlainCryptoHash;
+use starcoin_genesis::Genesis as StarcoinGenesis;
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool_mock_service::MockTxPoolService;
+use starcoin_wallet_api::WalletAccount;
+use std::sync::Arc;
+use storage::{cache_storage::CacheStorage, storage::StorageInstance, Storage};
+use traits::ChainService;
+use types::transaction::SignedUserTransaction;
+
+#[stest::test]
+async fn test_create_block_template_discard_txns() -> Result<()> {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance(
+        CacheStorage::new(),
+    ))?);
+    let genesis = StarcoinGenesis::load(node_config.net())?;
+    let startup_info = genesis.execute_genesis_block(node_config.net(), storage.clone())?;
+    // the sender of a mock txn does not exist on chain, so the txn is discarded by the vm.
+    let discarded_txn = SignedUserTransaction::mock();
+    let txpool = MockTxPoolService::new_with_txns(vec![discarded_txn.clone()]);
+    let chain_service = ChainServiceImpl::<DevConsensus, _>::new(
+        node_config,
+        startup_info,
+        storage,
+        txpool.clone(),
+        BusActor::launch(),
+    )?;
+
+    let miner_account = WalletAccount::random();
+    let block_template = chain_service.create_block_template(
+        *miner_account.address(),
+        Some(miner_account.get_auth_key().prefix().to_vec()),
+        None,
+        vec![discarded_txn.clone()],
+    )?;
+    assert!(block_template.body.transactions().is_empty());
+    assert!(txpool.find_txn(&discarded_txn.crypto_hash()).is_none());
+    Ok(())
+}
@@ -21,6 +21,12 @@ fn partition_nonce(id: u64, total: u64) -> Range<u64> {
     Range { start, end }
 }
 
+/// Nonce of a pool worker, the high 32 bits are the `extranonce` assigned by the pool, the low 32
+/// bits are the high 32 bits of `nonce`, so the threads still search their own nonce partition.
+fn pool_nonce(extranonce: u32, nonce: u64) -> u64 {
+    ((extranonce as u64) << 32) | (nonce >> 32)
+}
+
 fn nonce_generator(range: Range<u64>) -> impl FnMut() -> u64 {
     let mut rng = rand::thread_rng();
     let Range { start, end } = range;
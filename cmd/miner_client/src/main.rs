@@ -1,19 +1,25 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use config::{ConsensusStrategy, MinerConfig};
+use config::{ConsensusStrategy, ConsensusType, MinerConfig};
 use futures::executor;
 use logger::{self, prelude::*};
 use starcoin_miner_client::miner::Miner;
 use structopt::StructOpt;
 
-#[derive(Debug, Clone, StructOpt, Default)]
+#[derive(Debug, Clone, StructOpt)]
 #[structopt(name = "starcoin-miner", about = "Starcoin Miner")]
 pub struct StarcoinOpt {
     #[structopt(long, short = "a", default_value = "0.0.0.0:9940")]
     pub stratum_server: String,
     #[structopt(long, short = "n", default_value = "1")]
     pub thread_num: u16,
+    #[structopt(long, short = "c", default_value = "argon")]
+    /// Consensus of the chain to mine, argon or keccak.
+    pub consensus: ConsensusType,
+    #[structopt(long, short = "w", default_value = "miner")]
+    /// Worker name used to authorize to the stratum server.
+    pub worker_name: String,
 }
 
 fn main() {
@@ -22,7 +28,8 @@ fn main() {
     let config = {
         let mut cfg = MinerConfig::default();
         cfg.enable_stderr = true;
-        cfg.consensus_strategy = ConsensusStrategy::Argon(opts.thread_num);
+        cfg.consensus_strategy = ConsensusStrategy::new(opts.consensus, opts.thread_num);
+        cfg.worker_name = opts.worker_name;
         cfg.stratum_server = opts
             .stratum_server
             .parse()
@@ -14,7 +14,7 @@ use std::thread;
 use types::U256;
 
 pub struct Miner {
-    job_rx: mpsc::UnboundedReceiver<(Vec<u8>, U256)>,
+    job_rx: mpsc::UnboundedReceiver<(Vec<u8>, U256, Option<u32>)>,
     nonce_rx: mpsc::UnboundedReceiver<(Vec<u8>, u64)>,
     worker_controller: WorkerController,
     stratum_client: StratumClient,
@@ -57,8 +57,8 @@ impl Miner {
             debug!("In miner client select loop");
             futures::select! {
                 job = self.job_rx.select_next_some() => {
-                     let (pow_header, diff) = job;
-                     self.start_mint_work(pow_header, diff).await;
+                     let (pow_header, diff, extranonce) = job;
+                     self.start_mint_work(pow_header, diff, extranonce).await;
                 },
                 seal = self.nonce_rx.select_next_some() => {
                      let (pow_header, nonce) = seal;
@@ -95,9 +95,13 @@ impl Miner {
         }
     }
 
-    async fn start_mint_work(&mut self, pow_header: Vec<u8>, diff: U256) {
+    async fn start_mint_work(&mut self, pow_header: Vec<u8>, diff: U256, extranonce: Option<u32>) {
         self.worker_controller
-            .send_message(WorkerMessage::NewWork { pow_header, diff })
+            .send_message(WorkerMessage::NewWork {
+                pow_header,
+                diff,
+                extranonce,
+            })
             .await
     }
 }
@@ -3,7 +3,7 @@
 
 use anyhow::{anyhow, ensure, format_err, Result};
 use async_std::{io::BufReader, net::TcpStream, prelude::*, task};
-use config::MinerConfig;
+use config::{ConsensusStrategy, MinerConfig};
 use futures::channel::mpsc;
 use futures::{SinkExt, StreamExt};
 pub use jsonrpc_core::types::{
@@ -20,6 +20,8 @@ use types::U256;
 pub struct StratumClient {
     request_tx: mpsc::UnboundedSender<Vec<u8>>,
     tcp_stream: Arc<TcpStream>,
+    consensus_strategy: ConsensusStrategy,
+    worker_name: String,
 }
 
 impl StratumClient {
@@ -40,12 +42,17 @@ impl StratumClient {
         Ok(Self {
             request_tx,
             tcp_stream,
+            consensus_strategy: config.consensus_strategy,
+            worker_name: config.worker_name.clone(),
         })
     }
 
-    pub async fn subscribe(&mut self) -> Result<mpsc::UnboundedReceiver<(Vec<u8>, U256)>> {
+    pub async fn subscribe(
+        &mut self,
+    ) -> Result<mpsc::UnboundedReceiver<(Vec<u8>, U256, Option<u32>)>> {
         let (mut job_tx, job_rx) = mpsc::unbounded();
         let tcp_stream = self.tcp_stream.clone();
+        let consensus_strategy = self.consensus_strategy;
         let authed = self.auth(tcp_stream.clone()).await?;
         if !authed {
             return Err(anyhow::anyhow!("Stratum client auth failed"));
@@ -64,7 +71,7 @@ impl StratumClient {
                             continue;
                         };
                         debug!("Receive from stratum: {}", &request);
-                        match process_request(request.as_str()) {
+                        match process_request(request.as_str(), consensus_strategy) {
                             Ok(job) => {
                                 if let Err(e) = job_tx.send(job).await {
                                     error!("stratum subscribe job tx send failed:{:?}", e);
@@ -86,16 +93,17 @@ impl StratumClient {
     }
 
     pub async fn submit_seal(&mut self, seal: (Vec<u8>, u64)) -> Result<()> {
-        let (_pow_header, nonce) = seal;
+        let (pow_header, nonce) = seal;
         let nonce_hex = format!("{:x}", nonce);
-        let params = vec![json!(0), json!(0), json!(nonce_hex)];
+        let job_id = hex::encode(pow_header);
+        let params = vec![json!(self.worker_name), json!(job_id), json!(nonce_hex)];
         let method = "mining.submit".to_owned();
         self.request(method, params, 0).await?;
         Ok(())
     }
 
     async fn auth(&mut self, tcp_stream: Arc<TcpStream>) -> Result<bool> {
-        let params = vec![json!("miner"), json!("")];
+        let params = vec![json!(self.worker_name), json!("")];
         let method = "mining.authorize".to_owned();
         self.request(method, params, 0).await?;
         let mut auth_response = String::new();
@@ -148,12 +156,29 @@ pub(crate) fn parse_response<T: serde::de::DeserializeOwned>(
     }
 }
 
-pub(crate) fn process_request(req: &str) -> Result<(Vec<u8>, U256)> {
+pub(crate) fn process_request(
+    req: &str,
+    consensus_strategy: ConsensusStrategy,
+) -> Result<(Vec<u8>, U256, Option<u32>)> {
     let value = serde_json::from_str::<Value>(req).map_err(StratumError::Json)?;
     let request = serde_json::from_value::<MethodCall>(value).map_err(StratumError::Json)?;
     let params: Params = request.params.parse()?;
     if let Params::Array(values) = params {
-        ensure!(values.len() == 2, "Invalid mint request params");
+        ensure!(
+            values.len() >= 2 && values.len() <= 4,
+            "Invalid mint request params"
+        );
+        if let Some(strategy) = values.get(2) {
+            let strategy = strategy
+                .as_str()
+                .ok_or_else(|| format_err!("Invalid consensus field in request"))?;
+            ensure!(
+                strategy == consensus_strategy.to_string(),
+                "Mint job consensus {} mismatch with local consensus {}",
+                strategy,
+                consensus_strategy
+            );
+        }
         let header = values[0]
             .as_str()
             .ok_or_else(|| format_err!("Invalid header field in request"))
@@ -170,7 +195,20 @@ pub(crate) fn process_request(req: &str) -> Result<(Vec<u8>, U256)> {
                     .parse::<U256>()
                     .map_err(|e| anyhow!(e.to_string()))
             })?;
-        return Ok((header, difficulty));
+        // The high 32 bits of nonce assigned by pool.
+        let extranonce = match values.get(3) {
+            Some(extranonce) => Some(
+                extranonce
+                    .as_str()
+                    .ok_or_else(|| format_err!("Invalid extranonce field in request"))
+                    .and_then(|e| {
+                        u32::from_str_radix(e, 16)
+                            .map_err(|_| anyhow!("Invalid extranonce field with bad hex encode"))
+                    })?,
+            ),
+            None => None,
+        };
+        return Ok((header, difficulty, extranonce));
     }
     Err(anyhow!("mining.notify with bad params"))
 }
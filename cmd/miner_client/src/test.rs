@@ -6,8 +6,8 @@ use crate::stratum::{parse_response, process_request};
 use actix::Actor;
 use actix_rt::System;
 use bus::BusActor;
-use config::MinerConfig;
 use config::NodeConfig;
+use config::{ConsensusStrategy, MinerConfig};
 use consensus::argon::ArgonConsensus;
 use futures_timer::Delay;
 use logger::prelude::*;
@@ -27,8 +27,10 @@ fn test_stratum_client() {
     let mut system = System::new("test");
     system.block_on(async {
         let mut miner_config = MinerConfig::default();
-        miner_config.consensus_strategy = config::ConsensusStrategy::Argon(4);
-        let conf = Arc::new(NodeConfig::random_for_test());
+        miner_config.consensus_strategy = ConsensusStrategy::Argon(4);
+        let mut conf = NodeConfig::random_for_test();
+        conf.miner.consensus_strategy = miner_config.consensus_strategy;
+        let conf = Arc::new(conf);
         let mut miner = Miner::<ArgonConsensus>::new(BusActor::launch(), conf);
         let stratum = {
             let dispatcher = Arc::new(StratumManager::new(miner.clone()));
@@ -64,6 +66,46 @@ fn test_json() {
     let json_str = r#"
     { "id": 19, "method": "mining.notify", "params": ["e419ff9f57cc615f1b9ee900097f6ce34ad5eaff61eda78414efa1c3fa9e8200","1"] }
     "#;
-    let result = process_request(json_str);
+    let result = process_request(json_str, ConsensusStrategy::Argon(1));
     assert!(result.is_ok(), "process request fail:{:?}", result.err());
+
+    let json_str = r#"
+    { "id": 19, "method": "mining.notify", "params": ["e419ff9f57cc615f1b9ee900097f6ce34ad5eaff61eda78414efa1c3fa9e8200","1","keccak"] }
+    "#;
+    let result = process_request(json_str, ConsensusStrategy::Keccak(1));
+    assert!(result.is_ok(), "process request fail:{:?}", result.err());
+    let result = process_request(json_str, ConsensusStrategy::Argon(1));
+    assert!(result.is_err(), "consensus mismatch should fail");
+
+    let json_str = r#"
+    { "id": 19, "method": "mining.notify", "params": ["e419ff9f57cc615f1b9ee900097f6ce34ad5eaff61eda78414efa1c3fa9e8200","1","keccak","0000000a"] }
+    "#;
+    let (_, _, extranonce) = process_request(json_str, ConsensusStrategy::Keccak(1)).unwrap();
+    assert_eq!(extranonce, Some(10));
+}
+
+#[test]
+fn test_pool_nonce_keep_thread_partition() {
+    let extranonce = 0x1234_5678;
+    let thread_num = 4;
+    let ranges: Vec<(u64, u64)> = (0..thread_num)
+        .map(|i| {
+            let range = crate::partition_nonce(i, thread_num);
+            (
+                crate::pool_nonce(extranonce, range.start),
+                crate::pool_nonce(extranonce, range.end - 1),
+            )
+        })
+        .collect();
+    for (i, (start, end)) in ranges.iter().enumerate() {
+        assert_eq!(extranonce, (start >> 32) as u32);
+        assert_eq!(extranonce, (end >> 32) as u32);
+        assert!(start <= end);
+        if i > 0 {
+            assert!(
+                ranges[i - 1].1 < *start,
+                "nonce partitions of threads overlap"
+            );
+        }
+    }
 }
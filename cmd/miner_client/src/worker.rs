@@ -1,9 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{nonce_generator, partition_nonce};
+use crate::{nonce_generator, partition_nonce, pool_nonce};
 use config::{ConsensusStrategy, MinerConfig};
-use consensus::{argon, dev, difficulty::difficult_to_target, dummy};
+use consensus::{argon, dev, difficulty::difficult_to_target, dummy, keccak};
 use futures::channel::mpsc;
 use futures::executor::block_on;
 use futures::SinkExt;
@@ -12,21 +12,29 @@ use logger::prelude::*;
 use std::thread;
 use std::time::{Duration, Instant};
 use traits::Consensus;
-use types::U256;
+use types::{H256, U256};
 
 const HASH_RATE_UPDATE_DURATION_MILLIS: u128 = 300;
 
+type Solver = fn(&[u8], u64, U256, mpsc::UnboundedSender<(Vec<u8>, u64)>) -> bool;
+
 pub fn start_worker(
     config: &MinerConfig,
     nonce_tx: mpsc::UnboundedSender<(Vec<u8>, u64)>,
     mp: Option<&MultiProgress>,
 ) -> WorkerController {
     match config.consensus_strategy {
-        ConsensusStrategy::Argon(thread_num) => {
+        ConsensusStrategy::Argon(_) | ConsensusStrategy::Keccak(_) => {
+            let strategy = config.consensus_strategy;
+            let (thread_num, solver): (u16, Solver) = match strategy {
+                ConsensusStrategy::Argon(thread_num) => (thread_num, argon_solver),
+                ConsensusStrategy::Keccak(thread_num) => (thread_num, keccak_solver),
+                _ => unreachable!("Unsupported consensus {:?}", strategy),
+            };
             let worker_txs = (0..thread_num)
                 .map(|i| {
                     let (worker_tx, worker_rx) = mpsc::unbounded();
-                    let worker_name = format!("starcoin-miner-argon-cpu-worker-{}", i);
+                    let worker_name = format!("starcoin-miner-{}-cpu-worker-{}", strategy, i);
                     let pb = if let Some(mp) = mp {
                         let pb = mp.add(ProgressBar::new(100));
                         pb.set_style(ProgressStyle::default_bar().template(
@@ -44,7 +52,7 @@ pub fn start_worker(
                         .spawn(move || {
                             let mut worker = Worker::new(worker_rx, nonce_tx_clone);
                             let rng = nonce_generator(nonce_range);
-                            worker.run(rng, argon_solver, pb);
+                            worker.run(rng, solver, pb);
                         })
                         .expect("Start worker thread failed");
                     info!("start mine worker: {:?}", worker_name);
@@ -89,7 +97,11 @@ pub fn start_worker(
 #[derive(Clone)]
 pub enum WorkerMessage {
     Stop,
-    NewWork { pow_header: Vec<u8>, diff: U256 },
+    NewWork {
+        pow_header: Vec<u8>,
+        diff: U256,
+        extranonce: Option<u32>,
+    },
 }
 
 pub struct WorkerController {
@@ -115,6 +127,7 @@ pub struct Worker {
     worker_rx: mpsc::UnboundedReceiver<WorkerMessage>,
     diff: U256,
     pow_header: Option<Vec<u8>>,
+    extranonce: Option<u32>,
     start: bool,
     num_seal_found: u64,
 }
@@ -129,6 +142,7 @@ impl Worker {
             worker_rx,
             diff: 1.into(),
             pow_header: None,
+            extranonce: None,
             start: false,
             num_seal_found: 0,
         }
@@ -163,7 +177,11 @@ impl Worker {
                         }
                         start = Instant::now();
                         hash_counter = 0;
-                        if solver(&pow_header, rng(), self.diff, self.nonce_tx.clone()) {
+                        let nonce = match self.extranonce {
+                            Some(extranonce) => pool_nonce(extranonce, rng()),
+                            None => rng(),
+                        };
+                        if solver(&pow_header, nonce, self.diff, self.nonce_tx.clone()) {
                             self.start = false;
                             self.num_seal_found += 1;
                             if let Some(pb) = pb {
@@ -184,9 +202,14 @@ impl Worker {
         if let Ok(msg) = self.worker_rx.try_next() {
             if let Some(msg) = msg {
                 match msg {
-                    WorkerMessage::NewWork { pow_header, diff } => {
+                    WorkerMessage::NewWork {
+                        pow_header,
+                        diff,
+                        extranonce,
+                    } => {
                         self.pow_header = Some(pow_header);
                         self.diff = diff;
+                        self.extranonce = extranonce;
                         self.start = true;
                     }
                     WorkerMessage::Stop => {
@@ -199,13 +222,32 @@ impl Worker {
 }
 
 fn argon_solver(
+    pow_header: &[u8],
+    nonce: u64,
+    diff: U256,
+    nonce_tx: mpsc::UnboundedSender<(Vec<u8>, u64)>,
+) -> bool {
+    hash_solver(argon::calculate_hash, pow_header, nonce, diff, nonce_tx)
+}
+
+fn keccak_solver(
+    pow_header: &[u8],
+    nonce: u64,
+    diff: U256,
+    nonce_tx: mpsc::UnboundedSender<(Vec<u8>, u64)>,
+) -> bool {
+    hash_solver(keccak::calculate_hash, pow_header, nonce, diff, nonce_tx)
+}
+
+fn hash_solver<H: Fn(&[u8]) -> anyhow::Result<H256>>(
+    calculate_hash: H,
     pow_header: &[u8],
     nonce: u64,
     diff: U256,
     mut nonce_tx: mpsc::UnboundedSender<(Vec<u8>, u64)>,
 ) -> bool {
     let input = consensus::set_header_nonce(pow_header, nonce);
-    if let Ok(pow_hash) = argon::calculate_hash(&input) {
+    if let Ok(pow_hash) = calculate_hash(&input) {
         let pow_hash_u256: U256 = pow_hash.into();
         let target = difficult_to_target(diff);
         if pow_hash_u256 <= target {
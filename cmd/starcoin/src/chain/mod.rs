@@ -11,6 +11,7 @@ mod get_txn_cmd;
 mod get_txn_info_cmd;
 mod list_block_cmd;
 mod show_cmd;
+mod vm_config_cmd;
 
 pub use branchs_cmd::*;
 pub use epoch_info::*;
@@ -22,3 +23,4 @@ pub use get_txn_cmd::*;
 pub use get_txn_info_cmd::*;
 pub use list_block_cmd::*;
 pub use show_cmd::*;
+pub use vm_config_cmd::*;
@@ -0,0 +1,33 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::VMConfigView;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+/// Show the on-chain VM config of master, including the publishing option and gas schedule.
+/// The whitelist of a locked publishing option is changed by the `add_to_script_whitelist` and
+/// `remove_from_script_whitelist` scripts, chains started from the committed generated genesis
+/// need `dev upgrade_stdlib` first.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "vm-config")]
+pub struct VMConfigOpt {}
+
+pub struct VMConfigCommand;
+
+impl CommandAction for VMConfigCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = VMConfigOpt;
+    type ReturnItem = VMConfigView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        Ok(ctx.state().vm_config()?.into())
+    }
+}
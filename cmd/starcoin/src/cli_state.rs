@@ -7,7 +7,8 @@ use starcoin_node::NodeHandle;
 use starcoin_rpc_api::types::pubsub::ThinBlock;
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
-use starcoin_vm_types::account_config::association_address;
+use starcoin_vm_types::account_config::{association_address, genesis_address};
+use starcoin_vm_types::on_chain_config::{OnChainConfig, VMConfig};
 use starcoin_wallet_api::WalletAccount;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -99,6 +100,19 @@ impl CliState {
         self.client.wallet_get(association_address())
     }
 
+    pub fn genesis_account(&self) -> Result<Option<WalletAccount>> {
+        self.client.wallet_get(genesis_address())
+    }
+
+    /// Get the on-chain VMConfig of master.
+    pub fn vm_config(&self) -> Result<VMConfig> {
+        let bytes = self
+            .client
+            .state_get(VMConfig::CONFIG_ID.access_path())?
+            .ok_or_else(|| format_err!("VMConfig resource not exist."))?;
+        VMConfig::deserialize_into_config(bytes.as_slice())
+    }
+
     pub fn watch_txn(&self, txn_hash: HashValue) -> Result<ThinBlock> {
         let block = self
             .client
@@ -4,12 +4,16 @@
 use crate::cli_state::CliState;
 use crate::view::StringView;
 use crate::StarcoinOpt;
-use anyhow::{bail, Result};
+use anyhow::{bail, format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_move_compiler::command_line::parse_address;
 use starcoin_move_compiler::shared::Address;
-use starcoin_move_compiler::{compile_source_string_no_report, errors};
+use starcoin_move_compiler::{
+    compile_source_string_no_report, errors, script_parameter_names, CompiledUnit,
+};
 use starcoin_types::account_address::AccountAddress;
+use starcoin_vm_types::abi::{ModuleABI, ScriptABI, ABI_EXTENSION};
+use starcoin_vm_types::file_format::{CompiledModule, CompiledScript};
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -32,6 +36,10 @@ pub struct CompileOpt {
     #[structopt(short = "o", name = "out_dir", help = "out dir", parse(from_os_str))]
     out_dir: Option<PathBuf>,
 
+    #[structopt(long = "abi")]
+    /// also write the JSON ABI of the module or script to the out dir
+    abi: bool,
+
     #[structopt(name = "source", help = "source file path")]
     source_file: String,
 }
@@ -66,10 +74,9 @@ impl CommandAction for CompileCommand {
         let mut deps = stdlib::stdlib_files();
         // add extra deps
         deps.append(&mut ctx.opt().deps.clone());
+        let source = std::fs::read_to_string(source_file_path).expect("read file error");
         let (sources, compile_result) = compile_source_string_no_report(
-            std::fs::read_to_string(source_file_path)
-                .expect("read file error")
-                .as_str(),
+            source.as_str(),
             &deps,
             AccountAddress::new(sender.to_u8()),
         )?;
@@ -94,9 +101,29 @@ impl CommandAction for CompileCommand {
 
         txn_path.push(source_file_path.file_name().unwrap());
         txn_path.set_extension(stdlib::STAGED_EXTENSION);
+        let bytecode = compile_unit.serialize();
         let mut file = File::create(txn_path.clone()).expect("unable create out file");
-        file.write_all(&compile_unit.serialize())
-            .expect("write out file error");
+        file.write_all(&bytecode).expect("write out file error");
+
+        if ctx.opt().abi {
+            let abi_path = txn_path.with_extension(ABI_EXTENSION);
+            let abi_file = File::create(abi_path.as_path())?;
+            match compile_unit {
+                CompiledUnit::Module { .. } => {
+                    let module = CompiledModule::deserialize(bytecode.as_slice())
+                        .map_err(|e| format_err!("Deserialize module fail: {:?}", e))?;
+                    serde_json::to_writer_pretty(abi_file, &ModuleABI::new(&module))?;
+                }
+                CompiledUnit::Script { .. } => {
+                    let script = CompiledScript::deserialize(bytecode.as_slice())
+                        .map_err(|e| format_err!("Deserialize script fail: {:?}", e))?;
+                    let abi = ScriptABI::new(&script)
+                        .with_parameter_names(script_parameter_names(source.as_str())?)?;
+                    serde_json::to_writer_pretty(abi_file, &abi)?;
+                }
+            }
+            println!("abi file: {}", abi_path.display());
+        }
         Ok(StringView {
             result: txn_path.to_str().unwrap().to_string(),
         })
@@ -6,11 +6,12 @@ use crate::view::{ExecuteResultView, ExecutionOutputView};
 use crate::StarcoinOpt;
 use anyhow::{bail, format_err, Result};
 use scmd::{CommandAction, ExecContext};
+use serde_json::Value;
 use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_move_compiler::shared::Address;
 use starcoin_move_compiler::{
     command_line::parse_address, compile_source_string_no_report, errors, load_bytecode_file,
-    CompiledUnit, MOVE_EXTENSION,
+    script_parameter_names, CompiledUnit, MOVE_EXTENSION,
 };
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
@@ -19,10 +20,13 @@ use starcoin_types::transaction::{
     parse_transaction_argument, Module, RawUserTransaction, Script, TransactionArgument,
 };
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::abi::ScriptABI;
+use starcoin_vm_types::file_format::CompiledScript;
 use starcoin_vm_types::transaction::helpers::get_current_timestamp;
 use starcoin_vm_types::transaction::Transaction;
 use starcoin_vm_types::vm_status::KeptVMStatus;
 use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
+use std::fs::File;
 use std::path::PathBuf;
 use structopt::StructOpt;
 
@@ -45,6 +49,16 @@ pub struct ExecuteOpt {
     #[structopt(long = "arg", name = "transaction-args", help = "can specify multi arg", parse(try_from_str = parse_transaction_argument))]
     args: Vec<TransactionArgument>,
 
+    #[structopt(long = "args-json", name = "json-args", conflicts_with = "transaction-args", parse(try_from_str = serde_json::from_str))]
+    /// script arguments as a JSON object by parameter name, or a JSON array in order,
+    /// type checked and converted by the script ABI
+    json_args: Option<Value>,
+
+    #[structopt(long = "abi", parse(from_os_str))]
+    /// the JSON ABI file of the script, generated by `dev compile --abi`. If absent, the ABI is
+    /// generated from the script, the parameter names are from the source or `arg0`, `arg1`, ...
+    abi_file: Option<PathBuf>,
+
     #[structopt(
         name = "expiration_time",
         long = "timeout",
@@ -119,15 +133,15 @@ impl CommandAction for ExecuteCommand {
             .extension()
             .map(|os_str| os_str.to_str().expect("file extension should is utf8 str"))
             .unwrap_or_else(|| "");
+        let mut source = None;
         let (bytecode, is_script) = if ext == MOVE_EXTENSION {
             let mut deps = stdlib::stdlib_files();
             // add extra deps
             deps.append(&mut ctx.opt().deps.clone());
-            let (sources, compile_result) = compile_source_string_no_report(
-                std::fs::read_to_string(move_file_path.as_path())?.as_str(),
-                &deps,
-                sender,
-            )?;
+            let script_source = std::fs::read_to_string(move_file_path.as_path())?;
+            let (sources, compile_result) =
+                compile_source_string_no_report(script_source.as_str(), &deps, sender)?;
+            source = Some(script_source);
             let compile_unit = match compile_result {
                 Ok(c) => c,
                 Err(e) => {
@@ -151,7 +165,36 @@ impl CommandAction for ExecuteCommand {
         };
 
         let type_tags = opt.type_tags.clone();
-        let args = opt.args.clone();
+        let args = match opt.json_args.as_ref() {
+            Some(json_args) => {
+                if !is_script {
+                    bail!("args-json is only supported by script.");
+                }
+                let abi: ScriptABI = match opt.abi_file.as_ref() {
+                    Some(abi_file) => serde_json::from_reader(File::open(abi_file)?)?,
+                    None => {
+                        let script = CompiledScript::deserialize(bytecode.as_slice())
+                            .map_err(|e| format_err!("Deserialize script fail: {:?}", e))?;
+                        let abi = ScriptABI::new(&script);
+                        match source.as_ref() {
+                            Some(source) => {
+                                abi.with_parameter_names(script_parameter_names(source.as_str())?)?
+                            }
+                            None => abi,
+                        }
+                    }
+                };
+                if abi.type_parameters.len() != type_tags.len() {
+                    bail!(
+                        "Expect {} type tags, but got {}.",
+                        abi.type_parameters.len(),
+                        type_tags.len()
+                    );
+                }
+                abi.encode_args(json_args)?
+            }
+            None => opt.args.clone(),
+        };
 
         let client = ctx.state().client();
         let chain_state_reader = RemoteStateReader::new(client);
@@ -0,0 +1,192 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::GasScheduleDiffView;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::hash::{HashValue, PlainCryptoHash};
+use starcoin_rpc_client::RemoteStateReader;
+use starcoin_state_api::AccountStateReader;
+use starcoin_transaction_builder::encode_set_gas_schedule_script_by_cost_table;
+use starcoin_types::transaction::RawUserTransaction;
+use starcoin_vm_types::gas_schedule::CostTable;
+use starcoin_vm_types::transaction::helpers::get_current_timestamp;
+use starcoin_vm_types::transaction::TransactionPayload;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+fn read_gas_schedule(path: &Path) -> Result<CostTable> {
+    let file = File::open(path)
+        .map_err(|e| format_err!("Open gas schedule file {:?} fail: {}", path, e))?;
+    Ok(serde_json::from_reader(file)?)
+}
+
+/// Export the on-chain gas schedule of master as JSON.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export")]
+pub struct GasScheduleExportOpt {
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    /// write the gas schedule to the file, which can be edited as a proposal
+    output: Option<PathBuf>,
+}
+
+pub struct GasScheduleExportCommand;
+
+impl CommandAction for GasScheduleExportCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GasScheduleExportOpt;
+    type ReturnItem = CostTable;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let gas_schedule = ctx.state().vm_config()?.gas_schedule;
+        if let Some(output) = ctx.opt().output.as_ref() {
+            serde_json::to_writer_pretty(File::create(output)?, &gas_schedule)?;
+        }
+        Ok(gas_schedule)
+    }
+}
+
+/// Diff the on-chain gas schedule of master with the proposed gas schedule.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "diff")]
+pub struct GasScheduleDiffOpt {
+    #[structopt(name = "proposal", parse(from_os_str))]
+    /// the JSON file of proposed gas schedule
+    proposal: PathBuf,
+}
+
+pub struct GasScheduleDiffCommand;
+
+impl CommandAction for GasScheduleDiffCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GasScheduleDiffOpt;
+    type ReturnItem = GasScheduleDiffView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let proposed = read_gas_schedule(ctx.opt().proposal.as_path())?;
+        let current = ctx.state().vm_config()?.gas_schedule;
+        Ok(GasScheduleDiffView::new(current, proposed))
+    }
+}
+
+/// Propose the gas schedule by the genesis account, the instruction and native tables of
+/// on-chain gas schedule will be replaced. Chains started from the committed generated genesis
+/// need `dev upgrade_stdlib` first.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "propose")]
+pub struct GasScheduleProposeOpt {
+    #[structopt(name = "proposal", parse(from_os_str))]
+    /// the JSON file of proposed gas schedule
+    proposal: PathBuf,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "1000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct GasScheduleProposeCommand;
+
+impl CommandAction for GasScheduleProposeCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GasScheduleProposeOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let cli_state = ctx.state();
+        let client = cli_state.client();
+        let genesis_account = match cli_state.genesis_account()? {
+            Some(genesis_account) => genesis_account,
+            None => {
+                bail!("genesis_account not exists in wallet, please import genesis_account first.")
+            }
+        };
+        let proposed = read_gas_schedule(opt.proposal.as_path())?;
+        let current = cli_state.vm_config()?.gas_schedule;
+        ensure!(
+            proposed.instruction_table.len() == current.instruction_table.len(),
+            "The proposed instruction table size {} is not equal to the current {}.",
+            proposed.instruction_table.len(),
+            current.instruction_table.len()
+        );
+        ensure!(
+            proposed.native_table.len() == current.native_table.len(),
+            "The proposed native table size {} is not equal to the current {}.",
+            proposed.native_table.len(),
+            current.native_table.len()
+        );
+        ensure!(
+            proposed.gas_constants == current.gas_constants,
+            "The gas constants can not be changed by the proposal."
+        );
+
+        let chain_state_reader = RemoteStateReader::new(client);
+        let account_state_reader = AccountStateReader::new(&chain_state_reader);
+        let account_resource = account_state_reader
+            .get_account_resource(genesis_account.address())?
+            .ok_or_else(|| format_err!("genesis_account must exist on chain."))?;
+        let expiration_time = opt.expiration_time + get_current_timestamp();
+        let txn = RawUserTransaction::new(
+            genesis_account.address,
+            account_resource.sequence_number(),
+            TransactionPayload::Script(encode_set_gas_schedule_script_by_cost_table(&proposed)?),
+            opt.max_gas_amount,
+            opt.gas_price,
+            expiration_time,
+        );
+        let signed_txn = client.wallet_sign_txn(txn)?;
+        let txn_hash = signed_txn.crypto_hash();
+        let success = client.submit_transaction(signed_txn)?;
+        if let Err(e) = success {
+            bail!("execute-txn is reject by node, reason: {}", &e)
+        }
+        println!("txn {:#x} submitted.", txn_hash);
+
+        if opt.blocking {
+            ctx.state().watch_txn(txn_hash)?;
+        }
+        Ok(txn_hash)
+    }
+}
@@ -5,8 +5,10 @@ mod compile_cmd;
 mod deploy_cmd;
 mod derive_account_address_cmd;
 mod execute_cmd;
+mod gas_schedule_cmd;
 mod generate_multisig_txn_cmd;
 mod get_coin_cmd;
+mod module_upgrade_cmd;
 mod submit_multisig_txn_cmd;
 mod subscribe_cmd;
 mod upgrade_stdlib_cmd;
@@ -15,8 +17,10 @@ pub use compile_cmd::*;
 pub use deploy_cmd::*;
 pub use derive_account_address_cmd::*;
 pub use execute_cmd::*;
+pub use gas_schedule_cmd::*;
 pub use generate_multisig_txn_cmd::*;
 pub use get_coin_cmd::*;
+pub use module_upgrade_cmd::*;
 pub use submit_multisig_txn_cmd::*;
 pub use subscribe_cmd::*;
 pub use upgrade_stdlib_cmd::*;
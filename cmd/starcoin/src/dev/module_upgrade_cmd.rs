@@ -0,0 +1,508 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::{UpgradePlanItemView, UpgradePlanView};
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::hash::{HashValue, PlainCryptoHash};
+use starcoin_move_compiler::{check_compat_and_verify_module, load_bytecode_file};
+use starcoin_rpc_client::{RemoteStateReader, RpcClient};
+use starcoin_state_api::AccountStateReader;
+use starcoin_transaction_builder::{
+    encode_cancel_upgrade_plan_script, encode_submit_upgrade_plan_script_by_package,
+    encode_update_module_upgrade_strategy_script,
+};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{
+    ModuleUpgradeStrategy, ModuleUpgradeStrategyResource, TwoPhaseUpgradeResource, UpgradePlan,
+};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::transaction::{Module, Package, RawUserTransaction, TransactionPayload};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::transaction::helpers::get_current_timestamp;
+use starcoin_vm_types::{access::ModuleAccess, file_format::CompiledModule};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Load the module bytecode files as a package, all modules must be at the same address.
+fn load_package(bytecode_files: &[PathBuf]) -> Result<Package> {
+    ensure!(
+        !bytecode_files.is_empty(),
+        "At least one module bytecode file is required."
+    );
+    let mut modules = vec![];
+    for bytecode_file in bytecode_files {
+        let (bytecode, is_script) = load_bytecode_file(bytecode_file)?;
+        ensure!(
+            !is_script,
+            "Bytecode file {:?} is a script, not a module.",
+            bytecode_file
+        );
+        modules.push(Module::new(bytecode));
+    }
+    Package::new_with_modules(modules)
+}
+
+/// Check every module of the package is compatible with the on-chain module of master, the same
+/// check as the VM does when deploy the package.
+fn check_package_compat(client: &RpcClient, package: &Package) -> Result<()> {
+    for module in package.modules() {
+        let compiled_module = CompiledModule::deserialize(module.code())
+            .map_err(|e| format_err!("Invalid module bytecode: {:?}", e))?;
+        let module_id = compiled_module.self_id();
+        if let Some(pre_code) = client.state_get(AccessPath::code_access_path(&module_id))? {
+            check_compat_and_verify_module(pre_code.as_slice(), module.code()).map_err(|e| {
+                format_err!(
+                    "Module {} is not compatible with on chain: {}",
+                    module_id,
+                    e
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn get_current_block_number(client: &RpcClient) -> Result<BlockNumber> {
+    let head = client.chain_head()?;
+    Ok(client
+        .chain_get_block_by_hash(*head.get_head())?
+        .header()
+        .number())
+}
+
+fn get_upgrade_plan(client: &RpcClient, address: AccountAddress) -> Result<Option<UpgradePlan>> {
+    let chain_state_reader = RemoteStateReader::new(client);
+    let account_state_reader = AccountStateReader::new(&chain_state_reader);
+    Ok(account_state_reader
+        .get_resource::<TwoPhaseUpgradeResource>(address)?
+        .and_then(|resource| resource.plan().cloned()))
+}
+
+fn submit_txn(
+    ctx_state: &CliState,
+    sender: AccountAddress,
+    payload: TransactionPayload,
+    max_gas_amount: u64,
+    gas_price: u64,
+    expiration_time: u64,
+    blocking: bool,
+) -> Result<HashValue> {
+    let client = ctx_state.client();
+    let sender = ctx_state.wallet_account_or_default(Some(sender))?.address;
+    let chain_state_reader = RemoteStateReader::new(client);
+    let account_state_reader = AccountStateReader::new(&chain_state_reader);
+    let account_resource = account_state_reader
+        .get_account_resource(&sender)?
+        .ok_or_else(|| format_err!("account of address {} not exists on chain", sender))?;
+    let txn = RawUserTransaction::new(
+        sender,
+        account_resource.sequence_number(),
+        payload,
+        max_gas_amount,
+        gas_price,
+        expiration_time + get_current_timestamp(),
+    );
+    let signed_txn = client.wallet_sign_txn(txn)?;
+    let txn_hash = signed_txn.crypto_hash();
+    let succ = client.submit_transaction(signed_txn)?;
+    if let Err(e) = succ {
+        bail!("execute-txn is reject by node, reason: {}", &e)
+    }
+    println!("txn {:#x} submitted.", txn_hash);
+
+    if blocking {
+        ctx_state.watch_txn(txn_hash)?;
+    }
+    Ok(txn_hash)
+}
+
+/// Update the module upgrade strategy of the sender's code space, the strategy can only be raised.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "set-strategy")]
+pub struct SetUpgradeStrategyOpt {
+    #[structopt(short = "s", long)]
+    /// the account address of the code space, if absent, use default account.
+    sender: Option<AccountAddress>,
+
+    #[structopt(
+        name = "strategy",
+        possible_values = &["arbitrary", "two-phase", "new-module", "freeze"]
+    )]
+    /// the module upgrade strategy
+    strategy: ModuleUpgradeStrategy,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "1000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct SetUpgradeStrategyCommand;
+
+impl CommandAction for SetUpgradeStrategyCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SetUpgradeStrategyOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let sender = ctx.state().wallet_account_or_default(opt.sender)?.address;
+        let chain_state_reader = RemoteStateReader::new(ctx.state().client());
+        let current = AccountStateReader::new(&chain_state_reader)
+            .get_resource::<ModuleUpgradeStrategyResource>(sender)?
+            .map(|resource| resource.strategy())
+            .transpose()?
+            .unwrap_or_default();
+        ensure!(
+            opt.strategy > current,
+            "The module upgrade strategy can only be raised, current strategy is {}.",
+            current
+        );
+        submit_txn(
+            ctx.state(),
+            sender,
+            TransactionPayload::Script(encode_update_module_upgrade_strategy_script(
+                opt.strategy.into(),
+            )),
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time,
+            opt.blocking,
+        )
+    }
+}
+
+/// Submit the upgrade plan of a package, the package can be deployed once the block height
+/// reaches `active-after-height`. The package is checked to be compatible with the on-chain
+/// modules before submission.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "submit-plan")]
+pub struct SubmitUpgradePlanOpt {
+    #[structopt(short = "s", long)]
+    /// the account to sign the txn, the plan is submitted to its code space, if absent, use the
+    /// package address.
+    sender: Option<AccountAddress>,
+
+    #[structopt(short = "a", long = "active-after-height")]
+    /// the block height after which the package can be deployed
+    active_after_height: BlockNumber,
+
+    #[structopt(name = "bytecode_files", parse(from_os_str), required = true)]
+    /// module bytecode files of the package
+    bytecode_files: Vec<PathBuf>,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "1000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct SubmitUpgradePlanCommand;
+
+impl CommandAction for SubmitUpgradePlanCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubmitUpgradePlanOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let package = load_package(opt.bytecode_files.as_slice())?;
+        check_package_compat(client, &package)?;
+        let current_block_number = get_current_block_number(client)?;
+        ensure!(
+            opt.active_after_height >= current_block_number,
+            "active-after-height {} is less than current block number {}.",
+            opt.active_after_height,
+            current_block_number
+        );
+        let sender = opt.sender.unwrap_or_else(|| package.package_address());
+        if let Some(plan) = get_upgrade_plan(client, sender)? {
+            bail!(
+                "An upgrade plan of package {} already exists, cancel it first.",
+                plan.package_hash()?
+            );
+        }
+        println!("package hash: {}", package.crypto_hash());
+        submit_txn(
+            ctx.state(),
+            sender,
+            TransactionPayload::Script(encode_submit_upgrade_plan_script_by_package(
+                &package,
+                opt.active_after_height,
+            )),
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time,
+            opt.blocking,
+        )
+    }
+}
+
+/// Cancel the pending upgrade plan of the sender's code space.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "cancel-plan")]
+pub struct CancelUpgradePlanOpt {
+    #[structopt(short = "s", long)]
+    /// the account address of the code space, if absent, use default account.
+    sender: Option<AccountAddress>,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "1000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct CancelUpgradePlanCommand;
+
+impl CommandAction for CancelUpgradePlanCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = CancelUpgradePlanOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let sender = ctx.state().wallet_account_or_default(opt.sender)?.address;
+        if get_upgrade_plan(ctx.state().client(), sender)?.is_none() {
+            bail!("No upgrade plan of {} exists.", sender);
+        }
+        submit_txn(
+            ctx.state(),
+            sender,
+            TransactionPayload::Script(encode_cancel_upgrade_plan_script()),
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time,
+            opt.blocking,
+        )
+    }
+}
+
+/// Show the module upgrade strategy and the pending upgrade plan of a code space.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "show-plan")]
+pub struct ShowUpgradePlanOpt {
+    #[structopt(name = "module_address")]
+    /// the account address of the code space, if absent, use default account.
+    module_address: Option<AccountAddress>,
+}
+
+pub struct ShowUpgradePlanCommand;
+
+impl CommandAction for ShowUpgradePlanCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ShowUpgradePlanOpt;
+    type ReturnItem = UpgradePlanView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let module_address = match ctx.opt().module_address {
+            Some(module_address) => module_address,
+            None => ctx.state().default_account()?.address,
+        };
+        let chain_state_reader = RemoteStateReader::new(client);
+        let strategy = AccountStateReader::new(&chain_state_reader)
+            .get_resource::<ModuleUpgradeStrategyResource>(module_address)?
+            .map(|resource| resource.strategy())
+            .transpose()?
+            .unwrap_or_default();
+        let current_block_number = get_current_block_number(client)?;
+        let plan = match get_upgrade_plan(client, module_address)? {
+            Some(plan) => Some(UpgradePlanItemView {
+                package_hash: plan.package_hash()?,
+                active_after_height: plan.active_after_height(),
+                active: plan.is_active(current_block_number),
+            }),
+            None => None,
+        };
+        Ok(UpgradePlanView {
+            module_address,
+            strategy,
+            plan,
+            current_block_number,
+        })
+    }
+}
+
+/// Deploy the package of the upgrade plan, the plan must be active.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "deploy")]
+pub struct DeployUpgradeOpt {
+    #[structopt(short = "s", long)]
+    /// the account to sign the txn, must be the module maintainer of the package, if absent, use
+    /// the package address.
+    sender: Option<AccountAddress>,
+
+    #[structopt(name = "bytecode_files", parse(from_os_str), required = true)]
+    /// module bytecode files of the package, must be the same as submitted in the plan
+    bytecode_files: Vec<PathBuf>,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "1000000",
+        help = "max gas used to deploy the package"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to deploy the package"
+    )]
+    gas_price: u64,
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+}
+
+pub struct DeployUpgradeCommand;
+
+impl CommandAction for DeployUpgradeCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = DeployUpgradeOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let package = load_package(opt.bytecode_files.as_slice())?;
+        let package_address = package.package_address();
+        let plan = get_upgrade_plan(client, package_address)?
+            .ok_or_else(|| format_err!("No upgrade plan of {} exists.", package_address))?;
+        let package_hash = package.crypto_hash();
+        ensure!(
+            plan.package_hash()? == package_hash,
+            "Package hash {} is not the same as the upgrade plan {}.",
+            package_hash,
+            plan.package_hash()?
+        );
+        let current_block_number = get_current_block_number(client)?;
+        ensure!(
+            plan.is_active(current_block_number),
+            "The upgrade plan is active after height {}, current block number is {}.",
+            plan.active_after_height(),
+            current_block_number
+        );
+        check_package_compat(client, &package)?;
+        submit_txn(
+            ctx.state(),
+            opt.sender.unwrap_or(package_address),
+            TransactionPayload::Package(package),
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time,
+            opt.blocking,
+        )
+    }
+}
@@ -38,13 +38,15 @@ pub fn add_command(
                 .subcommand(state::GetCommand)
                 .subcommand(state::GetAccountCommand)
                 .subcommand(state::GetProofCommand)
-                .subcommand(state::GetRootCommand),
+                .subcommand(state::GetRootCommand)
+                .subcommand(state::GetModuleABICommand),
         )
         .command(
             Command::with_name("node")
                 .subcommand(node::InfoCommand)
                 .subcommand(node::PeersCommand)
-                .subcommand(node::MetricsCommand),
+                .subcommand(node::MetricsCommand)
+                .subcommand(node::SyncStatusCommand),
         )
         .command(
             Command::with_name("chain")
@@ -57,7 +59,8 @@ pub fn add_command(
                 .subcommand(chain::GetEventsCommand)
                 .subcommand(chain::GetBlockCommand)
                 .subcommand(chain::BranchesCommand)
-                .subcommand(chain::EpochInfoCommand),
+                .subcommand(chain::EpochInfoCommand)
+                .subcommand(chain::VMConfigCommand),
         )
         .command(
             Command::with_name("dev")
@@ -69,6 +72,20 @@ pub fn add_command(
                 .subcommand(dev::GenerateMultisigTxnCommand)
                 .subcommand(dev::ExecuteMultiSignedTxnCommand)
                 .subcommand(dev::UpgradeStdlibCommand)
+                .subcommand(
+                    Command::with_name("gas-schedule")
+                        .subcommand(dev::GasScheduleExportCommand)
+                        .subcommand(dev::GasScheduleDiffCommand)
+                        .subcommand(dev::GasScheduleProposeCommand),
+                )
+                .subcommand(
+                    Command::with_name("module-upgrade")
+                        .subcommand(dev::SetUpgradeStrategyCommand)
+                        .subcommand(dev::SubmitUpgradePlanCommand)
+                        .subcommand(dev::CancelUpgradePlanCommand)
+                        .subcommand(dev::ShowUpgradePlanCommand)
+                        .subcommand(dev::DeployUpgradeCommand),
+                )
                 .subcommand(
                     Command::with_name("subscribe")
                         .subcommand(dev::SubscribeBlockCommand)
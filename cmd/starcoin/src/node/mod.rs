@@ -4,7 +4,9 @@
 mod info_cmd;
 mod metrics_cmd;
 mod peers_cmd;
+mod sync_status_cmd;
 
 pub use info_cmd::*;
 pub use metrics_cmd::*;
 pub use peers_cmd::*;
+pub use sync_status_cmd::*;
@@ -6,6 +6,7 @@ use crate::view::AccountView;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
 use starcoin_types::account_address::AccountAddress;
@@ -16,6 +17,14 @@ use structopt::StructOpt;
 pub struct GetOpt {
     #[structopt(name = "account_address")]
     account_address: AccountAddress,
+
+    #[structopt(long = "block-id", conflicts_with = "state-root")]
+    /// get the state of the block, default is the head block
+    block_id: Option<HashValue>,
+
+    #[structopt(long = "state-root", name = "state-root")]
+    /// get the state at the state root
+    state_root: Option<HashValue>,
 }
 
 pub struct GetAccountCommand;
@@ -32,7 +41,19 @@ impl CommandAction for GetAccountCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        let chain_state_reader = RemoteStateReader::new(client);
+        let state_root = match opt.block_id {
+            Some(block_id) => Some(
+                client
+                    .chain_get_block_by_hash(block_id)?
+                    .header()
+                    .state_root(),
+            ),
+            None => opt.state_root,
+        };
+        let chain_state_reader = match state_root {
+            Some(state_root) => RemoteStateReader::new_with_root(client, state_root),
+            None => RemoteStateReader::new(client),
+        };
         let account_state_reader = AccountStateReader::new(&chain_state_reader);
         let sequence_number = account_state_reader
             .get_account_resource(&opt.account_address)?
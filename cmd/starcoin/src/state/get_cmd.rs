@@ -5,6 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::AccountResource;
@@ -17,6 +18,14 @@ use structopt::StructOpt;
 pub struct GetOpt {
     #[structopt(name = "account_address")]
     account_address: AccountAddress,
+
+    #[structopt(long = "block-id", conflicts_with = "state-root")]
+    /// get the state of the block, default is the head block
+    block_id: Option<HashValue>,
+
+    #[structopt(long = "state-root", name = "state-root")]
+    /// get the state at the state root
+    state_root: Option<HashValue>,
 }
 
 pub struct GetCommand;
@@ -34,10 +43,11 @@ impl CommandAction for GetCommand {
         let client = ctx.state().client();
         let opt = ctx.opt();
         let state = client
-            .state_get(AccessPath::new(
-                opt.account_address,
-                AccountResource::resource_path(),
-            ))?
+            .state_get_at(
+                AccessPath::new(opt.account_address, AccountResource::resource_path()),
+                opt.block_id,
+                opt.state_root,
+            )?
             .ok_or_else(|| {
                 format_err!(
                     "Account with address {} state not exist.",
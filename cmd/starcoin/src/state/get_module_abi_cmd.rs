@@ -0,0 +1,56 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_vm_types::abi::ModuleABI;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use structopt::StructOpt;
+
+fn parse_module_id(s: &str) -> Result<ModuleId> {
+    let parts: Vec<_> = s.split("::").collect();
+    if parts.len() != 2 {
+        return Err(format_err!("Invalid module id {}, expect address::name", s));
+    }
+    let address = if parts[0].starts_with("0x") {
+        parts[0].to_string()
+    } else {
+        format!("0x{}", parts[0])
+    };
+    Ok(ModuleId::new(
+        AccountAddress::from_hex_literal(address.as_str())?,
+        Identifier::new(parts[1])?,
+    ))
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "get_module_abi")]
+pub struct GetModuleABIOpt {
+    #[structopt(name = "module_id", parse(try_from_str = parse_module_id))]
+    /// the module id, like 0x1::Account
+    module_id: ModuleId,
+}
+
+pub struct GetModuleABICommand;
+
+impl CommandAction for GetModuleABICommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GetModuleABIOpt;
+    type ReturnItem = ModuleABI;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let module_id = ctx.opt().module_id.clone();
+        ctx.state()
+            .client()
+            .state_get_module_abi(module_id.clone())?
+            .ok_or_else(|| format_err!("Module {} not exists on chain.", module_id))
+    }
+}
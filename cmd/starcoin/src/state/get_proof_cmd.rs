@@ -6,6 +6,7 @@ use crate::view::StateWithProofView;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::AccountResource;
@@ -18,6 +19,14 @@ use structopt::StructOpt;
 pub struct GetOpt {
     #[structopt(name = "account_address")]
     account_address: AccountAddress,
+
+    #[structopt(long = "block-id", conflicts_with = "state-root")]
+    /// get the state of the block, default is the head block
+    block_id: Option<HashValue>,
+
+    #[structopt(long = "state-root", name = "state-root")]
+    /// get the state at the state root
+    state_root: Option<HashValue>,
 }
 
 pub struct GetProofCommand;
@@ -34,10 +43,11 @@ impl CommandAction for GetProofCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        let proof = client.state_get_with_proof(AccessPath::new(
-            opt.account_address,
-            AccountResource::resource_path(),
-        ))?;
+        let proof = client.state_get_with_proof_at(
+            AccessPath::new(opt.account_address, AccountResource::resource_path()),
+            opt.block_id,
+            opt.state_root,
+        )?;
 
         Ok(proof.into())
     }
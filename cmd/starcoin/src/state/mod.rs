@@ -3,10 +3,12 @@
 
 mod get_account_cmd;
 mod get_cmd;
+mod get_module_abi_cmd;
 mod get_proof_cmd;
 mod get_root_cmd;
 
 pub use get_account_cmd::*;
 pub use get_cmd::*;
+pub use get_module_abi_cmd::*;
 pub use get_proof_cmd::*;
 pub use get_root_cmd::*;
@@ -8,20 +8,25 @@ use starcoin_config::ChainNetwork;
 use starcoin_crypto::{hash::PlainCryptoHash, HashValue};
 use starcoin_rpc_api::node::NodeInfo;
 use starcoin_state_api::StateWithProof;
-use starcoin_types::account_config::{MintEvent, ReceivedPaymentEvent, SentPaymentEvent};
-use starcoin_types::block::{Block, BlockHeader};
+use starcoin_types::account_config::{
+    MintEvent, ModuleUpgradeStrategy, ReceivedPaymentEvent, SentPaymentEvent,
+};
+use starcoin_types::block::{Block, BlockHeader, BlockNumber};
 use starcoin_types::contract_event::ContractEvent;
 use starcoin_types::language_storage::TypeTag;
 use starcoin_types::peer_info::{PeerId, PeerInfo};
 use starcoin_types::transaction::{TransactionInfo, TransactionStatus};
 use starcoin_types::{account_address::AccountAddress, transaction::SignedUserTransaction, U256};
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::gas_schedule::{CostTable, GasCost};
 use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_config::{VMConfig, VMPublishingOption};
 use starcoin_vm_types::transaction::TransactionOutput;
 use starcoin_vm_types::vm_status::KeptVMStatus;
 use starcoin_vm_types::write_set::WriteOp;
 use starcoin_wallet_api::WalletAccount;
 use std::collections::HashMap;
+use stdlib::transaction_scripts::StdlibScript;
 
 //TODO add a derive to auto generate View Object
 
@@ -394,3 +399,125 @@ where
 {
     s.serialize_str(&hex::encode(bytes))
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VMConfigView {
+    pub publishing_option: PublishingOptionView,
+    pub gas_schedule: CostTable,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PublishingOptionView {
+    /// Only allow scripts on the whitelist to be run
+    Locked(Vec<WhitelistScriptView>),
+    CustomScripts,
+    Open,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WhitelistScriptView {
+    pub hash: HashValue,
+    /// The name of the stdlib script with the hash, if any.
+    pub stdlib_script: Option<String>,
+}
+
+impl From<VMConfig> for VMConfigView {
+    fn from(vm_config: VMConfig) -> Self {
+        let publishing_option = match vm_config.publishing_option {
+            VMPublishingOption::Locked(whitelist) => {
+                let stdlib_hashes = StdlibScript::hashes();
+                PublishingOptionView::Locked(
+                    whitelist
+                        .into_iter()
+                        .map(|hash| {
+                            let hash = HashValue::new(hash);
+                            let stdlib_script = stdlib_hashes
+                                .iter()
+                                .find(|(_, script_hash)| script_hash == &hash)
+                                .map(|(script, _)| script.name());
+                            WhitelistScriptView {
+                                hash,
+                                stdlib_script,
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            VMPublishingOption::CustomScripts => PublishingOptionView::CustomScripts,
+            VMPublishingOption::Open => PublishingOptionView::Open,
+        };
+        Self {
+            publishing_option,
+            gas_schedule: vm_config.gas_schedule,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasScheduleDiffView {
+    pub current: CostTable,
+    pub proposed: CostTable,
+    /// The costs which are different between the current and proposed table.
+    pub changes: Vec<GasCostChangeView>,
+    pub gas_constants_changed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasCostChangeView {
+    /// `instruction` or `native`
+    pub table: String,
+    pub index: usize,
+    pub current: Option<GasCost>,
+    pub proposed: Option<GasCost>,
+}
+
+impl GasScheduleDiffView {
+    pub fn new(current: CostTable, proposed: CostTable) -> Self {
+        let mut changes = vec![];
+        for (table, current_table, proposed_table) in &[
+            (
+                "instruction",
+                &current.instruction_table,
+                &proposed.instruction_table,
+            ),
+            ("native", &current.native_table, &proposed.native_table),
+        ] {
+            for index in 0..current_table.len().max(proposed_table.len()) {
+                let current_cost = current_table.get(index);
+                let proposed_cost = proposed_table.get(index);
+                if current_cost != proposed_cost {
+                    changes.push(GasCostChangeView {
+                        table: table.to_string(),
+                        index,
+                        current: current_cost.cloned(),
+                        proposed: proposed_cost.cloned(),
+                    });
+                }
+            }
+        }
+        let gas_constants_changed = current.gas_constants != proposed.gas_constants;
+        Self {
+            current,
+            proposed,
+            changes,
+            gas_constants_changed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpgradePlanView {
+    pub module_address: AccountAddress,
+    pub strategy: ModuleUpgradeStrategy,
+    /// The pending upgrade plan, only exists when the strategy is two phase upgrade.
+    pub plan: Option<UpgradePlanItemView>,
+    pub current_block_number: BlockNumber,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpgradePlanItemView {
+    pub package_hash: HashValue,
+    pub active_after_height: u64,
+    /// Whether the package can be deployed at the current block.
+    pub active: bool,
+}
@@ -7,6 +7,9 @@ use std::path::PathBuf;
 #[serde(default, deny_unknown_fields)]
 pub struct AccountVaultConfig {
     dir: PathBuf,
+    /// Remote signer endpoint, a unix socket file path or a http address,
+    /// accounts without local private key are signed by the remote signer.
+    pub remote_signer: Option<String>,
     #[serde(skip)]
     absolute_dir: Option<PathBuf>,
 }
@@ -30,6 +33,7 @@ impl ConfigModule for AccountVaultConfig {
     fn default_with_net(_net: ChainNetwork) -> Self {
         Self {
             dir: PathBuf::from("account_vaults"),
+            remote_signer: None,
             absolute_dir: None,
         }
     }
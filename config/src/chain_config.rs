@@ -153,6 +153,37 @@ impl Default for ChainNetwork {
     }
 }
 
+/// The proof of work algorithm used by a ChainNetwork.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+pub enum ConsensusType {
+    Dev,
+    Argon,
+    Keccak,
+}
+
+impl Display for ConsensusType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsensusType::Dev => write!(f, "dev"),
+            ConsensusType::Argon => write!(f, "argon"),
+            ConsensusType::Keccak => write!(f, "keccak"),
+        }
+    }
+}
+
+impl FromStr for ConsensusType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dev" => Ok(ConsensusType::Dev),
+            "argon" => Ok(ConsensusType::Argon),
+            "keccak" => Ok(ConsensusType::Keccak),
+            _ => Err(format_err!("Unknown consensus type: {}", s)),
+        }
+    }
+}
+
 /// ChainConfig is a static hard code config.
 #[derive(Debug)]
 pub struct ChainConfig {
@@ -170,6 +201,8 @@ pub struct ChainConfig {
     pub difficulty: U256,
     /// Genesis consensus nonce.
     pub nonce: u64,
+    /// Proof of work algorithm of the chain.
+    pub consensus: ConsensusType,
     /// pre mine to Association account, percent of total_supply, from 0~100.
     pub pre_mine_percent: u64,
     /// VM config for publishing_option and gas_schedule
@@ -222,6 +255,7 @@ pub static DEV_CHAIN_CONFIG: Lazy<ChainConfig> = Lazy::new(|| {
         reward_delay: 1,
         difficulty: 1.into(),
         nonce: 0,
+        consensus: ConsensusType::Dev,
         pre_mine_percent: 20,
         vm_config: VMConfig {
             publishing_option: VMPublishingOption::Open,
@@ -250,6 +284,7 @@ pub static HALLEY_CHAIN_CONFIG: Lazy<ChainConfig> = Lazy::new(|| {
         reward_delay: 3,
         difficulty: 10.into(),
         nonce: 0,
+        consensus: ConsensusType::Argon,
         pre_mine_percent: 20,
         vm_config: VMConfig {
             publishing_option: VMPublishingOption::Open,
@@ -283,6 +318,7 @@ pub static PROXIMA_CHAIN_CONFIG: Lazy<ChainConfig> = Lazy::new(|| {
         reward_delay: 7,
         difficulty: 10.into(),
         nonce: 0,
+        consensus: ConsensusType::Argon,
         pre_mine_percent: 20,
         vm_config: VMConfig {
             publishing_option: VMPublishingOption::Open,
@@ -315,6 +351,7 @@ pub static MAIN_CHAIN_CONFIG: Lazy<ChainConfig> = Lazy::new(|| ChainConfig {
     reward_delay: 7,
     difficulty: 10.into(),
     nonce: 0,
+    consensus: ConsensusType::Argon,
     pre_mine_percent: 0,
     vm_config: VMConfig {
         publishing_option: VMPublishingOption::Open,
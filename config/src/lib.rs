@@ -40,8 +40,8 @@ pub use available_port::{
 };
 
 pub use chain_config::{
-    genesis_key_pair, ChainConfig, ChainNetwork, DEV_CHAIN_CONFIG, HALLEY_CHAIN_CONFIG,
-    MAIN_CHAIN_CONFIG, PROXIMA_CHAIN_CONFIG,
+    genesis_key_pair, ChainConfig, ChainNetwork, ConsensusType, DEV_CHAIN_CONFIG,
+    HALLEY_CHAIN_CONFIG, MAIN_CHAIN_CONFIG, PROXIMA_CHAIN_CONFIG,
 };
 pub use libra_temppath::TempPath;
 pub use logger_config::LoggerConfig;
@@ -219,6 +219,10 @@ impl BaseConfig {
                 }
             }
         };
+        Self::new_with_base_data_dir(net, base_data_dir)
+    }
+
+    fn new_with_base_data_dir(net: ChainNetwork, base_data_dir: DataDirPath) -> Self {
         let data_dir = base_data_dir.as_ref().join(net.to_string());
         if !data_dir.exists() {
             create_dir_all(data_dir.as_path())
@@ -241,7 +245,12 @@ impl BaseConfig {
         self.base_data_dir.as_ref()
     }
     pub fn random_for_test() -> Self {
-        Self::new(ChainNetwork::Dev, None)
+        Self::random_for_test_with_net(ChainNetwork::Dev)
+    }
+
+    /// Base config of `net` with a temp data dir.
+    pub fn random_for_test_with_net(net: ChainNetwork) -> Self {
+        Self::new_with_base_data_dir(net, temp_path())
     }
 }
 
@@ -291,12 +300,33 @@ pub struct NodeConfig {
 
 impl NodeConfig {
     pub fn random_for_test() -> Self {
-        let mut config = NodeConfig::default_with_net(ChainNetwork::Dev);
-        let base = BaseConfig::random_for_test();
+        Self::random_for_test_with_net(ChainNetwork::Dev)
+    }
+
+    /// Random config of `net` for test, the data dir is a temp dir even if `net` is not dev.
+    pub fn random_for_test_with_net(net: ChainNetwork) -> Self {
+        let base = BaseConfig::random_for_test_with_net(net);
+        let mut config = NodeConfig::default_with_base(base.clone());
         config.random(&base);
         config
     }
 
+    fn default_with_base(base: BaseConfig) -> Self {
+        let net = base.net;
+        Self {
+            base,
+            network: NetworkConfig::default_with_net(net),
+            rpc: RpcConfig::default_with_net(net),
+            miner: MinerConfig::default_with_net(net),
+            storage: StorageConfig::default_with_net(net),
+            tx_pool: TxPoolConfig::default_with_net(net),
+            sync: SyncConfig::default_with_net(net),
+            vault: AccountVaultConfig::default_with_net(net),
+            metrics: MetricsConfig::default_with_net(net),
+            logger: LoggerConfig::default_with_net(net),
+        }
+    }
+
     pub fn load_with_opt(opt: &StarcoinOpt) -> Result<Self> {
         let base = BaseConfig::new(opt.net.unwrap_or_default(), opt.data_dir.clone());
         let data_dir = base.data_dir();
@@ -343,19 +373,7 @@ impl Default for NodeConfig {
 
 impl ConfigModule for NodeConfig {
     fn default_with_net(net: ChainNetwork) -> Self {
-        let base = BaseConfig::new(net, None);
-        Self {
-            base,
-            network: NetworkConfig::default_with_net(net),
-            rpc: RpcConfig::default_with_net(net),
-            miner: MinerConfig::default_with_net(net),
-            storage: StorageConfig::default_with_net(net),
-            tx_pool: TxPoolConfig::default_with_net(net),
-            sync: SyncConfig::default_with_net(net),
-            vault: AccountVaultConfig::default_with_net(net),
-            metrics: MetricsConfig::default_with_net(net),
-            logger: LoggerConfig::default_with_net(net),
-        }
+        Self::default_with_base(BaseConfig::new(net, None))
     }
 
     fn random(&mut self, base: &BaseConfig) {
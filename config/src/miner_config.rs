@@ -3,7 +3,7 @@
 
 use crate::{
     get_available_port_from, get_random_available_port, BaseConfig, ChainNetwork, ConfigModule,
-    StarcoinOpt,
+    ConsensusType, StarcoinOpt,
 };
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -22,8 +22,14 @@ pub struct MinerConfig {
     #[serde(skip)]
     pub enable_stderr: bool,
     pub block_gas_limit: u64,
+    /// Txns with gas price lower than this are not packed into blocks.
+    pub min_gas_price: u64,
+    /// Difficulty of the shares submitted by stratum workers, use block difficulty if absent.
+    pub share_difficulty: Option<u64>,
     #[serde(skip)]
     pub consensus_strategy: ConsensusStrategy,
+    #[serde(skip)]
+    pub worker_name: String,
 }
 
 impl Default for MinerConfig {
@@ -36,26 +42,36 @@ impl Default for MinerConfig {
 #[serde(tag = "type")]
 pub enum ConsensusStrategy {
     Argon(u16),
+    Keccak(u16),
     Dev,
     Dummy,
 }
 
+impl ConsensusStrategy {
+    /// The strategy to mine the chain with `consensus`, using `thread_num` threads.
+    pub fn new(consensus: ConsensusType, thread_num: u16) -> Self {
+        match consensus {
+            ConsensusType::Dev => ConsensusStrategy::Dev,
+            ConsensusType::Argon => ConsensusStrategy::Argon(thread_num),
+            ConsensusType::Keccak => ConsensusStrategy::Keccak(thread_num),
+        }
+    }
+}
+
 impl fmt::Display for ConsensusStrategy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ConsensusStrategy::Dummy => write!(f, "dummy"),
             ConsensusStrategy::Dev => write!(f, "dev"),
             ConsensusStrategy::Argon(_) => write!(f, "argon"),
+            ConsensusStrategy::Keccak(_) => write!(f, "keccak"),
         }
     }
 }
 
 impl ConfigModule for MinerConfig {
     fn default_with_net(net: ChainNetwork) -> Self {
-        let consensus_strategy = match net {
-            ChainNetwork::Dev => ConsensusStrategy::Dev,
-            _ => ConsensusStrategy::Argon(1),
-        };
+        let consensus_strategy = ConsensusStrategy::new(net.get_config().consensus, 1);
         let port = match net {
             ChainNetwork::Dev => get_available_port_from(DEFAULT_STRATUM_SERVER_PORT),
             _ => DEFAULT_STRATUM_SERVER_PORT,
@@ -73,7 +89,10 @@ impl ConfigModule for MinerConfig {
             enable_mint_empty_block: true,
             enable_stderr: false,
             block_gas_limit,
+            min_gas_price: 0,
+            share_difficulty: None,
             consensus_strategy,
+            worker_name: "miner".to_owned(),
         }
     }
 
@@ -91,14 +110,13 @@ impl ConfigModule for MinerConfig {
             .as_ref()
             .cloned()
             .unwrap_or_else(|| base.net.is_dev());
-        if base.net.is_dev() {
-            self.consensus_strategy = ConsensusStrategy::Dev;
-        } else {
+        if !base.net.is_dev() {
             if let Some(thread_num) = opt.miner_thread {
                 self.thread_num = thread_num;
             }
-            self.consensus_strategy = ConsensusStrategy::Argon(self.thread_num);
         }
+        self.consensus_strategy =
+            ConsensusStrategy::new(base.net.get_config().consensus, self.thread_num);
 
         if opt.disable_miner_client {
             self.enable_miner_client = false;
@@ -0,0 +1,438 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Simulate the difficulty adjustment of `get_next_work_required` with synthetic hash rate,
+//! without running real nodes.
+
+use anyhow::{format_err, Result};
+use crypto::HashValue;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use starcoin_config::ChainConfig;
+use starcoin_consensus::difficulty::{get_next_work_required, target_to_difficulty};
+use starcoin_state_api::ChainStateReader;
+use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::event::{EventHandle, EventKey};
+use starcoin_vm_types::on_chain_config::{
+    Consensus as ConsensusConfig, EpochDataResource, EpochInfo, EpochResource,
+};
+use std::collections::HashMap;
+use std::io::Write;
+use traits::{ChainReader, ExcludedTxns};
+use types::{
+    account_address::AccountAddress,
+    block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
+    transaction::{SignedUserTransaction, Transaction, TransactionInfo},
+    U256,
+};
+
+/// Hash rate of the whole network, in hashes per second.
+#[derive(Clone, Debug)]
+pub enum HashRateProfile {
+    Constant(f64),
+    /// Hash rate changes from `from` to `to` at block `at`.
+    Step {
+        from: f64,
+        to: f64,
+        at: BlockNumber,
+    },
+    /// Miners join and leave every `period` blocks, the hash rate switches between `base` and `peak`.
+    Oscillate {
+        base: f64,
+        peak: f64,
+        period: BlockNumber,
+    },
+}
+
+impl HashRateProfile {
+    pub fn hash_rate(&self, number: BlockNumber) -> f64 {
+        match self {
+            HashRateProfile::Constant(rate) => *rate,
+            HashRateProfile::Step { from, to, at } => {
+                if number < *at {
+                    *from
+                } else {
+                    *to
+                }
+            }
+            HashRateProfile::Oscillate { base, peak, period } => {
+                if (number / (*period).max(1)) % 2 == 0 {
+                    *base
+                } else {
+                    *peak
+                }
+            }
+        }
+    }
+}
+
+/// A miner with `share` of the hash rate, sets its block timestamp `future_offset` seconds ahead.
+#[derive(Clone, Debug)]
+pub struct TimestampAttack {
+    pub share: f64,
+    pub future_offset: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SimulatorConfig {
+    pub genesis_difficulty: U256,
+    pub block_time_target: u64,
+    pub block_difficulty_window: u64,
+    pub blocks: u64,
+    pub profile: HashRateProfile,
+    pub attack: Option<TimestampAttack>,
+    pub seed: u64,
+}
+
+impl SimulatorConfig {
+    pub fn new(chain_config: &ChainConfig, blocks: u64, profile: HashRateProfile) -> Self {
+        Self {
+            genesis_difficulty: chain_config.difficulty,
+            block_time_target: chain_config.init_block_time_target,
+            block_difficulty_window: chain_config.block_difficulty_window,
+            blocks,
+            profile,
+            attack: None,
+            seed: 0,
+        }
+    }
+}
+
+/// One simulated block.
+#[derive(Clone, Debug)]
+pub struct SimulatedBlock {
+    pub number: BlockNumber,
+    /// Real time when the block is found, in seconds.
+    pub time: f64,
+    pub timestamp: u64,
+    /// Header timestamp minus parent header timestamp.
+    pub block_time: u64,
+    pub difficulty: U256,
+    pub hash_rate: f64,
+    pub by_attacker: bool,
+}
+
+/// A chain only keeps headers, enough for `get_next_work_required`.
+pub struct MockChain {
+    headers: HashMap<HashValue, BlockHeader>,
+    head: BlockHeader,
+    block_time_target: u64,
+    block_difficulty_window: u64,
+}
+
+impl MockChain {
+    pub fn new(genesis_difficulty: U256, block_time_target: u64, window: u64) -> Self {
+        let genesis = Self::new_header(HashValue::zero(), 0, 0, genesis_difficulty);
+        let mut headers = HashMap::new();
+        headers.insert(genesis.id(), genesis.clone());
+        Self {
+            headers,
+            head: genesis,
+            block_time_target,
+            block_difficulty_window: window,
+        }
+    }
+
+    fn new_header(
+        parent_hash: HashValue,
+        number: BlockNumber,
+        timestamp: u64,
+        difficulty: U256,
+    ) -> BlockHeader {
+        BlockHeader::new(
+            parent_hash,
+            HashValue::zero(),
+            timestamp,
+            number,
+            AccountAddress::ZERO,
+            HashValue::zero(),
+            HashValue::zero(),
+            0,
+            0,
+            difficulty,
+            0,
+            None,
+        )
+    }
+
+    /// Difficulty of the next block.
+    pub fn next_difficulty(&self) -> Result<U256> {
+        Ok(target_to_difficulty(get_next_work_required(self)?))
+    }
+
+    pub fn append(&mut self, timestamp: u64, difficulty: U256) -> BlockHeader {
+        let header = Self::new_header(self.head.id(), self.head.number + 1, timestamp, difficulty);
+        self.headers.insert(header.id(), header.clone());
+        self.head = header.clone();
+        header
+    }
+}
+
+impl ChainReader for MockChain {
+    fn head_block(&self) -> Block {
+        unimplemented!()
+    }
+
+    fn current_header(&self) -> BlockHeader {
+        self.head.clone()
+    }
+
+    fn get_header(&self, hash: HashValue) -> Result<Option<BlockHeader>> {
+        Ok(self.headers.get(&hash).cloned())
+    }
+
+    fn get_header_by_number(&self, _number: BlockNumber) -> Result<Option<BlockHeader>> {
+        unimplemented!()
+    }
+
+    fn get_block_by_number(&self, _number: BlockNumber) -> Result<Option<Block>> {
+        unimplemented!()
+    }
+
+    fn get_blocks_by_number(
+        &self,
+        _number: Option<BlockNumber>,
+        _count: u64,
+    ) -> Result<Vec<Block>> {
+        unimplemented!()
+    }
+
+    fn get_block(&self, _hash: HashValue) -> Result<Option<Block>> {
+        unimplemented!()
+    }
+
+    fn get_transaction(&self, _hash: HashValue) -> Result<Option<Transaction>> {
+        unimplemented!()
+    }
+
+    fn get_transaction_info(&self, _txn_hash: HashValue) -> Result<Option<TransactionInfo>> {
+        unimplemented!()
+    }
+
+    fn get_latest_block_by_uncle(
+        &self,
+        _uncle_id: HashValue,
+        _times: u64,
+    ) -> Result<Option<Block>> {
+        unimplemented!()
+    }
+
+    fn get_transaction_info_by_version(&self, _version: u64) -> Result<Option<TransactionInfo>> {
+        unimplemented!()
+    }
+
+    fn create_block_template(
+        &self,
+        _author: AccountAddress,
+        _auth_key_prefix: Option<Vec<u8>>,
+        _parent_hash: Option<HashValue>,
+        _user_txns: Vec<SignedUserTransaction>,
+        _uncles: Vec<BlockHeader>,
+    ) -> Result<(BlockTemplate, ExcludedTxns)> {
+        unimplemented!()
+    }
+
+    fn chain_state_reader(&self) -> &dyn ChainStateReader {
+        unimplemented!()
+    }
+
+    fn get_block_info(&self, _block_id: Option<HashValue>) -> Result<Option<BlockInfo>> {
+        unimplemented!()
+    }
+
+    fn get_total_difficulty(&self) -> Result<U256> {
+        unimplemented!()
+    }
+
+    fn exist_block(&self, block_id: HashValue) -> bool {
+        self.headers.contains_key(&block_id)
+    }
+
+    /// A single endless epoch.
+    fn epoch_info(&self) -> Result<EpochInfo> {
+        let epoch = EpochResource::new(
+            0,
+            0,
+            0,
+            u64::max_value(),
+            self.block_time_target,
+            0,
+            0,
+            EventHandle::new(EventKey::new_from_address(&genesis_address(), 0), 0),
+        );
+        let consensus = ConsensusConfig {
+            uncle_rate_target: 0,
+            epoch_time_target: 0,
+            reward_half_epoch: 0,
+            block_difficulty_window: self.block_difficulty_window,
+            reward_per_uncle_percent: 0,
+            min_time_target: 0,
+            max_uncles_per_block: 0,
+        };
+        Ok(EpochInfo::new(
+            &epoch,
+            EpochDataResource::new(0, 0),
+            &consensus,
+        ))
+    }
+}
+
+/// Run the simulation, return the blocks after genesis.
+pub fn simulate(config: &SimulatorConfig) -> Result<Vec<SimulatedBlock>> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut chain = MockChain::new(
+        config.genesis_difficulty,
+        config.block_time_target,
+        config.block_difficulty_window,
+    );
+    let mut time = 0f64;
+    let mut blocks = Vec::with_capacity(config.blocks as usize);
+    for number in 1..=config.blocks {
+        let difficulty = chain.next_difficulty()?;
+        let hash_rate = config.profile.hash_rate(number);
+        if hash_rate <= 0f64 {
+            return Err(format_err!("Invalid hash rate {} at {}", hash_rate, number));
+        }
+        // The count of hashes to find a block follows the exponential distribution.
+        let sample: f64 = rng.gen_range(f64::EPSILON, 1f64);
+        time += -sample.ln() * u256_to_f64(difficulty) / hash_rate;
+
+        let parent_timestamp = chain.current_header().timestamp;
+        let by_attacker = match &config.attack {
+            Some(attack) => rng.gen::<f64>() < attack.share,
+            None => false,
+        };
+        let timestamp = if by_attacker {
+            time as u64 + config.attack.as_ref().map_or(0, |a| a.future_offset)
+        } else {
+            time as u64
+        };
+        // Block timestamp can not be older than parent.
+        let timestamp = timestamp.max(parent_timestamp);
+        chain.append(timestamp, difficulty);
+        blocks.push(SimulatedBlock {
+            number,
+            time,
+            timestamp,
+            block_time: timestamp - parent_timestamp,
+            difficulty,
+            hash_rate,
+            by_attacker,
+        });
+    }
+    Ok(blocks)
+}
+
+/// Write the blocks as CSV.
+pub fn write_csv<W: Write>(blocks: &[SimulatedBlock], mut writer: W) -> Result<()> {
+    writeln!(
+        writer,
+        "number,time,timestamp,block_time,difficulty,hash_rate,by_attacker"
+    )?;
+    for block in blocks {
+        writeln!(
+            writer,
+            "{},{:.3},{},{},{},{},{}",
+            block.number,
+            block.time,
+            block.timestamp,
+            block.block_time,
+            block.difficulty,
+            block.hash_rate,
+            block.by_attacker
+        )?;
+    }
+    Ok(())
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    value
+        .0
+        .iter()
+        .rev()
+        .fold(0f64, |acc, word| acc * 2f64.powi(64) + *word as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn avg_block_time(blocks: &[SimulatedBlock]) -> f64 {
+        blocks.iter().map(|b| b.block_time as f64).sum::<f64>() / blocks.len() as f64
+    }
+
+    #[test]
+    fn test_constant_hash_rate() {
+        let config = SimulatorConfig {
+            genesis_difficulty: 10.into(),
+            block_time_target: 20,
+            block_difficulty_window: 24,
+            blocks: 2000,
+            profile: HashRateProfile::Constant(1000f64),
+            attack: None,
+            seed: 1,
+        };
+        let blocks = simulate(&config).unwrap();
+        assert_eq!(blocks.len(), 2000);
+        let avg = avg_block_time(&blocks[1000..]);
+        assert!(avg > 10f64 && avg < 40f64, "avg block time: {}", avg);
+    }
+
+    #[test]
+    fn test_step_hash_rate() {
+        let config = SimulatorConfig {
+            genesis_difficulty: 10.into(),
+            block_time_target: 20,
+            block_difficulty_window: 24,
+            blocks: 2000,
+            profile: HashRateProfile::Step {
+                from: 1000f64,
+                to: 10000f64,
+                at: 1000,
+            },
+            attack: None,
+            seed: 1,
+        };
+        let blocks = simulate(&config).unwrap();
+        let difficulty_before = blocks[999].difficulty;
+        let difficulty_after = blocks[1999].difficulty;
+        assert!(difficulty_after > difficulty_before);
+    }
+
+    #[test]
+    fn test_out_of_order_timestamps() {
+        let mut chain = MockChain::new(10.into(), 20, 24);
+        for timestamp in &[20, 40, 30, 10, 60, 50] {
+            let difficulty = chain.next_difficulty().unwrap();
+            chain.append(*timestamp, difficulty);
+        }
+        assert!(chain.next_difficulty().is_ok());
+    }
+
+    #[test]
+    fn test_future_timestamp_attack() {
+        let mut config = SimulatorConfig {
+            genesis_difficulty: 10.into(),
+            block_time_target: 20,
+            block_difficulty_window: 24,
+            blocks: 2000,
+            profile: HashRateProfile::Constant(1000f64),
+            attack: None,
+            seed: 1,
+        };
+        let honest = simulate(&config).unwrap();
+        config.attack = Some(TimestampAttack {
+            share: 0.3,
+            future_offset: 15,
+        });
+        let attacked = simulate(&config).unwrap();
+        let avg_difficulty = |blocks: &[SimulatedBlock]| {
+            blocks[1000..]
+                .iter()
+                .map(|b| u256_to_f64(b.difficulty))
+                .sum::<f64>()
+                / 1000f64
+        };
+        assert!(avg_difficulty(&attacked) > avg_difficulty(&honest) / 2f64);
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use starcoin_config::ChainNetwork;
+use starcoin_difficulty_simulator::{
+    simulate, write_csv, HashRateProfile, SimulatorConfig, TimestampAttack,
+};
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "difficulty-simulator",
+    about = "Simulate difficulty adjustment and output CSV"
+)]
+struct Opt {
+    #[structopt(long, short = "n", default_value = "halley")]
+    /// Chain network to load the ChainConfig from.
+    net: ChainNetwork,
+
+    #[structopt(long, default_value = "1000")]
+    /// Count of blocks to simulate.
+    blocks: u64,
+
+    #[structopt(long)]
+    /// Override block time target of the ChainConfig, in seconds.
+    block_time_target: Option<u64>,
+
+    #[structopt(long)]
+    /// Override block difficulty window of the ChainConfig.
+    block_difficulty_window: Option<u64>,
+
+    #[structopt(long, default_value = "constant")]
+    /// Hash rate profile: constant, step or oscillate.
+    profile: String,
+
+    #[structopt(long, default_value = "1000")]
+    /// Hash rate in hashes per second, and the base hash rate of step and oscillate.
+    hash_rate: f64,
+
+    #[structopt(long, default_value = "10000")]
+    /// Hash rate after the step, or the peak hash rate of oscillate.
+    peak_hash_rate: f64,
+
+    #[structopt(long, default_value = "500")]
+    /// Block number of the step, or the period blocks of oscillate.
+    change_at: u64,
+
+    #[structopt(long)]
+    /// Share of the hash rate of a miner which sets future timestamps, from 0 to 1.
+    attacker_share: Option<f64>,
+
+    #[structopt(long, default_value = "15")]
+    /// How many seconds the attacker sets its timestamps ahead.
+    future_offset: u64,
+
+    #[structopt(long, default_value = "0")]
+    /// Random seed.
+    seed: u64,
+
+    #[structopt(long, short = "o", parse(from_os_str))]
+    /// Output CSV file, default to stdout.
+    output: Option<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::from_args();
+    let profile = match opt.profile.as_str() {
+        "constant" => HashRateProfile::Constant(opt.hash_rate),
+        "step" => HashRateProfile::Step {
+            from: opt.hash_rate,
+            to: opt.peak_hash_rate,
+            at: opt.change_at,
+        },
+        "oscillate" => HashRateProfile::Oscillate {
+            base: opt.hash_rate,
+            peak: opt.peak_hash_rate,
+            period: opt.change_at,
+        },
+        profile => bail!("Unknown hash rate profile: {}", profile),
+    };
+    let mut config = SimulatorConfig::new(opt.net.get_config(), opt.blocks, profile);
+    if let Some(block_time_target) = opt.block_time_target {
+        config.block_time_target = block_time_target;
+    }
+    if let Some(window) = opt.block_difficulty_window {
+        config.block_difficulty_window = window;
+    }
+    config.attack = opt.attacker_share.map(|share| TimestampAttack {
+        share,
+        future_offset: opt.future_offset,
+    });
+    config.seed = opt.seed;
+
+    let blocks = simulate(&config)?;
+    match opt.output {
+        Some(path) => write_csv(&blocks, File::create(path)?),
+        None => write_csv(&blocks, io::stdout()),
+    }
+}
@@ -38,6 +38,10 @@ impl Consensus for ArgonConsensus {
         nonce
     }
 
+    fn verify_nonce(header_hash: &[u8], nonce: u64, difficulty: U256) -> bool {
+        verify(header_hash, nonce, difficulty)
+    }
+
     fn verify(reader: &dyn ChainReader, header: &BlockHeader) -> Result<()> {
         let difficulty = ArgonConsensus::calculate_next_difficulty(reader)?;
         if header.difficulty() != difficulty {
@@ -46,4 +46,8 @@ impl Consensus for DevConsensus {
     fn verify(_reader: &dyn ChainReader, _header: &BlockHeader) -> Result<()> {
         Ok(())
     }
+
+    fn verify_nonce(_header_hash: &[u8], _nonce: u64, _difficulty: U256) -> bool {
+        true
+    }
 }
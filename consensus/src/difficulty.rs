@@ -3,22 +3,26 @@
 
 use types::U256;
 
-use crate::argon::ArgonConsensus;
 use anyhow::Result;
 use logger::prelude::*;
-use traits::{ChainReader, Consensus};
+use traits::ChainReader;
 
 pub fn difficult_1_target() -> U256 {
     U256::max_value()
 }
 
+/// The solve time of a block is limited to `MAX_SOLVE_TIME_FACTOR` times of block time target,
+/// so a future timestamp can not reduce the difficulty too much.
+pub const MAX_SOLVE_TIME_FACTOR: u64 = 6;
+
 /// Get the target of next pow work
 pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
     let mut current_header = chain.current_header();
+    let current_target = difficult_to_target(current_header.difficulty);
     if current_header.number <= 1 {
-        return Ok(difficult_to_target(current_header.difficulty));
+        return Ok(current_target);
     }
-    let epoch = ArgonConsensus::epoch(chain)?;
+    let epoch = chain.epoch_info()?;
     let blocks = {
         let mut blocks: Vec<BlockDiffInfo> = vec![];
 
@@ -53,27 +57,27 @@ pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
         }
         blocks
     };
+    if blocks.len() < 2 {
+        return Ok(current_target);
+    }
+    let time_plan = epoch.block_time_target();
+    let solve_times = solve_times(&blocks, time_plan * MAX_SOLVE_TIME_FACTOR);
 
     let mut avg_time: u64 = 0;
     let mut avg_target = U256::zero();
-    let mut latest_block_index = 0;
     let block_n = blocks.len() - 1;
-    while latest_block_index < block_n {
-        let solve_time =
-            blocks[latest_block_index].timestamp - blocks[latest_block_index + 1].timestamp;
+    for (latest_block_index, solve_time) in solve_times.iter().enumerate() {
         avg_time += solve_time * (block_n - latest_block_index) as u64;
         debug!(
             "solve_time:{:?}, avg_time:{:?}, block_n:{:?}",
             solve_time, avg_time, block_n
         );
         avg_target = avg_target + blocks[latest_block_index].target / block_n.into();
-        latest_block_index += 1
     }
     avg_time /= (block_n as u64) * ((block_n + 1) as u64) / 2;
     if avg_time == 0 {
         avg_time = 1
     }
-    let time_plan = epoch.block_time_target();
     // new_target = avg_target * avg_time_used/time_plan
     // avoid the target increase or reduce too fast.
     let new_target =
@@ -106,6 +110,26 @@ pub fn difficult_to_target(difficulty: U256) -> U256 {
     difficult_1_target() / difficulty
 }
 
+/// Solve times of `blocks` ordered from the latest, the last block is only used as start time.
+/// A timestamp older than any of its ancestors in the window counts as zero solve time,
+/// and the later blocks are measured from the newest timestamp seen,
+/// so out of order timestamps neither underflow nor add time twice.
+pub fn solve_times(blocks: &[BlockDiffInfo], max_solve_time: u64) -> Vec<u64> {
+    if blocks.is_empty() {
+        return vec![];
+    }
+    let block_n = blocks.len() - 1;
+    let mut solve_times = vec![0u64; block_n];
+    let mut newest_timestamp = blocks[block_n].timestamp;
+    for (solve_time, block) in solve_times.iter_mut().zip(&blocks[..block_n]).rev() {
+        if block.timestamp > newest_timestamp {
+            *solve_time = (block.timestamp - newest_timestamp).min(max_solve_time);
+            newest_timestamp = block.timestamp;
+        }
+    }
+    solve_times
+}
+
 #[derive(Clone)]
 pub struct BlockDiffInfo {
     pub timestamp: u64,
@@ -0,0 +1,90 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::difficulty::{difficult_to_target, target_to_difficulty};
+use crate::{difficulty, set_header_nonce};
+use anyhow::{anyhow, Result};
+use crypto::hash::PlainCryptoHash;
+use logger::prelude::*;
+use rand::Rng;
+use tiny_keccak::Keccak;
+use traits::{ChainReader, Consensus};
+use types::block::{BlockHeader, RawBlockHeader};
+use types::{H256, U256};
+
+/// Proof of work with a single keccak256 round, much cheaper to verify than argon.
+#[derive(Clone)]
+pub struct KeccakConsensus {}
+
+impl Consensus for KeccakConsensus {
+    fn calculate_next_difficulty(reader: &dyn ChainReader) -> Result<U256> {
+        let target = difficulty::get_next_work_required(reader)?;
+        Ok(target_to_difficulty(target))
+    }
+
+    fn solve_consensus_nonce(header_hash: &[u8], difficulty: U256) -> u64 {
+        let mut nonce = generate_nonce();
+        let target = difficult_to_target(difficulty);
+        loop {
+            let pow_hash: U256 = calculate_hash(&set_header_nonce(&header_hash, nonce))
+                .expect("calculate hash should work")
+                .into();
+            if pow_hash > target {
+                nonce = nonce.wrapping_add(1);
+                continue;
+            }
+            break;
+        }
+        nonce
+    }
+
+    fn verify_nonce(header_hash: &[u8], nonce: u64, difficulty: U256) -> bool {
+        verify(header_hash, nonce, difficulty)
+    }
+
+    fn verify(reader: &dyn ChainReader, header: &BlockHeader) -> Result<()> {
+        let difficulty = KeccakConsensus::calculate_next_difficulty(reader)?;
+        if header.difficulty() != difficulty {
+            return Err(anyhow!(
+                "Difficulty mismatch: {:?}, {:?}",
+                header.difficulty(),
+                difficulty
+            ));
+        }
+        let nonce = header.nonce;
+        debug!(
+            "Verify header, nonce, difficulty :{:?}, {:o}, {:x}",
+            header, nonce, difficulty
+        );
+        let raw_block_header: RawBlockHeader = header.clone().into();
+        if verify(&raw_block_header.crypto_hash().to_vec(), nonce, difficulty) {
+            Ok(())
+        } else {
+            Err(anyhow::Error::msg("Invalid header"))
+        }
+    }
+}
+
+pub fn verify(header: &[u8], nonce: u64, difficulty: U256) -> bool {
+    let pow_header = set_header_nonce(header, nonce);
+    match calculate_hash(&pow_header) {
+        Ok(pow_hash) => {
+            let hash_u256: U256 = pow_hash.into();
+            hash_u256 <= difficult_to_target(difficulty)
+        }
+        Err(_) => false,
+    }
+}
+
+pub fn calculate_hash(header: &[u8]) -> Result<H256> {
+    let mut keccak = Keccak::new_keccak256();
+    keccak.update(header);
+    let mut output = [0u8; 32];
+    keccak.finalize(&mut output);
+    Ok(output.into())
+}
+
+fn generate_nonce() -> u64 {
+    let mut rng = rand::thread_rng();
+    rng.gen_range(0, u64::max_value())
+}
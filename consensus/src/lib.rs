@@ -5,6 +5,7 @@ pub mod argon;
 pub mod dev;
 pub mod difficulty;
 pub mod dummy;
+pub mod keccak;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
@@ -1,5 +1,7 @@
 use crypto::hash::PlainCryptoHash;
 use starcoin_consensus::argon::{verify, ArgonConsensus};
+use starcoin_consensus::difficulty::{solve_times, BlockDiffInfo};
+use starcoin_consensus::keccak::{self, KeccakConsensus};
 use traits::Consensus;
 use types::block::{BlockHeader, RawBlockHeader};
 
@@ -33,3 +35,67 @@ fn verify_header_test() {
         1.into(),
     ));
 }
+
+#[stest::test]
+fn verify_keccak_header_test() {
+    let header = BlockHeader::random();
+    let raw_header: RawBlockHeader = header.into();
+    let nonce = KeccakConsensus::solve_consensus_nonce(
+        raw_header.crypto_hash().to_vec().as_slice(),
+        1.into(),
+    );
+    assert!(keccak::verify(
+        raw_header.crypto_hash().to_vec().as_slice(),
+        nonce,
+        1.into(),
+    ));
+}
+
+#[stest::test]
+fn verify_keccak_difficulty_test() {
+    let header = BlockHeader::random();
+    let raw_header: RawBlockHeader = header.into();
+    let header_hash = raw_header.crypto_hash().to_vec();
+    let difficulty = 1000.into();
+    let nonce = KeccakConsensus::solve_consensus_nonce(header_hash.as_slice(), difficulty);
+    assert!(keccak::verify(header_hash.as_slice(), nonce, difficulty));
+    // A seal found for a higher difficulty is also valid for a lower one.
+    assert!(keccak::verify(header_hash.as_slice(), nonce, 1.into()));
+    let invalid = (0..u64::max_value())
+        .map(|i| nonce.wrapping_add(i + 1))
+        .find(|n| !keccak::verify(header_hash.as_slice(), *n, difficulty));
+    assert!(invalid.is_some());
+}
+
+fn diff_infos(timestamps: &[u64]) -> Vec<BlockDiffInfo> {
+    timestamps
+        .iter()
+        .map(|timestamp| BlockDiffInfo {
+            timestamp: *timestamp,
+            target: 1.into(),
+        })
+        .collect()
+}
+
+#[stest::test]
+fn solve_times_test() {
+    // From the latest block.
+    let blocks = diff_infos(&[40, 30, 20, 10]);
+    assert_eq!(solve_times(&blocks, 100), vec![10, 10, 10]);
+}
+
+#[stest::test]
+fn solve_times_out_of_order_test() {
+    // The block at 15 is older than its parent, it should not underflow,
+    // and the time after it is measured from 30.
+    let blocks = diff_infos(&[50, 15, 30, 10]);
+    assert_eq!(solve_times(&blocks, 100), vec![20, 0, 20]);
+    assert_eq!(solve_times(&blocks, 100).iter().sum::<u64>(), 40);
+}
+
+#[stest::test]
+fn solve_times_future_timestamp_test() {
+    // A block with a timestamp far in the future only counts max solve time.
+    let blocks = diff_infos(&[1010, 1000, 20, 10]);
+    assert_eq!(solve_times(&blocks, 60), vec![10, 60, 10]);
+}
@@ -7,8 +7,8 @@ use serde::{Deserialize, Serialize};
 use starcoin_accumulator::node::{AccumulatorStoreType, ACCUMULATOR_PLACEHOLDER_HASH};
 use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::BlockChain;
-use starcoin_config::{genesis_key_pair, ChainNetwork};
-use starcoin_consensus::{argon::ArgonConsensus, dev::DevConsensus};
+use starcoin_config::{genesis_key_pair, ChainNetwork, ConsensusType};
+use starcoin_consensus::{argon::ArgonConsensus, dev::DevConsensus, keccak::KeccakConsensus};
 use starcoin_logger::prelude::*;
 use starcoin_state_api::ChainState;
 use starcoin_statedb::ChainStateDB;
@@ -219,10 +219,10 @@ impl Genesis {
         net: ChainNetwork,
         storage: Arc<dyn Store>,
     ) -> Result<StartupInfo> {
-        if net.is_dev() {
-            self.execute_genesis_block_inner::<DevConsensus>(storage)
-        } else {
-            self.execute_genesis_block_inner::<ArgonConsensus>(storage)
+        match net.get_config().consensus {
+            ConsensusType::Dev => self.execute_genesis_block_inner::<DevConsensus>(storage),
+            ConsensusType::Argon => self.execute_genesis_block_inner::<ArgonConsensus>(storage),
+            ConsensusType::Keccak => self.execute_genesis_block_inner::<KeccakConsensus>(storage),
         }
     }
 
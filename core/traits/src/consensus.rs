@@ -40,6 +40,9 @@ pub trait Consensus: std::marker::Unpin + Clone + Sync + Send {
 
     fn verify(reader: &dyn ChainReader, header: &BlockHeader) -> Result<()>;
 
+    /// Verify the pow hash of `header_hash` with `nonce` meets `difficulty`.
+    fn verify_nonce(header_hash: &[u8], nonce: u64, difficulty: U256) -> bool;
+
     /// Construct block with BlockTemplate, this a shortcut method for calculate_next_difficulty + solve_consensus_nonce
     fn create_block(reader: &dyn ChainReader, block_template: BlockTemplate) -> Result<Block> {
         let difficulty = Self::calculate_next_difficulty(reader)?;
@@ -5,12 +5,12 @@ use crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     HashValue, PrivateKey, Uniform,
 };
-use executor::{encode_create_account_script, encode_transfer_script};
+use executor::{encode_create_account_script, encode_peer_to_peer_script};
 use logger::prelude::*;
 use rand::{rngs::StdRng, SeedableRng};
 use starcoin_config::ChainNetwork;
 use starcoin_genesis::Genesis;
-use starcoin_state_api::ChainState;
+use starcoin_state_api::ChainStateWriter;
 use statedb::ChainStateDB;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc;
@@ -21,13 +21,19 @@ use storage::Storage;
 use types::{
     account_address,
     account_address::AccountAddress,
+    account_config::stc_type_tag,
     block_metadata::BlockMetadata,
-    transaction::{authenticator::AuthenticationKey, Script, Transaction, TransactionPayload},
+    transaction::{
+        authenticator::AuthenticationKey, RawUserTransaction, Script, Transaction,
+        TransactionPayload, TransactionStatus,
+    },
 };
 
 struct AccountData {
+    private_key: Ed25519PrivateKey,
     public_key: Ed25519PublicKey,
     address: AccountAddress,
+    sequence_number: u64,
 }
 
 impl AccountData {
@@ -43,8 +49,10 @@ impl AccountData {
         let public_key = private_key.public_key();
         let address = account_address::from_public_key(&public_key);
         AccountData {
+            private_key,
             public_key,
             address,
+            sequence_number: 0,
         }
     }
 }
@@ -76,8 +84,10 @@ impl TransactionGenerator {
             let public_key = private_key.public_key();
             let address = account_address::from_public_key(&public_key);
             let account = AccountData {
+                private_key,
                 public_key,
                 address,
+                sequence_number: 0,
             };
             accounts.push(account);
         }
@@ -116,9 +126,10 @@ impl TransactionGenerator {
                 let txn = create_transaction(
                     self.sequence,
                     encode_create_account_script(
-                        &account.address,
+                        stc_type_tag(),
+                        account.address,
                         account.auth_key_prefix(),
-                        init_account_balance,
+                        u128::from(init_account_balance),
                     ),
                     self.time.now() + j as u64 + 1,
                 );
@@ -134,7 +145,8 @@ impl TransactionGenerator {
         }
     }
 
-    /// Generates transactions for random pairs of accounts.
+    /// Generates transactions for random pairs of accounts, every txn is sent by the sender
+    /// account, so the txns of different senders can be executed in parallel.
     fn gen_transfer_transactions(&mut self, block_size: usize, num_blocks: usize) {
         for _i in 0..num_blocks {
             self.time.increment();
@@ -151,23 +163,31 @@ impl TransactionGenerator {
 
             for j in 0..block_size {
                 let indices = rand::seq::index::sample(&mut self.rng, self.accounts.len(), 2);
-                //                let sender_idx = indices.index(0);
+                let sender_idx = indices.index(0);
                 let receiver_idx = indices.index(1);
 
-                //                let sender = &self.accounts[sender_idx];
                 let receiver = &self.accounts[receiver_idx];
-                let txn = create_transaction(
-                    self.sequence,
-                    encode_transfer_script(
-                        &receiver.address,
-                        receiver.auth_key_prefix(),
-                        1, /* amount */
-                    ),
-                    self.time.now() + j as u64 + 1,
+                let program = encode_peer_to_peer_script(
+                    stc_type_tag(),
+                    receiver.address,
+                    receiver.auth_key_prefix(),
+                    1, /* amount */
                 );
-                transactions.push(txn);
+                let sender = &mut self.accounts[sender_idx];
+                let signed_txn = RawUserTransaction::new(
+                    sender.address,
+                    sender.sequence_number,
+                    TransactionPayload::Script(program),
+                    400_000,
+                    1,
+                    self.time.now() + j as u64 + 1,
+                )
+                .sign(&sender.private_key, sender.public_key.clone())
+                .expect("Sign txn should be ok.")
+                .into_inner();
+                transactions.push(Transaction::UserTransaction(signed_txn));
 
-                self.sequence += 1;
+                sender.sequence_number += 1;
             }
 
             self.block_sender
@@ -185,35 +205,62 @@ impl TransactionGenerator {
 }
 
 struct TxnExecutor<'test> {
-    chain_state: &'test dyn ChainState,
+    chain_state: &'test ChainStateDB,
     block_receiver: mpsc::Receiver<Vec<Transaction>>,
+    parallel: bool,
 }
 
 impl<'test> TxnExecutor<'test> {
     fn new(
-        chain_state: &'test dyn ChainState,
+        chain_state: &'test ChainStateDB,
         block_receiver: mpsc::Receiver<Vec<Transaction>>,
+        parallel: bool,
     ) -> Self {
         Self {
             chain_state,
             block_receiver,
+            parallel,
         }
     }
 
-    fn run(&mut self) {
+    /// Execute and commit the blocks, return the TPS of execution.
+    fn run(&mut self) -> u128 {
         let mut version = 0;
+        let mut total_execute_time = std::time::Duration::default();
         while let Ok(transactions) = self.block_receiver.recv() {
             let execute_start = std::time::Instant::now();
             let num_txns = transactions.len();
             version += num_txns as u64;
 
-            let _ = executor::block_execute(self.chain_state, transactions, u64::MAX)
-                .expect("Execute transactions fail.");
-            self.chain_state.flush().expect("flush state should be ok");
+            let outputs = if self.parallel {
+                executor::execute_block_transactions_parallel(
+                    self.chain_state,
+                    transactions,
+                    u64::MAX,
+                )
+            } else {
+                executor::execute_block_transactions(self.chain_state, transactions, u64::MAX)
+            }
+            .expect("Execute transactions fail.");
 
             let execute_time = std::time::Instant::now().duration_since(execute_start);
+            total_execute_time += execute_time;
             let commit_start = std::time::Instant::now();
 
+            for output in outputs {
+                assert!(
+                    matches!(output.status(), TransactionStatus::Keep(_)),
+                    "Txn should not be discarded."
+                );
+                self.chain_state
+                    .apply_write_set(output.write_set().clone())
+                    .expect("apply write set should be ok");
+            }
+            self.chain_state
+                .commit()
+                .expect("commit state should be ok");
+            self.chain_state.flush().expect("flush state should be ok");
+
             let commit_time = std::time::Instant::now().duration_since(commit_start);
             let total_time = execute_time + commit_time;
 
@@ -225,16 +272,19 @@ impl<'test> TxnExecutor<'test> {
                 num_txns as u128 * 1_000_000_000 / total_time.as_nanos(),
             );
         }
+        version as u128 * 1_000_000_000 / total_execute_time.as_nanos().max(1)
     }
 }
 
-/// Runs the benchmark with given parameters.
+/// Runs the benchmark with given parameters, execute the user txns in parallel if `parallel`,
+/// return the TPS of execution.
 pub fn run_benchmark(
     num_accounts: usize,
     init_account_balance: u64,
     block_size: usize,
     num_transfer_blocks: usize,
-) {
+    parallel: bool,
+) -> u128 {
     let cache_storage = CacheStorage::new();
     let storage =
         Arc::new(Storage::new(StorageInstance::new_cache_instance(cache_storage)).unwrap());
@@ -258,8 +308,8 @@ pub fn run_benchmark(
     let exe_thread = std::thread::Builder::new()
         .name("txn_executor".to_string())
         .spawn(move || {
-            let mut exe = TxnExecutor::new(&chain_state, block_receiver);
-            exe.run();
+            let mut exe = TxnExecutor::new(&chain_state, block_receiver, parallel);
+            exe.run()
         })
         .expect("Failed to spawn transaction executor thread.");
 
@@ -268,7 +318,7 @@ pub fn run_benchmark(
     // Drop the sender so the executor thread can eventually exit.
     generator.drop_sender();
     // Wait until all transactions are committed.
-    exe_thread.join().unwrap();
+    exe_thread.join().unwrap()
 }
 
 fn create_transaction(
@@ -321,6 +371,18 @@ mod tests {
             1_000_000, /* init_account_balance */
             5,         /* block_size */
             5,         /* num_transfer_blocks */
+            false,     /* parallel */
+        );
+    }
+
+    #[test]
+    fn test_parallel_benchmark() {
+        super::run_benchmark(
+            25,        /* num_accounts */
+            1_000_000, /* init_account_balance */
+            5,         /* block_size */
+            5,         /* num_transfer_blocks */
+            true,      /* parallel */
         );
     }
 }
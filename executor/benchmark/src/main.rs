@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use logger::prelude::*;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -16,6 +17,14 @@ struct Opt {
 
     #[structopt(long, default_value = "10")]
     num_transfer_blocks: usize,
+
+    /// Execute the user txns in parallel.
+    #[structopt(long)]
+    parallel: bool,
+
+    /// Run both the sequential and parallel execution, and compare the TPS.
+    #[structopt(long, conflicts_with = "parallel")]
+    compare: bool,
 }
 
 fn main() {
@@ -28,10 +37,24 @@ fn main() {
         .build_global()
         .expect("Failed to build rayon global thread pool.");
 
-    starcoin_executor_benchmark::run_benchmark(
-        opt.num_accounts,
-        opt.init_account_balance,
-        opt.block_size,
-        opt.num_transfer_blocks,
-    );
+    let run = |parallel| {
+        starcoin_executor_benchmark::run_benchmark(
+            opt.num_accounts,
+            opt.init_account_balance,
+            opt.block_size,
+            opt.num_transfer_blocks,
+            parallel,
+        )
+    };
+    if opt.compare {
+        let sequential_tps = run(false);
+        let parallel_tps = run(true);
+        info!(
+            "Sequential execution TPS: {}, parallel execution TPS: {}.",
+            sequential_tps, parallel_tps
+        );
+    } else {
+        let tps = run(opt.parallel);
+        info!("Execution TPS: {}.", tps);
+    }
 }
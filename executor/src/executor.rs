@@ -41,6 +41,29 @@ fn do_execute_block_transactions(
     Ok(result)
 }
 
+/// Execute a block transactions like `execute_block_transactions`, but execute the user
+/// transactions in parallel, the outputs are the same as the sequential execution.
+pub fn execute_block_transactions_parallel<S>(
+    chain_state: &S,
+    txns: Vec<Transaction>,
+    block_gas_limit: u64,
+) -> Result<Vec<TransactionOutput>>
+where
+    S: StateView + Sync,
+{
+    let timer = TXN_EXECUTION_HISTOGRAM
+        .with_label_values(&["execute_block_transactions_parallel"])
+        .start_timer();
+    let mut vm = StarcoinVM::new();
+    let result = vm
+        .execute_block_transactions_parallel(chain_state, txns, Some(block_gas_limit))?
+        .into_iter()
+        .map(|(_, output)| output)
+        .collect();
+    timer.observe_duration();
+    Ok(result)
+}
+
 pub fn validate_transaction(
     chain_state: &dyn StateView,
     txn: SignedUserTransaction,
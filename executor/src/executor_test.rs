@@ -4,24 +4,37 @@
 use anyhow::Result;
 use logger::prelude::*;
 use starcoin_config::ChainNetwork;
+use starcoin_crypto::hash::PlainCryptoHash;
+use starcoin_crypto::HashValue;
 use starcoin_functional_tests::account::{
     create_account_txn_sent_as_association, peer_to_peer_txn, Account,
 };
 use starcoin_genesis::Genesis;
 use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, ChainStateWriter};
 use starcoin_transaction_builder::{
-    build_stdlib_package, create_signed_txn_with_association_account, StdlibScript,
-    DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
+    build_stdlib_package, create_signed_txn_with_association_account,
+    encode_add_to_script_whitelist_script, encode_cancel_upgrade_plan_script,
+    encode_lock_publishing_option_script, encode_remove_from_script_whitelist_script,
+    encode_set_gas_schedule_script_by_cost_table, encode_submit_upgrade_plan_script_by_package,
+    encode_update_module_upgrade_strategy_script, StdlibScript, DEFAULT_EXPIRATION_TIME,
+    DEFAULT_MAX_GAS_AMOUNT,
 };
 use starcoin_types::language_storage::CORE_CODE_ADDRESS;
 use starcoin_types::transaction::TransactionOutput;
 use starcoin_types::{
     account_address::AccountAddress,
     account_config,
+    account_config::{
+        ModuleUpgradeStrategy, ModuleUpgradeStrategyResource, TwoPhaseUpgradeResource, UpgradePlan,
+    },
     block_metadata::BlockMetadata,
     transaction::Transaction,
     transaction::TransactionStatus,
-    transaction::{Module, TransactionPayload},
+    transaction::{Module, Script, TransactionArgument, TransactionPayload},
+};
+use starcoin_vm_types::gas_schedule::{GasAlgebra, GasCost};
+use starcoin_vm_types::on_chain_config::{
+    OnChainConfig, VMConfig, VMPublishingOption, INITIAL_GAS_SCHEDULE,
 };
 use starcoin_vm_types::vm_status::KeptVMStatus;
 use starcoin_vm_types::{parser, transaction::Package, vm_status::StatusCode};
@@ -383,6 +396,67 @@ fn test_execute_multi_txn_with_same_account() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+fn test_parallel_execute_same_as_sequential() -> Result<()> {
+    let chain_state = prepare_genesis();
+    let accounts = (0..4).map(|_| Account::new()).collect::<Vec<_>>();
+    for (seq_number, account) in accounts.iter().enumerate() {
+        let txn = Transaction::UserTransaction(create_account_txn_sent_as_association(
+            account,
+            seq_number as u64,
+            50_000_000,
+            DEFAULT_EXPIRATION_TIME,
+        ));
+        let output = execute_and_apply(&chain_state, txn);
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+
+    let block_meta = BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        1,
+        *accounts[0].address(),
+        Some(accounts[0].auth_key_prefix()),
+        0,
+    );
+    let receiver = Account::new();
+    let mut txns = vec![Transaction::BlockMetadata(block_meta)];
+    for account in &accounts {
+        // all transfer to a same new account.
+        txns.push(Transaction::UserTransaction(peer_to_peer_txn(
+            account,
+            &receiver,
+            0,
+            10_000,
+            DEFAULT_EXPIRATION_TIME,
+        )));
+        // transfer to independent accounts.
+        txns.push(Transaction::UserTransaction(peer_to_peer_txn(
+            account,
+            &Account::new(),
+            1,
+            10_000,
+            DEFAULT_EXPIRATION_TIME,
+        )));
+    }
+    // transfer too much, the txn aborts.
+    txns.push(Transaction::UserTransaction(peer_to_peer_txn(
+        &accounts[0],
+        &receiver,
+        2,
+        100_000_000,
+        DEFAULT_EXPIRATION_TIME,
+    )));
+
+    let block_gas_limit = DEFAULT_MAX_GAS_AMOUNT * txns.len() as u64;
+    let sequential_outputs =
+        crate::execute_block_transactions(&chain_state, txns.clone(), block_gas_limit)?;
+    let parallel_outputs =
+        crate::execute_block_transactions_parallel(&chain_state, txns, block_gas_limit)?;
+    assert_eq!(sequential_outputs.len(), 10);
+    assert_eq!(sequential_outputs, parallel_outputs);
+    Ok(())
+}
+
 #[stest::test]
 fn test_sequence_number() -> Result<()> {
     let chain_state = prepare_genesis();
@@ -430,6 +504,278 @@ fn test_gas_used() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+fn test_set_gas_schedule() -> Result<()> {
+    let chain_state = prepare_genesis();
+
+    let account1 = Account::new();
+    let txn = crate::build_transfer_from_association(
+        *account1.address(),
+        account1.auth_key_prefix(),
+        0,
+        1000,
+        1,
+    );
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let gas_used_before = output.gas_used();
+
+    // double the cost of every instruction.
+    let mut gas_schedule = INITIAL_GAS_SCHEDULE.clone();
+    for cost in gas_schedule.instruction_table.iter_mut() {
+        *cost = GasCost::new(cost.instruction_gas.get() * 2, cost.memory_gas.get());
+    }
+    let genesis_account = Account::new_genesis_account(account_config::genesis_address());
+    let txn = genesis_account.create_signed_txn_impl(
+        *genesis_account.address(),
+        TransactionPayload::Script(encode_set_gas_schedule_script_by_cost_table(&gas_schedule)?),
+        get_sequence_number(*genesis_account.address(), &chain_state),
+        DEFAULT_MAX_GAS_AMOUNT,
+        0,
+        DEFAULT_EXPIRATION_TIME,
+    );
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let vm_config = VMConfig::fetch_config(&chain_state as &dyn ChainStateReader)?
+        .expect("VMConfig must exist.");
+    assert_eq!(vm_config.gas_schedule, gas_schedule);
+
+    let account2 = Account::new();
+    let txn = crate::build_transfer_from_association(
+        *account2.address(),
+        account2.auth_key_prefix(),
+        1,
+        1000,
+        1,
+    );
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    assert!(output.gas_used() > gas_used_before);
+
+    Ok(())
+}
+
+#[stest::test]
+fn test_script_whitelist() -> Result<()> {
+    let chain_state = prepare_genesis();
+    let genesis_account = Account::new_genesis_account(account_config::genesis_address());
+    let execute_by_genesis = |script: Script| -> Result<KeptVMStatus> {
+        let txn = genesis_account.create_signed_txn_impl(
+            *genesis_account.address(),
+            TransactionPayload::Script(script),
+            get_sequence_number(*genesis_account.address(), &chain_state),
+            DEFAULT_MAX_GAS_AMOUNT,
+            0,
+            DEFAULT_EXPIRATION_TIME,
+        );
+        let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+        Ok(output.status().status().unwrap())
+    };
+    let publishing_option = || -> Result<VMPublishingOption> {
+        Ok(
+            VMConfig::fetch_config(&chain_state as &dyn ChainStateReader)?
+                .expect("VMConfig must exist.")
+                .publishing_option,
+        )
+    };
+    let abort_code = |status: KeptVMStatus| match status {
+        KeptVMStatus::MoveAbort(_, code) => code,
+        status => panic!("Unexpected status: {:?}", status),
+    };
+
+    let script_hash = HashValue::sha3_256_of(b"custom script");
+    // the publishing option is not locked.
+    assert_eq!(
+        abort_code(execute_by_genesis(encode_add_to_script_whitelist_script(
+            script_hash.to_vec()
+        ))?),
+        2
+    );
+
+    let stdlib_whitelist = StdlibScript::whitelist();
+    assert_eq!(
+        KeptVMStatus::Executed,
+        execute_by_genesis(encode_lock_publishing_option_script(
+            stdlib_whitelist.clone()
+        )?)?
+    );
+    assert_eq!(
+        KeptVMStatus::Executed,
+        execute_by_genesis(encode_add_to_script_whitelist_script(script_hash.to_vec()))?
+    );
+    let mut whitelist = stdlib_whitelist.clone();
+    whitelist.push(*script_hash.as_ref());
+    assert_eq!(publishing_option()?, VMPublishingOption::Locked(whitelist));
+    assert!(publishing_option()?.is_allowed_script(b"custom script"));
+    assert_eq!(
+        abort_code(execute_by_genesis(encode_add_to_script_whitelist_script(
+            script_hash.to_vec()
+        ))?),
+        4
+    );
+
+    assert_eq!(
+        KeptVMStatus::Executed,
+        execute_by_genesis(encode_remove_from_script_whitelist_script(
+            script_hash.to_vec()
+        ))?
+    );
+    assert_eq!(
+        publishing_option()?,
+        VMPublishingOption::Locked(stdlib_whitelist)
+    );
+    assert_eq!(
+        abort_code(execute_by_genesis(
+            encode_remove_from_script_whitelist_script(script_hash.to_vec())
+        )?),
+        5
+    );
+    assert_eq!(
+        abort_code(execute_by_genesis(encode_add_to_script_whitelist_script(
+            vec![0u8; 16]
+        ))?),
+        3
+    );
+
+    Ok(())
+}
+
+#[stest::test]
+fn test_set_gas_schedule_on_generated_genesis() -> Result<()> {
+    // The committed dev genesis is generated before `VMConfig::set_gas_schedule`, the function
+    // is deployed by a stdlib upgrade.
+    let net = ChainNetwork::Dev;
+    let chain_state = prepare_generated_genesis(net)?;
+
+    let mut gas_schedule = INITIAL_GAS_SCHEDULE.clone();
+    for cost in gas_schedule.instruction_table.iter_mut() {
+        *cost = GasCost::new(cost.instruction_gas.get() * 2, cost.memory_gas.get());
+    }
+    let genesis_account = Account::new_genesis_account(account_config::genesis_address());
+    let set_gas_schedule = || -> Result<TransactionOutput> {
+        let txn = genesis_account.create_signed_txn_impl(
+            *genesis_account.address(),
+            TransactionPayload::Script(encode_set_gas_schedule_script_by_cost_table(
+                &gas_schedule,
+            )?),
+            get_sequence_number(*genesis_account.address(), &chain_state),
+            DEFAULT_MAX_GAS_AMOUNT,
+            0,
+            DEFAULT_EXPIRATION_TIME,
+        );
+        Ok(execute_and_apply(
+            &chain_state,
+            Transaction::UserTransaction(txn),
+        ))
+    };
+    let output = set_gas_schedule()?;
+    assert_ne!(
+        output.status(),
+        &TransactionStatus::Keep(KeptVMStatus::Executed)
+    );
+
+    upgrade_stdlib(&chain_state, net)?;
+
+    let output = set_gas_schedule()?;
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    let vm_config = VMConfig::fetch_config(&chain_state as &dyn ChainStateReader)?
+        .expect("VMConfig must exist.");
+    assert_eq!(vm_config.gas_schedule, gas_schedule);
+
+    Ok(())
+}
+
+#[stest::test]
+fn test_script_whitelist_on_generated_genesis() -> Result<()> {
+    // The committed dev genesis is generated before `VMConfig::add_to_script_whitelist` and
+    // `VMConfig::remove_from_script_whitelist`, the functions are deployed by a stdlib upgrade.
+    let net = ChainNetwork::Dev;
+    let chain_state = prepare_generated_genesis(net)?;
+    let genesis_account = Account::new_genesis_account(account_config::genesis_address());
+    let execute_by_genesis = |script: Script| -> Result<TransactionStatus> {
+        let txn = genesis_account.create_signed_txn_impl(
+            *genesis_account.address(),
+            TransactionPayload::Script(script),
+            get_sequence_number(*genesis_account.address(), &chain_state),
+            DEFAULT_MAX_GAS_AMOUNT,
+            0,
+            DEFAULT_EXPIRATION_TIME,
+        );
+        let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+        Ok(output.status().clone())
+    };
+    let executed = TransactionStatus::Keep(KeptVMStatus::Executed);
+
+    let first_hash = HashValue::sha3_256_of(b"first script");
+    let second_hash = HashValue::sha3_256_of(b"second script");
+    assert_ne!(
+        executed,
+        execute_by_genesis(encode_add_to_script_whitelist_script(first_hash.to_vec()))?
+    );
+
+    // upgrade before locking the publishing option, the locked option rejects packages.
+    upgrade_stdlib(&chain_state, net)?;
+    let stdlib_whitelist = StdlibScript::whitelist();
+    assert_eq!(
+        executed,
+        execute_by_genesis(encode_lock_publishing_option_script(
+            stdlib_whitelist.clone()
+        )?)?
+    );
+    for script_hash in &[first_hash, second_hash] {
+        assert_eq!(
+            executed,
+            execute_by_genesis(encode_add_to_script_whitelist_script(script_hash.to_vec()))?
+        );
+    }
+    // remove the hash before another, the whitelist keeps the order of the others.
+    assert_eq!(
+        executed,
+        execute_by_genesis(encode_remove_from_script_whitelist_script(
+            first_hash.to_vec()
+        ))?
+    );
+    let mut whitelist = stdlib_whitelist;
+    whitelist.push(*second_hash.as_ref());
+    let vm_config = VMConfig::fetch_config(&chain_state as &dyn ChainStateReader)?
+        .expect("VMConfig must exist.");
+    assert_eq!(
+        vm_config.publishing_option,
+        VMPublishingOption::Locked(whitelist)
+    );
+
+    Ok(())
+}
+
+/// Execute the genesis txn of the committed generated genesis of `net`.
+fn prepare_generated_genesis(net: ChainNetwork) -> Result<ChainStateDB> {
+    let chain_state = ChainStateDB::mock();
+    let genesis = Genesis::load(net)?;
+    let genesis_txn = genesis
+        .block()
+        .transactions()
+        .first()
+        .cloned()
+        .expect("Genesis txn must exist.");
+    Genesis::execute_genesis_txn(&chain_state, genesis_txn)?;
+    Ok(chain_state)
+}
+
+/// Upgrade the stdlib of `chain_state` to the staged stdlib, like `dev upgrade_stdlib`.
+fn upgrade_stdlib(chain_state: &ChainStateDB, net: ChainNetwork) -> Result<()> {
+    let upgrade_package = build_stdlib_package(net, StdLibOptions::Staged, false)?;
+    let txn = create_signed_txn_with_association_account(
+        TransactionPayload::Package(upgrade_package),
+        get_sequence_number(account_config::association_address(), chain_state),
+        2_000_000,
+        1,
+        1,
+    );
+    let output = execute_and_apply(chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    Ok(())
+}
+
 fn get_sequence_number(addr: AccountAddress, chain_state: &dyn ChainState) -> u64 {
     let account_reader = AccountStateReader::new(chain_state.as_super());
     account_reader
@@ -457,6 +803,15 @@ fn compile_module_with_address(address: AccountAddress, code: &str) -> Module {
     Module::new(compiled_result.serialize())
 }
 
+fn compile_script(code: &str) -> Vec<u8> {
+    let stdlib_files = stdlib_files();
+    starcoin_move_compiler::compile_source_string_no_report(code, &stdlib_files, CORE_CODE_ADDRESS)
+        .expect("compile fail")
+        .1
+        .expect("compile fail")
+        .serialize()
+}
+
 #[stest::test]
 fn test_publish_module_and_upgrade() -> Result<()> {
     let chain_state = prepare_genesis();
@@ -516,6 +871,189 @@ fn test_publish_module_and_upgrade() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+fn test_two_phase_upgrade() -> Result<()> {
+    let chain_state = prepare_genesis();
+
+    let account1 = Account::new();
+    let txn1 = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account1, 0, 50_000_000, 1,
+    ));
+    let output1 = execute_and_apply(&chain_state, txn1);
+    assert_eq!(KeptVMStatus::Executed, output1.status().status().unwrap());
+
+    let module_source = r#"
+        module M {
+            public fun hello(){
+            }
+        }
+        "#;
+    let compiled_module = compile_module_with_address(*account1.address(), module_source);
+    let txn = account1.create_signed_txn_impl(
+        *account1.address(),
+        TransactionPayload::Package(Package::new_with_module(compiled_module)?),
+        get_sequence_number(*account1.address(), &chain_state),
+        100_000,
+        1,
+        DEFAULT_EXPIRATION_TIME,
+    );
+    let output = execute_and_apply(&chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let execute_by_account1 = |payload: TransactionPayload| -> TransactionStatus {
+        let txn = account1.create_signed_txn_impl(
+            *account1.address(),
+            payload,
+            get_sequence_number(*account1.address(), &chain_state),
+            100_000,
+            1,
+            DEFAULT_EXPIRATION_TIME,
+        );
+        execute_and_apply(&chain_state, Transaction::UserTransaction(txn))
+            .status()
+            .clone()
+    };
+    let executed = TransactionStatus::Keep(KeptVMStatus::Executed);
+    // the same check as the package txn prologue, to get the abort code.
+    let check_package_txn_script = compile_script(
+        r#"
+        script {
+        use 0x1::PackageTxnManager;
+        fun main(package_address: address, package_hash: vector<u8>) {
+            PackageTxnManager::check_package_txn(package_address, package_address, package_hash);
+        }
+        }
+        "#,
+    );
+    let check_package_txn = |package: &Package| -> TransactionStatus {
+        execute_by_account1(TransactionPayload::Script(Script::new(
+            check_package_txn_script.clone(),
+            vec![],
+            vec![
+                TransactionArgument::Address(package.package_address()),
+                TransactionArgument::U8Vector(package.crypto_hash().to_vec()),
+            ],
+        )))
+    };
+    let abort_code = |status: TransactionStatus| match status {
+        TransactionStatus::Keep(KeptVMStatus::MoveAbort(_, code)) => code,
+        status => panic!("Unexpected status: {:?}", status),
+    };
+    let upgrade_plan = || -> Result<Option<UpgradePlan>> {
+        Ok(AccountStateReader::new(chain_state.as_super())
+            .get_resource::<TwoPhaseUpgradeResource>(*account1.address())?
+            .expect("TwoPhaseUpgrade must exist.")
+            .plan()
+            .cloned())
+    };
+
+    assert_eq!(
+        executed,
+        execute_by_account1(TransactionPayload::Script(
+            encode_update_module_upgrade_strategy_script(ModuleUpgradeStrategy::TwoPhase.into())
+        ))
+    );
+
+    let module_source = r#"
+        module M {
+            public fun hello(){
+            }
+            public fun hello2(){
+            }
+        }
+        "#;
+    let compiled_module = compile_module_with_address(*account1.address(), module_source);
+    let package = Package::new_with_module(compiled_module)?;
+
+    // deploy without upgrade plan should fail.
+    assert_ne!(
+        executed,
+        execute_by_account1(TransactionPayload::Package(package.clone()))
+    );
+    assert_eq!(abort_code(check_package_txn(&package)), 1001);
+
+    // the current block height is 0, the plan is not active until the next block.
+    assert_eq!(
+        executed,
+        execute_by_account1(TransactionPayload::Script(
+            encode_submit_upgrade_plan_script_by_package(&package, 1)
+        ))
+    );
+    let account_reader = AccountStateReader::new(chain_state.as_super());
+    let strategy = account_reader
+        .get_resource::<ModuleUpgradeStrategyResource>(*account1.address())?
+        .expect("ModuleUpgradeStrategy must exist.");
+    assert_eq!(ModuleUpgradeStrategy::TwoPhase, strategy.strategy()?);
+    let plan = upgrade_plan()?.expect("Upgrade plan must exist.");
+    assert_eq!(package.crypto_hash(), plan.package_hash()?);
+    assert_eq!(1, plan.active_after_height());
+
+    // deploy before the plan is active should fail.
+    assert_ne!(
+        executed,
+        execute_by_account1(TransactionPayload::Package(package.clone()))
+    );
+    assert_eq!(abort_code(check_package_txn(&package)), 1003);
+
+    // cancel the plan, the package can not be deployed any more.
+    assert_eq!(
+        executed,
+        execute_by_account1(TransactionPayload::Script(
+            encode_cancel_upgrade_plan_script()
+        ))
+    );
+    assert!(upgrade_plan()?.is_none());
+    assert_eq!(abort_code(check_package_txn(&package)), 1001);
+
+    assert_eq!(
+        executed,
+        execute_by_account1(TransactionPayload::Script(
+            encode_submit_upgrade_plan_script_by_package(&package, 1)
+        ))
+    );
+    let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+        starcoin_crypto::HashValue::random(),
+        SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        *account1.address(),
+        Some(account1.auth_key_prefix()),
+        0,
+    ));
+    let output = execute_and_apply(&chain_state, block_metadata);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    assert_eq!(
+        executed,
+        execute_by_account1(TransactionPayload::Package(package))
+    );
+    // the plan is finished after deploy.
+    assert!(upgrade_plan()?.is_none());
+
+    // the package is not compatible with the on-chain module, `hello2` is removed.
+    let module_source = r#"
+        module M {
+            public fun hello(){
+            }
+        }
+        "#;
+    let compiled_module = compile_module_with_address(*account1.address(), module_source);
+    let incompatible_package = Package::new_with_module(compiled_module)?;
+    assert_eq!(
+        executed,
+        execute_by_account1(TransactionPayload::Script(
+            encode_submit_upgrade_plan_script_by_package(&incompatible_package, 1)
+        ))
+    );
+    assert_ne!(
+        executed,
+        execute_by_account1(TransactionPayload::Package(incompatible_package.clone()))
+    );
+    // the plan is kept, as the deploy fails.
+    let plan = upgrade_plan()?.expect("Upgrade plan must exist.");
+    assert_eq!(incompatible_package.crypto_hash(), plan.package_hash()?);
+
+    Ok(())
+}
+
 #[stest::test]
 fn test_block_metadata() -> Result<()> {
     let chain_config = ChainNetwork::Dev.get_config();
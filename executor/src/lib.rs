@@ -6,7 +6,7 @@ pub use executor::*;
 pub use starcoin_transaction_builder::{
     build_accept_token_txn, build_transfer_from_association, build_transfer_txn,
     build_transfer_txn_by_token_type, create_signed_txn_with_association_account,
-    encode_create_account_script, encode_transfer_script, peer_to_peer_txn_sent_as_association,
+    encode_create_account_script, encode_peer_to_peer_script, peer_to_peer_txn_sent_as_association,
     DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
 };
 
@@ -26,6 +26,7 @@ mod metrics;
 pub mod miner;
 mod ondemand_pacemaker;
 pub mod stratum;
+pub mod txn_selector;
 pub(crate) type TransactionStatusEvent = Arc<Vec<(HashValue, TxStatus)>>;
 
 #[derive(Default, Debug, Message)]
@@ -80,7 +81,10 @@ where
 
             let stratum = sc_stratum::Stratum::start(
                 &config.miner.stratum_server,
-                Arc::new(stratum::StratumManager::new(miner.clone())),
+                Arc::new(
+                    stratum::StratumManager::new(miner.clone())
+                        .with_share_difficulty(config.miner.share_difficulty.map(Into::into)),
+                ),
                 None,
             )
             .unwrap();
@@ -132,11 +136,15 @@ where
         let miner = self.miner.clone();
         let stratum = self.stratum.clone();
         let miner_account = self.miner_account.clone();
-        // block_gas_limit / min_gas_per_txn
-        let max_txns = self.config.miner.block_gas_limit / 600;
+        let block_gas_limit = self.config.miner.block_gas_limit;
+        let min_gas_price = self.config.miner.min_gas_price;
         let enable_mint_empty_block = self.config.miner.enable_mint_empty_block;
         let f = async move {
-            let txns = txpool.get_pending_txns(Some(max_txns), None);
+            let txns = txn_selector::select_txns(
+                txpool.get_pending_txns(None, None),
+                block_gas_limit,
+                min_gas_price,
+            );
             let startup_info = chain.clone().master_startup_info().await?;
             debug!(
                 "On GenerateBlockEvent, master: {:?}, txn len: {}",
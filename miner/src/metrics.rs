@@ -3,8 +3,8 @@
 
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
-    register_histogram_vec, register_int_gauge, HistogramOpts, HistogramVec, IntGauge, Opts,
-    PrometheusError,
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, HistogramOpts,
+    HistogramVec, IntCounterVec, IntGauge, Opts, PrometheusError,
 };
 
 pub static MINER_METRICS: Lazy<MinerMetrics> = Lazy::new(|| MinerMetrics::register().unwrap());
@@ -13,6 +13,9 @@ pub static MINER_METRICS: Lazy<MinerMetrics> = Lazy::new(|| MinerMetrics::regist
 pub struct MinerMetrics {
     pub block_mint_count: IntGauge,
     pub block_mint_time: HistogramVec,
+    /// count of stratum shares, labeled by accepted/rejected/stale/block. Worker names are chosen
+    /// by the stratum clients, so they are not used as labels to keep the cardinality bounded.
+    pub stratum_shares: IntCounterVec,
 }
 
 impl MinerMetrics {
@@ -25,10 +28,15 @@ impl MinerMetrics {
             HistogramOpts::new("block_mint_time", "Histogram of block mint").namespace("starcoin"),
             &["mint_time"]
         )?;
+        let stratum_shares = register_int_counter_vec!(
+            Opts::new("stratum_shares", "Counters of stratum shares").namespace("starcoin"),
+            &["result"]
+        )?;
 
         Ok(Self {
             block_mint_count,
             block_mint_time,
+            stratum_shares,
         })
     }
 }
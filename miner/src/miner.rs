@@ -9,7 +9,9 @@ use config::NodeConfig;
 use crypto::hash::PlainCryptoHash;
 use crypto::HashValue;
 use logger::prelude::*;
+use sc_stratum::ShareResult;
 use starcoin_metrics::HistogramTimer;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -32,6 +34,8 @@ pub struct MineCtx {
     block_template: BlockTemplate,
     difficulty: U256,
     metrics_timer: Option<HistogramTimer>,
+    /// Nonces of the shares submitted for this job.
+    submitted_nonces: HashSet<u64>,
 }
 
 impl MineCtx {
@@ -48,6 +52,7 @@ impl MineCtx {
             block_template,
             difficulty,
             metrics_timer,
+            submitted_nonces: HashSet::new(),
         }
     }
 }
@@ -72,13 +77,81 @@ where
     pub fn get_mint_job(&mut self) -> String {
         let state = self.state.lock().unwrap();
         let x = state.as_ref().unwrap().to_owned();
-        format!(r#"["{:x}","{:x}"]"#, x.header_hash, x.difficulty)
+        format!(
+            r#"["{:x}","{:x}","{}"]"#,
+            x.header_hash, x.difficulty, self.config.miner.consensus_strategy
+        )
     }
 
-    pub fn submit(&self, payload: String) -> Result<()> {
+    /// The job for a pool worker, the worker mines with `share_difficulty` if it is lower than the
+    /// block difficulty, and uses `extranonce` as the high 32 bits of the nonce.
+    pub fn get_worker_job(
+        &self,
+        extranonce: u32,
+        share_difficulty: Option<U256>,
+    ) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        state.as_ref().map(|x| {
+            format!(
+                r#"["{:x}","{:x}","{}","{:08x}"]"#,
+                x.header_hash,
+                Self::job_difficulty(x.difficulty, share_difficulty),
+                self.config.miner.consensus_strategy,
+                extranonce
+            )
+        })
+    }
+
+    fn job_difficulty(difficulty: U256, share_difficulty: Option<U256>) -> U256 {
+        match share_difficulty {
+            Some(share_difficulty) if share_difficulty < difficulty => share_difficulty,
+            _ => difficulty,
+        }
+    }
+
+    /// Validate the share of a pool worker, submit the block if the share meets block difficulty.
+    pub fn submit_share(
+        &self,
+        job_id: &str,
+        nonce_hex: &str,
+        extranonce: u32,
+        share_difficulty: Option<U256>,
+    ) -> Result<ShareResult> {
+        let nonce = u64::from_str_radix(nonce_hex, 16).map_err(|e| {
+            format_err!(
+                "Invalid nonce submit: {}, decode failed:{}",
+                nonce_hex,
+                e.to_string()
+            )
+        })?;
         let mut state = self.state.lock().unwrap();
-        let metrics_timer = state.as_mut().unwrap().metrics_timer.take();
-        let block_template = state.as_ref().unwrap().block_template.clone();
+        let ctx = match state.as_mut() {
+            Some(ctx) => ctx,
+            None => return Ok(ShareResult::Stale),
+        };
+        if job_id != format!("{:x}", ctx.header_hash) {
+            return Ok(ShareResult::Stale);
+        }
+        if (nonce >> 32) as u32 != extranonce || !ctx.submitted_nonces.insert(nonce) {
+            return Ok(ShareResult::Rejected);
+        }
+        let header_hash = ctx.header_hash.to_vec();
+        if C::verify_nonce(&header_hash, nonce, ctx.difficulty) {
+            // Keep the lock, so the block is sealed with the job the share is validated against.
+            self.mint_block(ctx, nonce);
+            Ok(ShareResult::Block)
+        } else if C::verify_nonce(
+            &header_hash,
+            nonce,
+            Self::job_difficulty(ctx.difficulty, share_difficulty),
+        ) {
+            Ok(ShareResult::Accepted)
+        } else {
+            Ok(ShareResult::Rejected)
+        }
+    }
+
+    pub fn submit(&self, payload: String) -> Result<()> {
         let nonce = u64::from_str_radix(&payload, 16).map_err(|e| {
             format_err!(
                 "Invalid payload submit: {}, decode failed:{}",
@@ -86,16 +159,190 @@ where
                 e.to_string()
             )
         })?;
-        let difficulty = state.as_ref().unwrap().difficulty;
-        let block = block_template.into_block(nonce, difficulty);
+        let mut state = self.state.lock().unwrap();
+        let ctx = state
+            .as_mut()
+            .ok_or_else(|| format_err!("No mint job for payload submit: {}", payload))?;
+        self.mint_block(ctx, nonce);
+        Ok(())
+    }
+
+    fn mint_block(&self, ctx: &mut MineCtx, nonce: u64) {
+        let block = ctx.block_template.clone().into_block(nonce, ctx.difficulty);
         info!("Miner new block with id: {:?}", block.id());
         self.bus.do_send(Broadcast {
             msg: MinedBlock(Arc::new(block)),
         });
         MINER_METRICS.block_mint_count.inc();
-        if let Some(timer) = metrics_timer {
+        if let Some(timer) = ctx.metrics_timer.take() {
             timer.observe_duration();
         }
-        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stratum::StratumManager;
+    use actix_rt::System;
+    use sc_stratum::{JobDispatcher, WorkerInfo};
+    use traits::ChainReader;
+    use types::{
+        account_address::AccountAddress,
+        block::{BlockBody, BlockHeader},
+    };
+
+    /// The nonce meets the difficulty if its low 32 bits are not less than the difficulty.
+    #[derive(Clone)]
+    struct MockConsensus;
+
+    impl Consensus for MockConsensus {
+        fn calculate_next_difficulty(_reader: &dyn ChainReader) -> Result<U256> {
+            Ok(1.into())
+        }
+
+        fn solve_consensus_nonce(_header_hash: &[u8], _difficulty: U256) -> u64 {
+            0
+        }
+
+        fn verify(_reader: &dyn ChainReader, _header: &BlockHeader) -> Result<()> {
+            Ok(())
+        }
+
+        fn verify_nonce(_header_hash: &[u8], nonce: u64, difficulty: U256) -> bool {
+            U256::from(nonce & 0xffff_ffff) >= difficulty
+        }
+    }
+
+    const EXTRANONCE: u32 = 1;
+    const SHARE_DIFFICULTY: u64 = 10;
+    const BLOCK_DIFFICULTY: u64 = 100;
+
+    fn mock_miner() -> (Miner<MockConsensus>, String) {
+        let mut miner = Miner::new(BusActor::launch(), Arc::new(NodeConfig::random_for_test()));
+        let block_template = BlockTemplate::new(
+            HashValue::random(),
+            HashValue::random(),
+            0,
+            1,
+            AccountAddress::random(),
+            None,
+            HashValue::random(),
+            HashValue::random(),
+            0,
+            0,
+            None,
+            BlockBody::new(vec![], None),
+        );
+        let ctx = MineCtx::new(block_template, BLOCK_DIFFICULTY.into());
+        let job_id = format!("{:x}", ctx.header_hash);
+        miner.set_mint_job(ctx);
+        (miner, job_id)
+    }
+
+    fn nonce_hex(extranonce: u32, low: u64) -> String {
+        format!("{:x}", ((extranonce as u64) << 32) | low)
+    }
+
+    #[test]
+    fn test_submit_share() {
+        let mut system = System::new("test");
+        system.block_on(async {
+            let (miner, job_id) = mock_miner();
+            let share_difficulty = Some(SHARE_DIFFICULTY.into());
+            let submit_share = |job_id: &str, nonce_hex: &str| {
+                miner
+                    .submit_share(job_id, nonce_hex, EXTRANONCE, share_difficulty)
+                    .unwrap()
+            };
+
+            let accepted = nonce_hex(EXTRANONCE, SHARE_DIFFICULTY);
+            assert_eq!(ShareResult::Accepted, submit_share(&job_id, &accepted));
+            // duplicate share
+            assert_eq!(ShareResult::Rejected, submit_share(&job_id, &accepted));
+            // share below share difficulty
+            let low = nonce_hex(EXTRANONCE, SHARE_DIFFICULTY - 1);
+            assert_eq!(ShareResult::Rejected, submit_share(&job_id, &low));
+            // share of another worker's nonce range
+            let other = nonce_hex(EXTRANONCE + 1, BLOCK_DIFFICULTY);
+            assert_eq!(ShareResult::Rejected, submit_share(&job_id, &other));
+            // share of an outdated job
+            let stale = nonce_hex(EXTRANONCE, BLOCK_DIFFICULTY);
+            assert_eq!(ShareResult::Stale, submit_share("1234", &stale));
+            // share meets block difficulty
+            let block = nonce_hex(EXTRANONCE, BLOCK_DIFFICULTY);
+            assert_eq!(ShareResult::Block, submit_share(&job_id, &block));
+            assert!(miner
+                .state
+                .lock()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .metrics_timer
+                .is_none());
+
+            assert!(miner
+                .submit_share(&job_id, "not a nonce", EXTRANONCE, share_difficulty)
+                .is_err());
+        });
+    }
+
+    #[test]
+    fn test_submit_without_job() {
+        let mut system = System::new("test");
+        system.block_on(async {
+            let miner = Miner::<MockConsensus>::new(
+                BusActor::launch(),
+                Arc::new(NodeConfig::random_for_test()),
+            );
+            assert!(miner.submit(nonce_hex(EXTRANONCE, 0)).is_err());
+            assert_eq!(
+                ShareResult::Stale,
+                miner
+                    .submit_share("1234", &nonce_hex(EXTRANONCE, 0), EXTRANONCE, None)
+                    .unwrap()
+            );
+        });
+    }
+
+    #[test]
+    fn test_stratum_shares_metrics() {
+        let mut system = System::new("test");
+        system.block_on(async {
+            let (miner, job_id) = mock_miner();
+            let manager =
+                StratumManager::new(miner).with_share_difficulty(Some(SHARE_DIFFICULTY.into()));
+            let worker = WorkerInfo {
+                name: "test_stratum_shares_metrics".to_string(),
+                extranonce: EXTRANONCE,
+            };
+            let count = |result: &str| {
+                MINER_METRICS
+                    .stratum_shares
+                    .with_label_values(&[result])
+                    .get()
+            };
+            let before: Vec<_> = ["accepted", "rejected", "stale", "block"]
+                .iter()
+                .map(|result| count(result))
+                .collect();
+            let shares = vec![
+                (job_id.clone(), SHARE_DIFFICULTY, ShareResult::Accepted),
+                (job_id.clone(), SHARE_DIFFICULTY, ShareResult::Rejected),
+                (job_id.clone(), 0, ShareResult::Rejected),
+                ("1234".to_string(), SHARE_DIFFICULTY + 1, ShareResult::Stale),
+                (job_id, BLOCK_DIFFICULTY, ShareResult::Block),
+            ];
+            for (job_id, low, expect) in shares {
+                let result = manager
+                    .submit_share(&worker, vec![job_id, nonce_hex(EXTRANONCE, low)])
+                    .unwrap();
+                assert_eq!(expect, result);
+            }
+            assert_eq!(before[0] + 2, count("accepted"));
+            assert_eq!(before[1] + 2, count("rejected"));
+            assert_eq!(before[2] + 1, count("stale"));
+            assert_eq!(before[3] + 1, count("block"));
+        });
     }
 }
@@ -1,18 +1,20 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::metrics::MINER_METRICS;
 use crate::miner::{MineCtx, Miner};
 use logger::prelude::*;
 use sc_stratum::*;
 use std::sync::Arc;
 use traits::{ChainReader, Consensus};
-use types::block::BlockTemplate;
+use types::{block::BlockTemplate, U256};
 
 pub struct StratumManager<C>
 where
     C: Consensus + Sync + Send + 'static,
 {
     miner: Miner<C>,
+    share_difficulty: Option<U256>,
 }
 
 impl<C> StratumManager<C>
@@ -20,7 +22,16 @@ where
     C: Consensus + Sync + Send + 'static,
 {
     pub fn new(miner: Miner<C>) -> Self {
-        Self { miner }
+        Self {
+            miner,
+            share_difficulty: None,
+        }
+    }
+
+    /// Workers submit shares with `share_difficulty` when it is lower than the block difficulty.
+    pub fn with_share_difficulty(mut self, share_difficulty: Option<U256>) -> Self {
+        self.share_difficulty = share_difficulty;
+        self
     }
 }
 
@@ -28,10 +39,54 @@ impl<C> JobDispatcher for StratumManager<C>
 where
     C: Consensus + Sync + Send + 'static,
 {
+    fn worker_job(&self, payload: &str, worker: &WorkerInfo) -> String {
+        self.miner
+            .get_worker_job(worker.extranonce, self.share_difficulty)
+            .unwrap_or_else(|| payload.to_owned())
+    }
+
     fn submit(&self, payload: Vec<String>) -> Result<(), Error> {
-        //todo:: error handle
-        let _ = self.miner.submit(payload[0].clone());
-        Ok(())
+        self.miner
+            .submit(payload[0].clone())
+            .map_err(|e| Error::Dispatch(e.to_string()))
+    }
+
+    fn submit_share(
+        &self,
+        worker: &WorkerInfo,
+        payload: Vec<String>,
+    ) -> Result<ShareResult, Error> {
+        if payload.len() < 2 {
+            return Err(Error::Dispatch(format!(
+                "Invalid share payload: {:?}",
+                payload
+            )));
+        }
+        let share_result = self
+            .miner
+            .submit_share(
+                &payload[0],
+                &payload[1],
+                worker.extranonce,
+                self.share_difficulty,
+            )
+            .map_err(|e| Error::Dispatch(e.to_string()))?;
+        let result = match share_result {
+            ShareResult::Block | ShareResult::Accepted => "accepted",
+            ShareResult::Rejected => "rejected",
+            ShareResult::Stale => "stale",
+        };
+        MINER_METRICS
+            .stratum_shares
+            .with_label_values(&[result])
+            .inc();
+        if share_result == ShareResult::Block {
+            MINER_METRICS
+                .stratum_shares
+                .with_label_values(&["block"])
+                .inc();
+        }
+        Ok(share_result)
     }
 }
 
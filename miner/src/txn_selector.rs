@@ -0,0 +1,155 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use types::account_address::AccountAddress;
+use types::transaction::SignedUserTransaction;
+
+/// The next txn of a sender, ordered by gas price, then by the position in pending txns.
+#[derive(Eq, PartialEq)]
+struct SenderHead {
+    gas_unit_price: u64,
+    position: Reverse<usize>,
+    sender: AccountAddress,
+}
+
+impl Ord for SenderHead {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.gas_unit_price
+            .cmp(&other.gas_unit_price)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for SenderHead {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Select txns from `pending` to pack into a block.
+/// Txns of each sender are taken in sequence number order, and the sender with the highest gas
+/// price of its next txn goes first. A txn is selected only if its `max_gas_amount` fits in the
+/// gas left of `block_gas_limit`, and its gas price is not less than `min_gas_price`, otherwise
+/// the txn and the later txns of the sender are skipped.
+pub fn select_txns(
+    pending: Vec<SignedUserTransaction>,
+    block_gas_limit: u64,
+    min_gas_price: u64,
+) -> Vec<SignedUserTransaction> {
+    let mut sender_txns: HashMap<AccountAddress, Vec<(usize, SignedUserTransaction)>> =
+        HashMap::new();
+    for (position, txn) in pending.into_iter().enumerate() {
+        sender_txns
+            .entry(txn.sender())
+            .or_insert_with(Vec::new)
+            .push((position, txn));
+    }
+
+    let mut heads = BinaryHeap::new();
+    for (sender, txns) in sender_txns.iter_mut() {
+        // Sort in reverse, so pop the txn with the lowest sequence number.
+        txns.sort_by_key(|(_, txn)| Reverse(txn.sequence_number()));
+        if let Some((position, txn)) = txns.last() {
+            heads.push(SenderHead {
+                gas_unit_price: txn.gas_unit_price(),
+                position: Reverse(*position),
+                sender: *sender,
+            });
+        }
+    }
+
+    let mut gas_left = block_gas_limit;
+    let mut selected = vec![];
+    while let Some(head) = heads.pop() {
+        let txns = sender_txns
+            .get_mut(&head.sender)
+            .expect("sender of head must exist");
+        let (_, txn) = txns.pop().expect("head txn must exist");
+        if txn.gas_unit_price() < min_gas_price || txn.max_gas_amount() > gas_left {
+            continue;
+        }
+        gas_left -= txn.max_gas_amount();
+        selected.push(txn);
+        if let Some((position, next_txn)) = txns.last() {
+            heads.push(SenderHead {
+                gas_unit_price: next_txn.gas_unit_price(),
+                position: Reverse(*position),
+                sender: head.sender,
+            });
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::keygen::KeyGen;
+    use types::transaction::{RawUserTransaction, Script, TransactionPayload};
+
+    fn txn(
+        sender: AccountAddress,
+        sequence_number: u64,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+    ) -> SignedUserTransaction {
+        let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+        RawUserTransaction::new(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            max_gas_amount,
+            gas_unit_price,
+            u64::max_value(),
+        )
+        .sign(&private_key, public_key)
+        .unwrap()
+        .into_inner()
+    }
+
+    #[test]
+    fn test_select_by_gas_price_in_sequence_order() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let alice_0 = txn(alice, 0, 100, 1);
+        let alice_1 = txn(alice, 1, 100, 10);
+        let bob_0 = txn(bob, 0, 100, 5);
+        let selected = select_txns(
+            vec![alice_1.clone(), bob_0.clone(), alice_0.clone()],
+            1000,
+            0,
+        );
+        assert_eq!(selected, vec![bob_0, alice_0, alice_1]);
+    }
+
+    #[test]
+    fn test_select_within_block_gas_limit() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let carol = AccountAddress::random();
+        let alice_0 = txn(alice, 0, 800, 10);
+        let bob_0 = txn(bob, 0, 300, 5);
+        let bob_1 = txn(bob, 1, 50, 5);
+        let carol_0 = txn(carol, 0, 100, 1);
+        let selected = select_txns(
+            vec![alice_0.clone(), bob_0, bob_1, carol_0.clone()],
+            1000,
+            0,
+        );
+        // bob_0 exceeds the gas left, so bob_1 can not be packed too.
+        assert_eq!(selected, vec![alice_0, carol_0]);
+    }
+
+    #[test]
+    fn test_skip_txns_below_min_gas_price() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let alice_0 = txn(alice, 0, 100, 1);
+        let alice_1 = txn(alice, 1, 100, 10);
+        let bob_0 = txn(bob, 0, 100, 5);
+        let selected = select_txns(vec![alice_0, alice_1, bob_0.clone()], 1000, 2);
+        assert_eq!(selected, vec![bob_0]);
+    }
+}
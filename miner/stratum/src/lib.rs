@@ -22,7 +22,9 @@ use std::net::{Shutdown, SocketAddr};
 
 mod traits;
 
-pub use traits::{Error, JobDispatcher, PushWorkHandler, ServiceConfiguration};
+pub use traits::{
+    Error, JobDispatcher, PushWorkHandler, ServiceConfiguration, ShareResult, WorkerInfo,
+};
 
 use jsonrpc_core::{to_value, Compatibility, IoDelegate, MetaIoHandler, Metadata, Params, Value};
 use jsonrpc_tcp_server::{
@@ -67,6 +69,8 @@ impl Stratum {
             job_que: RwLock::default(),
             dispatcher,
             workers: Arc::new(RwLock::default()),
+            extranonces: RwLock::default(),
+            next_extranonce: RwLock::new(0),
             secret,
             notify_counter: RwLock::new(NOTIFY_COUNTER_INITIAL),
         });
@@ -122,8 +126,12 @@ struct StratumImpl {
     job_que: RwLock<HashSet<SocketAddr>>,
     /// Payload manager
     dispatcher: Arc<dyn JobDispatcher>,
-    /// Authorized workers (socket - worker_id)
-    workers: Arc<RwLock<HashMap<SocketAddr, String>>>,
+    /// Authorized workers (socket - worker)
+    workers: Arc<RwLock<HashMap<SocketAddr, WorkerInfo>>>,
+    /// Extranonce of every connection
+    extranonces: RwLock<HashMap<SocketAddr, u32>>,
+    /// Next extranonce to assign
+    next_extranonce: RwLock<u32>,
     /// Secret if any
     secret: Option<H256>,
     /// Dispatch notify counter
@@ -131,12 +139,23 @@ struct StratumImpl {
 }
 
 impl StratumImpl {
+    /// Extranonce of the connection, assign a new one if absent.
+    fn extranonce(&self, addr: &SocketAddr) -> u32 {
+        *self.extranonces.write().entry(*addr).or_insert_with(|| {
+            let mut next_extranonce = self.next_extranonce.write();
+            let extranonce = *next_extranonce;
+            *next_extranonce = next_extranonce.wrapping_add(1);
+            extranonce
+        })
+    }
+
     /// rpc method `mining.subscribe`
     fn subscribe(&self, _params: Params, meta: SocketMetadata) -> RpcResult {
         use std::str::FromStr;
 
         self.subscribers.write().push(*meta.addr());
         self.job_que.write().insert(*meta.addr());
+        let extranonce = self.extranonce(meta.addr());
         trace!(target: "stratum", "Subscription request from {:?}", meta.addr());
 
         Ok(match self.dispatcher.initial() {
@@ -147,9 +166,14 @@ impl StratumImpl {
                     to_value(&[0u8; 0])
                 }
             },
-            None => to_value(&[0u8; 0]),
+            // [[["mining.notify", subscription_id]], extranonce1, extranonce2_size]
+            None => to_value((
+                vec![("mining.notify", format!("{:08x}", extranonce))],
+                format!("{:08x}", extranonce),
+                4,
+            )),
         }
-        .expect("Empty slices are serializable; qed"))
+        .expect("Subscribe result is always serializable; qed"))
     }
 
     /// rpc method `mining.authorize`
@@ -164,30 +188,43 @@ impl StratumImpl {
                     }
                 }
                 trace!(target: "stratum", "New worker #{} registered", worker_id);
-                self.workers.write().insert(*meta.addr(), worker_id);
+                let worker = WorkerInfo {
+                    name: worker_id,
+                    extranonce: self.extranonce(meta.addr()),
+                };
+                self.workers.write().insert(*meta.addr(), worker);
                 to_value(true)
             })
             .map(|v| v.expect("Only true/false is returned and it's always serializable; qed"))
     }
 
     /// rpc method `mining.submit`
-    fn submit(&self, params: Params, _meta: SocketMetadata) -> RpcResult {
+    fn submit(&self, params: Params, meta: SocketMetadata) -> RpcResult {
+        let worker = match self.workers.read().get(meta.addr()) {
+            Some(worker) => worker.clone(),
+            None => {
+                trace!(target: "stratum", "Submit from unauthorized worker {:?}", meta.addr());
+                return Ok(to_value(false).expect("bool is always serializable; qed"));
+            }
+        };
         Ok(match params {
             Params::Array(vals) => {
-                // first two elements are service messages (worker_id & job_id)
-                match self.dispatcher.submit(
+                // first element is service message (worker_id), the job_id is passed to dispatcher
+                match self.dispatcher.submit_share(
+                    &worker,
                     vals.iter()
-                        .skip(2)
-                        .filter_map(|val| match *val {
-                            Value::String(ref s) => Some(s.to_owned()),
-                            _ => None,
+                        .skip(1)
+                        .map(|val| match *val {
+                            Value::String(ref s) => s.to_owned(),
+                            ref val => val.to_string(),
                         })
                         .collect::<Vec<String>>(),
                 ) {
-                    Ok(()) => {
+                    Ok(share_result) => {
+                        trace!(target: "stratum", "Share from #{}: {:?}", worker.name, share_result);
                         // Do not update peers in submit
                         //self.update_peers(&meta.tcp_dispatcher.expect("tcp_dispatcher is always initialized; qed"));
-                        to_value(true)
+                        to_value(share_result.is_accepted())
                     }
                     Err(submit_err) => {
                         warn!("Error while submitting share: {:?}", submit_err);
@@ -226,14 +263,15 @@ impl StratumImpl {
             };
 
             let mut hup_peers = HashSet::with_capacity(0); // most of the cases won't be needed, hence avoid allocation
-            let workers_msg = format!(
-                "{{ \"id\": {}, \"method\": \"mining.notify\", \"params\": {} }}",
-                next_request_id, payload
-            );
-            trace!(target: "stratum", "pushing work for {} workers (payload: '{}')", workers.len(), &workers_msg);
-            for (ref addr, _) in workers.iter() {
+            trace!(target: "stratum", "pushing work for {} workers (payload: '{}')", workers.len(), &payload);
+            for (ref addr, worker) in workers.iter() {
                 trace!(target: "stratum", "pusing work to {}", addr);
-                match tcp_dispatcher.push_message(addr, workers_msg.clone()) {
+                let workers_msg = format!(
+                    "{{ \"id\": {}, \"method\": \"mining.notify\", \"params\": {} }}",
+                    next_request_id,
+                    self.dispatcher.worker_job(&payload, worker)
+                );
+                match tcp_dispatcher.push_message(addr, workers_msg) {
                     Err(PushMessageError::NoSuchPeer) => {
                         trace!(target: "stratum", "Worker no longer connected: {}", addr);
                         hup_peers.insert(**addr);
@@ -249,8 +287,10 @@ impl StratumImpl {
 
         if !hup_peers.is_empty() {
             let mut workers = self.workers.write();
+            let mut extranonces = self.extranonces.write();
             for hup_peer in hup_peers {
                 workers.remove(&hup_peer);
+                extranonces.remove(&hup_peer);
             }
         }
 
@@ -461,6 +501,43 @@ mod tests {
         assert_eq!(1, stratum.implementation.workers.read().len());
     }
 
+    #[test]
+    fn assigns_extranonce_per_worker() {
+        let addr = format!("127.0.0.1:{}", starcoin_config::get_random_available_port())
+            .parse()
+            .unwrap();
+        let stratum = Stratum::start(&addr, Arc::new(VoidManager), None)
+            .expect("There should be no error starting stratum");
+
+        let request = r#"{"jsonrpc": "2.0", "method": "mining.authorize", "params": ["miner1", ""], "id": 1}"#;
+        dummy_request(&addr, request);
+        dummy_request(&addr, request);
+
+        let workers = stratum.implementation.workers.read();
+        let extranonces = workers
+            .values()
+            .map(|worker| worker.extranonce)
+            .collect::<HashSet<_>>();
+        assert_eq!(2, extranonces.len());
+    }
+
+    #[test]
+    fn rejects_unauthorized_submit() {
+        let addr = format!("127.0.0.1:{}", starcoin_config::get_random_available_port())
+            .parse()
+            .unwrap();
+        let _stratum = Stratum::start(&addr, Arc::new(VoidManager), None)
+            .expect("There should be no error starting stratum");
+
+        let request = r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "00", "01"], "id": 3}"#;
+        let response = String::from_utf8(dummy_request(&addr, request)).unwrap();
+
+        assert_eq!(
+            terminated_str(r#"{"jsonrpc":"2.0","result":false,"id":3}"#),
+            response
+        );
+    }
+
     #[test]
     fn can_push_work() {
         starcoin_logger::init_for_test();
@@ -24,6 +24,38 @@ impl From<PushMessageError> for Error {
     }
 }
 
+/// A worker authorized by `mining.authorize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerInfo {
+    /// Worker name from `mining.authorize`.
+    pub name: String,
+    /// Assigned by the server for every connection, the worker should use it as the high 32 bits
+    /// of the nonce, so workers do not search the same nonce range.
+    pub extranonce: u32,
+}
+
+/// Result of a share submitted by a worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareResult {
+    /// The share meets the block difficulty, a new block is mined.
+    Block,
+    /// The share meets the share difficulty.
+    Accepted,
+    /// The share is invalid or duplicated.
+    Rejected,
+    /// The share is for an outdated job.
+    Stale,
+}
+
+impl ShareResult {
+    pub fn is_accepted(self) -> bool {
+        match self {
+            ShareResult::Block | ShareResult::Accepted => true,
+            ShareResult::Rejected | ShareResult::Stale => false,
+        }
+    }
+}
+
 /// Interface that can provide pow/blockchain-specific responses for the clients
 pub trait JobDispatcher: Send + Sync {
     // json for initial client handshake
@@ -38,8 +70,21 @@ pub trait JobDispatcher: Send + Sync {
     fn job(&self) -> Option<String> {
         None
     }
+    // json for job update of the worker, split from the job pushed to all workers
+    fn worker_job(&self, payload: &str, _worker: &WorkerInfo) -> String {
+        payload.to_owned()
+    }
     // miner job result
     fn submit(&self, payload: Vec<String>) -> Result<(), Error>;
+    // miner share result of the worker, `payload` starts with the job id
+    fn submit_share(
+        &self,
+        _worker: &WorkerInfo,
+        payload: Vec<String>,
+    ) -> Result<ShareResult, Error> {
+        self.submit(payload.into_iter().skip(1).collect())
+            .map(|_| ShareResult::Block)
+    }
 }
 
 /// Interface that can handle requests to push job for workers
@@ -11,6 +11,7 @@ use network::network::NetworkActor;
 use starcoin_genesis::Genesis;
 use starcoin_miner::MinerActor;
 use starcoin_miner::MinerClientActor;
+use starcoin_sync_api::SyncProgress;
 use starcoin_wallet_api::WalletAccount;
 use std::sync::Arc;
 use storage::cache_storage::CacheStorage;
@@ -106,6 +107,7 @@ fn test_miner_with_ondemand_pacemaker() {
             txpool.get_service(),
             network.clone(),
             storage.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
 
@@ -21,6 +21,7 @@
 
 mod peersstate;
 
+use futures::channel::oneshot;
 use futures::prelude::*;
 use log::{debug, error, trace};
 use serde_json::json;
@@ -58,6 +59,10 @@ enum Action {
     SetPriorityGroup(String, HashSet<PeerId>),
     AddToPriorityGroup(String, PeerId),
     RemoveFromPriorityGroup(String, PeerId),
+    BanPeer(PeerId),
+    UnbanPeer(PeerId),
+    PeerScores(oneshot::Sender<Vec<PeerScore>>),
+    ReservedPeers(oneshot::Sender<(Vec<PeerId>, bool)>),
 }
 
 /// Description of a reputation adjustment for a node.
@@ -84,6 +89,16 @@ impl ReputationChange {
     }
 }
 
+/// Reputation state of a known node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerScore {
+    pub peer_id: PeerId,
+    pub reputation: i32,
+    pub connected: bool,
+    /// Whether the node is banned manually, banned nodes never recover reputation over time.
+    pub banned: bool,
+}
+
 /// Shared handle to the peer set manager (PSM). Distributed around the code.
 #[derive(Debug, Clone)]
 pub struct PeersetHandle {
@@ -141,6 +156,34 @@ impl PeersetHandle {
             .tx
             .unbounded_send(Action::RemoveFromPriorityGroup(group_id, peer_id));
     }
+
+    /// Ban a peer, the peer is disconnected and never be connected again until `unban_peer`.
+    pub fn ban_peer(&self, peer_id: PeerId) {
+        let _ = self.tx.unbounded_send(Action::BanPeer(peer_id));
+    }
+
+    /// Unban a previously banned peer and reset its reputation.
+    pub fn unban_peer(&self, peer_id: PeerId) {
+        let _ = self.tx.unbounded_send(Action::UnbanPeer(peer_id));
+    }
+
+    /// Returns the reputation of all known nodes.
+    pub async fn peer_scores(&self) -> Result<Vec<PeerScore>, ()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .unbounded_send(Action::PeerScores(tx))
+            .map_err(|_| ())?;
+        rx.await.map_err(|_| ())
+    }
+
+    /// Returns the reserved peers and whether the peerset only connects to reserved peers.
+    pub async fn reserved_peers(&self) -> Result<(Vec<PeerId>, bool), ()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .unbounded_send(Action::ReservedPeers(tx))
+            .map_err(|_| ())?;
+        rx.await.map_err(|_| ())
+    }
 }
 
 /// Message that can be sent by the peer set manager (PSM).
@@ -218,6 +261,8 @@ pub struct Peerset {
     created: Instant,
     /// Last time when we updated the reputations of connected nodes.
     latest_time_update: Instant,
+    /// Nodes banned manually, they keep the minimum reputation until unbanned.
+    banned: HashSet<PeerId>,
 }
 
 impl Peerset {
@@ -238,6 +283,7 @@ impl Peerset {
             message_queue: VecDeque::new(),
             created: now,
             latest_time_update: now,
+            banned: HashSet::new(),
         };
 
         for node in config.priority_groups.into_iter().flat_map(|(_, l)| l) {
@@ -386,6 +432,60 @@ impl Peerset {
         }
     }
 
+    fn on_ban_peer(&mut self, peer_id: PeerId) {
+        self.update_time();
+        debug!(target: "peerset", "Ban {}", peer_id);
+        self.banned.insert(peer_id.clone());
+        match self.data.peer(&peer_id) {
+            peersstate::Peer::Connected(mut peer) => {
+                peer.set_reputation(i32::min_value());
+                peer.disconnect();
+                self.message_queue.push_back(Message::Drop(peer_id));
+            }
+            peersstate::Peer::NotConnected(mut peer) => peer.set_reputation(i32::min_value()),
+            peersstate::Peer::Unknown(peer) => peer.discover().set_reputation(i32::min_value()),
+        }
+    }
+
+    fn on_unban_peer(&mut self, peer_id: PeerId) {
+        if !self.banned.remove(&peer_id) {
+            return;
+        }
+        debug!(target: "peerset", "Unban {}", peer_id);
+        match self.data.peer(&peer_id) {
+            peersstate::Peer::Connected(mut peer) => peer.set_reputation(0),
+            peersstate::Peer::NotConnected(mut peer) => peer.set_reputation(0),
+            peersstate::Peer::Unknown(_) => {}
+        }
+        self.alloc_slots();
+    }
+
+    fn on_peer_scores(&mut self, sender: oneshot::Sender<Vec<PeerScore>>) {
+        self.update_time();
+        let scores = self
+            .data
+            .peers()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .filter_map(|peer_id| {
+                let (reputation, connected) = match self.data.peer(&peer_id) {
+                    peersstate::Peer::Connected(entry) => (entry.reputation(), true),
+                    peersstate::Peer::NotConnected(entry) => (entry.reputation(), false),
+                    peersstate::Peer::Unknown(_) => return None,
+                };
+                let banned = self.banned.contains(&peer_id);
+                Some(PeerScore {
+                    peer_id,
+                    reputation,
+                    connected,
+                    banned,
+                })
+            })
+            .collect();
+        let _ = sender.send(scores);
+    }
+
     /// Updates the value of `self.latest_time_update` and performs all the updates that happen
     /// over time, such as reputation increases for staying connected.
     fn update_time(&mut self) {
@@ -406,6 +506,10 @@ impl Peerset {
         // empirically determine a value of `k` that looks correct.
         for _ in 0..secs_diff {
             for peer_id in self.data.peers().cloned().collect::<Vec<_>>() {
+                // Banned nodes are only released by `unban_peer`.
+                if self.banned.contains(&peer_id) {
+                    continue;
+                }
                 // We use `k = 0.98`, so we divide by `50`. With that value, it takes 34.3 seconds
                 // to reduce the reputation by half.
                 fn reput_tick(reput: i32) -> i32 {
@@ -452,11 +556,12 @@ impl Peerset {
         loop {
             let next = {
                 let data = &mut self.data;
+                let banned = &self.banned;
                 self.priority_groups
                     .get(RESERVED_NODES)
                     .into_iter()
                     .flatten()
-                    .find(move |n| data.peer(n).into_connected().is_none())
+                    .find(move |n| !banned.contains(n) && data.peer(n).into_connected().is_none())
                     .cloned()
             };
 
@@ -491,10 +596,11 @@ impl Peerset {
         loop {
             let next = {
                 let data = &mut self.data;
+                let banned = &self.banned;
                 self.priority_groups
                     .values()
                     .flatten()
-                    .find(move |n| data.peer(n).into_connected().is_none())
+                    .find(move |n| !banned.contains(n) && data.peer(n).into_connected().is_none())
                     .cloned()
             };
 
@@ -550,6 +656,11 @@ impl Peerset {
         trace!(target: "peerset", "Incoming {:?}", peer_id);
         self.update_time();
 
+        if self.banned.contains(&peer_id) {
+            self.message_queue.push_back(Message::Reject(index));
+            return;
+        }
+
         if self.reserved_only
             && !self
                 .priority_groups
@@ -700,6 +811,16 @@ impl Stream for Peerset {
                 Action::RemoveFromPriorityGroup(group_id, peer_id) => {
                     self.on_remove_from_priority_group(&group_id, peer_id)
                 }
+                Action::BanPeer(peer_id) => self.on_ban_peer(peer_id),
+                Action::UnbanPeer(peer_id) => self.on_unban_peer(peer_id),
+                Action::PeerScores(sender) => self.on_peer_scores(sender),
+                Action::ReservedPeers(sender) => {
+                    let reserved_peers = self
+                        .priority_group(RESERVED_NODES)
+                        .map(|peers| peers.cloned().collect())
+                        .unwrap_or_default();
+                    let _ = sender.send((reserved_peers, self.reserved_only));
+                }
             }
         }
     }
@@ -755,6 +876,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_peerset_reserved_peers() {
+        let reserved_peer = PeerId::random();
+        let (peerset, handle) = Peerset::from_config(PeersetConfig {
+            in_peers: 0,
+            out_peers: 2,
+            bootnodes: vec![],
+            reserved_only: false,
+            priority_groups: Vec::new(),
+        });
+        handle.add_reserved_peer(reserved_peer.clone());
+        handle.set_reserved_only(true);
+
+        let (reserved_peers, reserved_only) = futures::executor::block_on(async move {
+            let drive = peerset.for_each(|_| async {});
+            futures::pin_mut!(drive);
+            let query = handle.reserved_peers();
+            futures::pin_mut!(query);
+            match future::select(query, drive).await {
+                future::Either::Left((result, _)) => result.unwrap(),
+                future::Either::Right(_) => panic!("peerset stream should never end"),
+            }
+        });
+        assert_eq!(reserved_peers, vec![reserved_peer]);
+        assert!(reserved_only);
+    }
+
     #[test]
     fn test_peerset_incoming() {
         let bootnode = PeerId::random();
@@ -875,4 +1023,43 @@ mod tests {
 
         futures::executor::block_on(fut);
     }
+
+    #[test]
+    fn test_peerset_ban_and_unban() {
+        let (mut peerset, handle) = Peerset::from_config(PeersetConfig {
+            in_peers: 25,
+            out_peers: 25,
+            bootnodes: vec![],
+            reserved_only: false,
+            priority_groups: vec![],
+        });
+
+        let peer_id = PeerId::random();
+        handle.ban_peer(peer_id.clone());
+
+        let fut = futures::future::poll_fn(move |cx| {
+            assert_eq!(Stream::poll_next(Pin::new(&mut peerset), cx), Poll::Pending);
+
+            // A banned node do not recover over time.
+            thread::sleep(Duration::from_millis(1500));
+            peerset.incoming(peer_id.clone(), IncomingIndex(1));
+            if let Poll::Ready(msg) = Stream::poll_next(Pin::new(&mut peerset), cx) {
+                assert_eq!(msg.unwrap(), Message::Reject(IncomingIndex(1)));
+            } else {
+                panic!()
+            }
+
+            // Once unbanned, the node is treated as a fresh node and we try to connect it.
+            handle.unban_peer(peer_id.clone());
+            if let Poll::Ready(msg) = Stream::poll_next(Pin::new(&mut peerset), cx) {
+                assert_eq!(msg.unwrap(), Message::Connect(peer_id.clone()));
+            } else {
+                panic!()
+            }
+
+            Poll::Ready(())
+        });
+
+        futures::executor::block_on(fut);
+    }
 }
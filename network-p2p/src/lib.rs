@@ -17,6 +17,7 @@ pub use libp2p::{
     },
     multiaddr as build_multiaddr,
 };
+pub use peerset::{PeerScore, ReputationChange};
 
 mod behaviour;
 pub mod config;
@@ -44,7 +44,7 @@ use libp2p::swarm::{
 use libp2p::{kad::record, PeerId};
 use log::{error, info, trace, warn};
 use parking_lot::Mutex;
-use peerset::{PeersetHandle, ReputationChange};
+use peerset::{PeerScore, PeersetHandle, ReputationChange};
 use types::peer_info::PeerInfo;
 
 use crate::config::{Params, TransportConfig};
@@ -530,6 +530,27 @@ impl NetworkService {
         self.peerset.report_peer(who, cost_benefit);
     }
 
+    /// Ban a peer, the peer is disconnected and can not connect to us until `unban_peer`.
+    pub fn ban_peer(&self, who: PeerId) {
+        self.peerset.ban_peer(who);
+    }
+
+    /// Unban a previously banned peer.
+    pub fn unban_peer(&self, who: PeerId) {
+        self.peerset.unban_peer(who);
+    }
+
+    /// Returns the reputation of all known peers.
+    pub async fn peer_scores(&self) -> Vec<PeerScore> {
+        match self.peerset.peer_scores().await {
+            Ok(scores) => scores,
+            Err(_) => {
+                warn!("peerset is closed when get peer scores.");
+                vec![]
+            }
+        }
+    }
+
     /// Disconnect from a node as soon as possible.
     ///
     /// This triggers the same effects as if the connection had closed itself spontaneously.
@@ -590,6 +611,27 @@ impl NetworkService {
         Ok(())
     }
 
+    /// Adds the address of a peer, the peer is treated as a discovered peer and connected
+    /// if there is free slot. The string should encode the address and peer ID of the remote node.
+    pub fn add_peer(&self, peer: String) -> Result<(), String> {
+        let (peer_id, addr) = parse_str_addr(&peer).map_err(|e| format!("{:?}", e))?;
+        let _ = self
+            .to_worker
+            .unbounded_send(ServiceToWorkerMsg::AddKnownAddress(peer_id, addr));
+        Ok(())
+    }
+
+    /// Returns the reserved peers and whether only reserved peers are accepted.
+    pub async fn reserved_peers(&self) -> (Vec<PeerId>, bool) {
+        match self.peerset.reserved_peers().await {
+            Ok(result) => result,
+            Err(_) => {
+                warn!("peerset is closed when get reserved peers.");
+                (vec![], false)
+            }
+        }
+    }
+
     /// Modify a peerset priority group.
     pub fn set_priority_group(
         &self,
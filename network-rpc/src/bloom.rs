@@ -0,0 +1,95 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::HashValue;
+use serde::{Deserialize, Serialize};
+
+/// Max bits of the filter, about 128KB.
+const MAX_BITS: usize = 1 << 20;
+/// Bits per item, for about 1% false positive rate.
+const BITS_PER_ITEM: usize = 10;
+const HASH_COUNT: u8 = 7;
+/// Every bit index is taken from 4 bytes of the hash, so a 32 bytes hash supports 8 indexes.
+const MAX_HASH_COUNT: u8 = 8;
+
+/// Bloom filter of txn hashes, used to tell peers which txns we already have.
+/// Txn hashes are uniformly distributed, so the bit indexes are taken from the hash directly.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TxnBloomFilter {
+    bits: Vec<u64>,
+    hash_count: u8,
+}
+
+impl TxnBloomFilter {
+    /// Create a filter for about `items` hashes.
+    pub fn new(items: usize) -> Self {
+        let bits = (items.max(1) * BITS_PER_ITEM).min(MAX_BITS);
+        Self {
+            bits: vec![0; (bits + 63) / 64],
+            hash_count: HASH_COUNT,
+        }
+    }
+
+    pub fn insert(&mut self, hash: &HashValue) {
+        for index in self.bit_indexes(hash) {
+            self.bits[index / 64] |= 1 << (index % 64);
+        }
+    }
+
+    pub fn contains(&self, hash: &HashValue) -> bool {
+        if self.bits.is_empty() {
+            return false;
+        }
+        self.bit_indexes(hash)
+            .into_iter()
+            .all(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    fn bit_indexes(&self, hash: &HashValue) -> Vec<usize> {
+        let total_bits = self.bits.len() * 64;
+        let bytes = hash.to_vec();
+        bytes
+            .chunks(4)
+            .take(self.hash_count.min(MAX_HASH_COUNT) as usize)
+            .map(|chunk| {
+                let mut index = [0u8; 4];
+                index.copy_from_slice(chunk);
+                u32::from_le_bytes(index) as usize % total_bits
+            })
+            .collect()
+    }
+}
+
+impl Extend<HashValue> for TxnBloomFilter {
+    fn extend<I: IntoIterator<Item = HashValue>>(&mut self, iter: I) {
+        for hash in iter {
+            self.insert(&hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_txn_bloom_filter() {
+        let hashes = (0..1000).map(|_| HashValue::random()).collect::<Vec<_>>();
+        let mut filter = TxnBloomFilter::new(hashes.len());
+        filter.extend(hashes.clone());
+        assert!(hashes.iter().all(|hash| filter.contains(hash)));
+        let false_positive = (0..1000)
+            .filter(|_| filter.contains(&HashValue::random()))
+            .count();
+        assert!(false_positive < 50, "false positive {}", false_positive);
+    }
+
+    #[test]
+    fn test_empty_filter() {
+        let filter = TxnBloomFilter {
+            bits: vec![],
+            hash_count: 100,
+        };
+        assert!(!filter.contains(&HashValue::random()));
+    }
+}
@@ -16,11 +16,13 @@ use txpool::TxPoolService;
 use types::block::{BlockHeader, BlockNumber};
 use types::transaction::SignedUserTransaction;
 
+mod bloom;
 mod rpc;
 mod rpc_impl;
 #[cfg(test)]
 mod tests;
 
+pub use bloom::TxnBloomFilter;
 pub use rpc::gen_client;
 pub fn start_network_rpc_server<C>(
     rpc_rx: mpsc::UnboundedReceiver<RawRpcRequestMessage>,
@@ -40,6 +42,20 @@ pub struct TransactionsData {
     pub txns: Vec<SignedUserTransaction>,
 }
 
+/// Request the txns at `indexes` of the block, used for filling compact block.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBlockTxns {
+    pub block_id: HashValue,
+    pub indexes: Vec<u64>,
+}
+
+/// Txns of the block, in the same order as the `indexes` of `GetBlockTxns`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BlockTxns {
+    pub block_id: HashValue,
+    pub txns: Vec<SignedUserTransaction>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GetBlockHeaders {
     pub block_id: HashValue,
@@ -68,6 +84,26 @@ pub struct GetAccumulatorNodeByNodeHash {
     pub accumulator_storage_type: AccumulatorStoreType,
 }
 
+/// Max count of node hashes in one batch node request.
+pub const MAX_BATCH_NODE_SIZE: usize = 256;
+/// Max bytes of the nodes in one batch node response, the nodes after the cap are not returned.
+pub const MAX_BATCH_NODE_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Request state nodes by node hashes, the response is in the same order as `node_hashes`,
+/// `None` if the node is not found. The response may be shorter than the request because of
+/// `MAX_BATCH_NODE_RESPONSE_BYTES`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GetStateNodes {
+    pub node_hashes: Vec<HashValue>,
+}
+
+/// Request accumulator nodes by node hashes, the response is the same as `GetStateNodes`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GetAccumulatorNodes {
+    pub node_hashes: Vec<HashValue>,
+    pub accumulator_storage_type: AccumulatorStoreType,
+}
+
 impl GetBlockHeadersByNumber {
     pub fn new(number: BlockNumber, step: usize, max_size: usize) -> Self {
         GetBlockHeadersByNumber {
@@ -94,4 +130,18 @@ pub struct GetTxns {
     pub ids: Option<Vec<HashValue>>,
 }
 
+/// Max count of txns in one `GetTxns` request or response.
+pub const MAX_TXNS: usize = 256;
+
+/// Max count of txn hashes in one `GetTxnHashes` response.
+pub const MAX_TXN_HASHES: usize = 4096;
+
+/// Request hashes of the pending txns in peer's txpool.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetTxnHashes {
+    /// Txns the requester already has, the hashes matched by the filter are not returned.
+    pub filter: Option<TxnBloomFilter>,
+    pub max_size: usize,
+}
+
 pub(crate) const DELAY_TIME: u64 = 15;
@@ -1,6 +1,7 @@
 use crate::{
-    BlockBody, GetAccumulatorNodeByNodeHash, GetBlockHeaders, GetBlockHeadersByNumber, GetTxns,
-    TransactionsData, DELAY_TIME,
+    BlockBody, BlockTxns, GetAccumulatorNodeByNodeHash, GetAccumulatorNodes, GetBlockHeaders,
+    GetBlockHeadersByNumber, GetBlockTxns, GetStateNodes, GetTxnHashes, GetTxns, TransactionsData,
+    DELAY_TIME,
 };
 use accumulator::AccumulatorNode;
 use actix::prelude::*;
@@ -20,12 +21,20 @@ use types::{
 pub trait NetworkRpc: Sized + Send + Sync + 'static {
     fn get_txns(&self, peer_id: PeerId, req: GetTxns) -> BoxFuture<Result<TransactionsData>>;
 
+    fn get_txn_hashes(
+        &self,
+        peer_id: PeerId,
+        req: GetTxnHashes,
+    ) -> BoxFuture<Result<Vec<HashValue>>>;
+
     fn get_txn_infos(
         &self,
         peer_id: PeerId,
         block_id: HashValue,
     ) -> BoxFuture<Result<Option<Vec<TransactionInfo>>>>;
 
+    fn get_block_txns(&self, peer_id: PeerId, req: GetBlockTxns) -> BoxFuture<Result<BlockTxns>>;
+
     fn get_headers_by_number(
         &self,
         peer_id: PeerId,
@@ -61,4 +70,16 @@ pub trait NetworkRpc: Sized + Send + Sync + 'static {
         peer_id: PeerId,
         request: GetAccumulatorNodeByNodeHash,
     ) -> BoxFuture<Result<AccumulatorNode>>;
+
+    fn get_state_nodes_by_node_hash(
+        &self,
+        peer_id: PeerId,
+        request: GetStateNodes,
+    ) -> BoxFuture<Result<Vec<Option<StateNode>>>>;
+
+    fn get_accumulator_nodes_by_node_hash(
+        &self,
+        peer_id: PeerId,
+        request: GetAccumulatorNodes,
+    ) -> BoxFuture<Result<Vec<Option<AccumulatorNode>>>>;
 }
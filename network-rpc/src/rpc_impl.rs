@@ -1,11 +1,12 @@
 use accumulator::AccumulatorNode;
 use anyhow::*;
 use chain::ChainActorRef;
-use crypto::HashValue;
+use crypto::{hash::PlainCryptoHash, HashValue};
 use futures::future::BoxFuture;
 use logger::prelude::*;
 use starcoin_state_tree::StateNode;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use storage::Store;
 use traits::{ChainAsyncService, Consensus};
 use txpool::TxPoolService;
@@ -16,11 +17,17 @@ use types::{
 };
 
 use crate::{
-    rpc::gen_server::NetworkRpc, BlockBody, GetAccumulatorNodeByNodeHash, GetBlockHeaders,
-    GetBlockHeadersByNumber, GetTxns, TransactionsData,
+    rpc::gen_server::NetworkRpc, BlockBody, BlockTxns, GetAccumulatorNodeByNodeHash,
+    GetAccumulatorNodes, GetBlockHeaders, GetBlockHeadersByNumber, GetBlockTxns, GetStateNodes,
+    GetTxnHashes, GetTxns, TransactionsData, MAX_BATCH_NODE_RESPONSE_BYTES, MAX_BATCH_NODE_SIZE,
+    MAX_TXNS, MAX_TXN_HASHES,
 };
 use txpool_api::TxPoolSyncService;
 
+/// The pending txn hashes served by `get_txn_hashes` are cached for a while, so that peers can
+/// not make the node rehash the whole pool by requesting frequently.
+const TXN_HASHES_CACHE_TTL: Duration = Duration::from_secs(5);
+
 pub struct NetworkRpcImpl<C>
 where
     C: Consensus + Sync + Send + 'static + Clone,
@@ -28,6 +35,7 @@ where
     chain_reader: ChainActorRef<C>,
     txpool: TxPoolService,
     storage: Arc<dyn Store>,
+    txn_hashes_cache: Arc<Mutex<Option<(Instant, Arc<Vec<HashValue>>)>>>,
 }
 
 impl<C> NetworkRpcImpl<C>
@@ -43,6 +51,31 @@ where
             chain_reader,
             txpool,
             storage,
+            txn_hashes_cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Hashes of the pending txns, recomputed at most once every `TXN_HASHES_CACHE_TTL`.
+    fn pending_txn_hashes(&self) -> Arc<Vec<HashValue>> {
+        let mut cache = self
+            .txn_hashes_cache
+            .lock()
+            .expect("txn hashes cache lock poisoned");
+        match cache.as_ref() {
+            Some((updated_at, hashes)) if updated_at.elapsed() < TXN_HASHES_CACHE_TTL => {
+                hashes.clone()
+            }
+            _ => {
+                let hashes: Arc<Vec<HashValue>> = Arc::new(
+                    self.txpool
+                        .get_pending_txns(None, None)
+                        .iter()
+                        .map(|txn| txn.crypto_hash())
+                        .collect(),
+                );
+                *cache = Some((Instant::now(), hashes.clone()));
+                hashes
+            }
         }
     }
 }
@@ -58,12 +91,19 @@ where
             let data = {
                 match req.ids {
                     // get from txpool
-                    None => txpool.get_pending_txns(None, None),
-                    // get from storage
+                    None => txpool.get_pending_txns(Some(MAX_TXNS as u64), None),
+                    // get from txpool first, then fallback to storage
                     Some(ids) => {
+                        ensure!(
+                            ids.len() <= MAX_TXNS,
+                            "too many txns requested : {}",
+                            ids.len()
+                        );
                         let mut data = vec![];
                         for id in ids {
-                            if let Ok(txn) = storage.get_transaction(id) {
+                            if let Some(txn) = txpool.find_txn(&id) {
+                                data.push(txn);
+                            } else if let Ok(txn) = storage.get_transaction(id) {
                                 if let Some(txn) = txn {
                                     if let Ok(stxn) = txn.as_signed_user_txn() {
                                         data.push(stxn.clone());
@@ -80,6 +120,28 @@ where
         Box::pin(fut)
     }
 
+    fn get_txn_hashes(
+        &self,
+        _peer_id: PeerId,
+        req: GetTxnHashes,
+    ) -> BoxFuture<Result<Vec<HashValue>>> {
+        let pending_txn_hashes = self.pending_txn_hashes();
+        let fut = async move {
+            let max_size = req.max_size.min(MAX_TXN_HASHES);
+            let hashes = pending_txn_hashes
+                .iter()
+                .cloned()
+                .filter(|hash| match &req.filter {
+                    Some(filter) => !filter.contains(hash),
+                    None => true,
+                })
+                .take(max_size)
+                .collect();
+            Ok(hashes)
+        };
+        Box::pin(fut)
+    }
+
     fn get_txn_infos(
         &self,
         _peer_id: PeerId,
@@ -96,6 +158,26 @@ where
         Box::pin(fut)
     }
 
+    fn get_block_txns(&self, _peer_id: PeerId, req: GetBlockTxns) -> BoxFuture<Result<BlockTxns>> {
+        let chain_reader = self.chain_reader.clone();
+        let fut = async move {
+            let block = chain_reader.get_block_by_hash(req.block_id).await?;
+            let block_txns = block.transactions();
+            let mut txns = Vec::new();
+            for index in req.indexes {
+                match block_txns.get(index as usize) {
+                    Some(txn) => txns.push(txn.clone()),
+                    None => bail!("txn index {} of block {} out of range", index, req.block_id),
+                }
+            }
+            Ok(BlockTxns {
+                block_id: req.block_id,
+                txns,
+            })
+        };
+        Box::pin(fut)
+    }
+
     fn get_headers_by_number(
         &self,
         _peer_id: PeerId,
@@ -301,4 +383,64 @@ where
         };
         Box::pin(fut)
     }
+
+    fn get_state_nodes_by_node_hash(
+        &self,
+        _peer_id: PeerId,
+        request: GetStateNodes,
+    ) -> BoxFuture<Result<Vec<Option<StateNode>>>> {
+        let storage = self.storage.clone();
+        let fut = async move {
+            ensure!(
+                request.node_hashes.len() <= MAX_BATCH_NODE_SIZE,
+                "too many state nodes requested : {}",
+                request.node_hashes.len()
+            );
+            let mut state_nodes = Vec::new();
+            let mut total_bytes = 0;
+            for node_key in request.node_hashes {
+                let state_node = storage.get(&node_key)?;
+                if let Some(state_node) = &state_node {
+                    total_bytes += scs::to_bytes(state_node)?.len();
+                    if total_bytes > MAX_BATCH_NODE_RESPONSE_BYTES && !state_nodes.is_empty() {
+                        break;
+                    }
+                }
+                state_nodes.push(state_node);
+            }
+            Ok(state_nodes)
+        };
+        Box::pin(fut)
+    }
+
+    fn get_accumulator_nodes_by_node_hash(
+        &self,
+        _peer_id: PeerId,
+        request: GetAccumulatorNodes,
+    ) -> BoxFuture<Result<Vec<Option<AccumulatorNode>>>> {
+        let storage = self.storage.clone();
+        let fut = async move {
+            ensure!(
+                request.node_hashes.len() <= MAX_BATCH_NODE_SIZE,
+                "too many accumulator nodes requested : {}",
+                request.node_hashes.len()
+            );
+            let mut accumulator_nodes = Vec::new();
+            let mut total_bytes = 0;
+            for node_key in request.node_hashes {
+                let accumulator_node =
+                    storage.get_node(request.accumulator_storage_type.clone(), node_key)?;
+                if let Some(accumulator_node) = &accumulator_node {
+                    total_bytes += scs::to_bytes(accumulator_node)?.len();
+                    if total_bytes > MAX_BATCH_NODE_RESPONSE_BYTES && !accumulator_nodes.is_empty()
+                    {
+                        break;
+                    }
+                }
+                accumulator_nodes.push(accumulator_node);
+            }
+            Ok(accumulator_nodes)
+        };
+        Box::pin(fut)
+    }
 }
@@ -1,5 +1,7 @@
 use crate::gen_client;
-use crate::{start_network_rpc_server, GetBlockHeadersByNumber};
+use crate::rpc::gen_server::NetworkRpc;
+use crate::rpc_impl::NetworkRpcImpl;
+use crate::{start_network_rpc_server, GetBlockHeadersByNumber, GetBlockTxns, GetStateNodes};
 use actix::{Actor, Addr, System};
 use block_relayer::BlockRelayer;
 use bus::BusActor;
@@ -18,11 +20,12 @@ use std::sync::Arc;
 use std::time::Duration;
 use storage::cache_storage::CacheStorage;
 use storage::storage::StorageInstance;
-use storage::Storage;
+use storage::{BlockStore, Storage};
 use txpool::{TxPool, TxPoolService};
 use types::{
-    block::BlockHeader,
+    block::{Block, BlockBody, BlockHeader, BlockState},
     peer_info::{PeerId, PeerInfo},
+    transaction::SignedUserTransaction,
 };
 use wallet_api::WalletAccount;
 
@@ -35,7 +38,7 @@ fn test_network_rpc() {
         let config_1 = NodeConfig::random_for_test();
         gen_chain_env(config_1)
     };
-    let (.., network_2, _) = {
+    let (_, storage_2, _, network_2, _) = {
         let mut config_2 = NodeConfig::random_for_test();
         config_2.network.seeds = vec![net_addr_1];
         gen_chain_env(config_2)
@@ -44,14 +47,85 @@ fn test_network_rpc() {
     // network rpc client for chain 1
     let peer_id_2 = network_2.identify().clone();
     let client = gen_client::NetworkRpcClient::new(network_1);
+    let genesis_id = storage_2.get_startup_info().unwrap().unwrap().master;
+    let state_root = storage_2
+        .get_block_header_by_hash(genesis_id)
+        .unwrap()
+        .unwrap()
+        .state_root();
     let fut = async move {
         Delay::new(Duration::from_secs(15)).await;
         let req = GetBlockHeadersByNumber::new(1, 1, 1);
         let resp: Vec<BlockHeader> = client
-            .get_headers_by_number(peer_id_2.into(), req)
+            .get_headers_by_number(peer_id_2.clone().into(), req)
             .await
             .unwrap();
         assert!(!resp.is_empty());
+
+        let req = GetStateNodes {
+            node_hashes: vec![state_root, HashValue::random()],
+        };
+        let state_nodes = client
+            .get_state_nodes_by_node_hash(peer_id_2.into(), req)
+            .await
+            .unwrap();
+        assert_eq!(state_nodes.len(), 2);
+        assert_eq!(state_nodes[0].as_ref().unwrap().0.hash(), state_root);
+        assert!(state_nodes[1].is_none());
+    };
+    system.block_on(fut);
+    drop(rt);
+}
+
+#[test]
+fn test_get_block_txns() {
+    ::logger::init_for_test();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut system = System::new("test");
+    let (chain, storage, txpool, ..) = gen_chain_env(NodeConfig::random_for_test());
+    let txns: Vec<_> = (0..3).map(|_| SignedUserTransaction::mock()).collect();
+    let block = Block::new(BlockHeader::random(), BlockBody::new(txns.clone(), None));
+    storage
+        .commit_block(block.clone(), BlockState::Executed)
+        .unwrap();
+    let rpc_impl = NetworkRpcImpl::new(chain, txpool, storage);
+
+    let fut = async move {
+        let block_txns = rpc_impl
+            .get_block_txns(
+                PeerId::random(),
+                GetBlockTxns {
+                    block_id: block.id(),
+                    indexes: vec![2, 0],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(block_txns.block_id, block.id());
+        assert_eq!(block_txns.txns, vec![txns[2].clone(), txns[0].clone()]);
+
+        // Index out of range.
+        assert!(rpc_impl
+            .get_block_txns(
+                PeerId::random(),
+                GetBlockTxns {
+                    block_id: block.id(),
+                    indexes: vec![3],
+                },
+            )
+            .await
+            .is_err());
+        // Unknown block.
+        assert!(rpc_impl
+            .get_block_txns(
+                PeerId::random(),
+                GetBlockTxns {
+                    block_id: HashValue::random(),
+                    indexes: vec![0],
+                },
+            )
+            .await
+            .is_err());
     };
     system.block_on(fut);
     drop(rt);
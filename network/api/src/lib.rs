@@ -5,7 +5,9 @@ use starcoin_types::system_events::NewHeadBlock;
 use std::time::Duration;
 
 pub mod messages;
+pub mod reputation;
 
+use crate::reputation::ReputationChange;
 use async_trait::async_trait;
 use starcoin_types::peer_info::{PeerInfo, RpcInfo};
 use std::borrow::Cow;
@@ -52,6 +54,9 @@ pub trait NetworkService: Send + Sync + Clone + Sized + std::marker::Unpin {
         proto_name: Cow<'static, [u8]>,
         rpc_info: RpcInfo,
     ) -> Result<()>;
+
+    /// Adjust the reputation of a peer by its behaviour.
+    fn report_peer(&self, peer_id: PeerId, change: ReputationChange);
 }
 
 #[derive(Clone)]
@@ -131,4 +136,6 @@ impl NetworkService for DummyNetworkService {
     ) -> Result<()> {
         Ok(())
     }
+
+    fn report_peer(&self, _peer_id: PeerId, _change: ReputationChange) {}
 }
@@ -0,0 +1,37 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/// Reputation adjustment of a peer, a peer is disconnected and banned for a while
+/// once its reputation falls under the network banned threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReputationChange {
+    /// Reputation delta.
+    pub value: i32,
+    /// Reason for reputation change.
+    pub reason: &'static str,
+}
+
+impl ReputationChange {
+    pub const fn new(value: i32, reason: &'static str) -> ReputationChange {
+        ReputationChange { value, reason }
+    }
+
+    /// A change which forces the minimum possible reputation, the peer is banned immediately.
+    pub const fn new_fatal(reason: &'static str) -> ReputationChange {
+        ReputationChange {
+            value: i32::min_value(),
+            reason,
+        }
+    }
+}
+
+/// The peer send a block which fails verification.
+pub const INVALID_BLOCK: ReputationChange =
+    ReputationChange::new(i32::min_value() / 2, "Invalid block");
+/// The peer send a state node, accumulator node or other data which does not match the requested proof.
+pub const INVALID_PROOF: ReputationChange =
+    ReputationChange::new(i32::min_value() / 4, "Invalid proof");
+/// The peer relay a transaction with a bad signature.
+pub const INVALID_TXN: ReputationChange = ReputationChange::new(-(1 << 16), "Invalid transaction");
+/// The peer do not response a rpc request in time.
+pub const SLOW_RESPONSE: ReputationChange = ReputationChange::new(-(1 << 20), "Slow response");
@@ -19,7 +19,7 @@ pub use network_api::messages::*;
 pub use helper::get_unix_ts;
 
 pub use net::{build_network_service, SNetworkService};
-pub use network::NetworkAsyncService;
+pub use network::{NetworkAsyncService, PeerManageMessage};
 use network_p2p::PeerId;
 
 use anyhow::*;
@@ -9,9 +9,10 @@ use config::NetworkConfig;
 use crypto::hash::HashValue;
 use futures::{channel::mpsc, prelude::*};
 use libp2p::PeerId;
+use network_api::reputation::ReputationChange;
 use network_p2p::{
     identity, Event, Multiaddr, NetworkConfiguration, NetworkService, NetworkWorker, NodeKeyConfig,
-    Params, Secret, PROTOCOL_NAME,
+    Params, PeerScore, Secret, PROTOCOL_NAME,
 };
 use parity_codec::alloc::collections::HashSet;
 use std::borrow::Cow;
@@ -155,6 +156,59 @@ impl SNetworkService {
         self.service.exist_notif_proto(protocol_name).await
     }
 
+    pub fn report_peer(&self, peer_id: PeerId, change: ReputationChange) {
+        self.service.report_peer(
+            peer_id,
+            network_p2p::ReputationChange::new(change.value, change.reason),
+        );
+    }
+
+    pub fn ban_peer(&self, peer_id: PeerId) {
+        self.service.ban_peer(peer_id);
+    }
+
+    pub fn unban_peer(&self, peer_id: PeerId) {
+        self.service.unban_peer(peer_id);
+    }
+
+    pub async fn peer_scores(&self) -> Vec<PeerScore> {
+        self.service.peer_scores().await
+    }
+
+    pub fn add_peer(&self, peer: String) -> Result<()> {
+        self.service
+            .add_peer(peer)
+            .map_err(|e| format_err!("{}", e))
+    }
+
+    /// Disconnect the peer and remove it from reserved peers.
+    pub fn remove_peer(&self, peer_id: PeerId) {
+        self.service.remove_reserved_peer(peer_id.clone());
+        self.service.disconnect_peer(peer_id);
+    }
+
+    pub fn add_reserved_peer(&self, peer: String) -> Result<()> {
+        self.service
+            .add_reserved_peer(peer)
+            .map_err(|e| format_err!("{}", e))
+    }
+
+    pub fn remove_reserved_peer(&self, peer_id: PeerId) {
+        self.service.remove_reserved_peer(peer_id);
+    }
+
+    pub fn set_reserved_only(&self, reserved_only: bool) {
+        if reserved_only {
+            self.service.deny_unreserved_peers();
+        } else {
+            self.service.accept_unreserved_peers();
+        }
+    }
+
+    pub async fn reserved_peers(&self) -> (Vec<PeerId>, bool) {
+        self.service.reserved_peers().await
+    }
+
     pub async fn sub_stream(&self, protocol_name: Cow<'static, [u8]>) -> impl Stream<Item = Event> {
         self.service.sub_stream(protocol_name)
     }
@@ -22,7 +22,11 @@ use futures_timer::Delay;
 use libp2p::multiaddr::Protocol;
 use libp2p::PeerId;
 use lru::LruCache;
-use network_api::{messages::RawRpcRequestMessage, NetworkService};
+use network_api::{
+    messages::RawRpcRequestMessage,
+    reputation::{self, ReputationChange},
+    NetworkService,
+};
 use network_p2p::Multiaddr;
 use scs::SCSCodec;
 use starcoin_block_relayer_api::{NetCmpctBlockMessage, PeerCmpctBlockEvent};
@@ -35,9 +39,8 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 use tx_relay::*;
-use types::peer_info::{PeerInfo, RpcInfo};
+use types::peer_info::{PeerInfo, PeerScore, RpcInfo};
 use types::system_events::NewHeadBlock;
-use types::transaction::SignedUserTransaction;
 use types::{BLOCK_PROTOCOL_NAME, TXN_PROTOCOL_NAME};
 
 const LRU_CACHE_SIZE: usize = 1024;
@@ -100,6 +103,19 @@ struct BlockMessage {
     event: NewHeadBlock,
 }
 
+/// Peer management commands from node admin.
+#[rtype(result = "Result<()>")]
+#[derive(Debug, Message)]
+pub enum PeerManageMessage {
+    /// Connect to a peer by its multiaddr with `/p2p/<peer_id>` suffix.
+    AddPeer(String),
+    RemovePeer(PeerId),
+    AddReservedPeer(String),
+    RemoveReservedPeer(PeerId),
+    /// Only connect to and accept reserved peers if true.
+    SetReservedOnly(bool),
+}
+
 #[async_trait]
 impl NetworkService for NetworkAsyncService {
     async fn send_peer_message(
@@ -160,6 +176,7 @@ impl NetworkService for NetworkAsyncService {
         }
 
         let metrics = self.metrics.clone();
+        let network_service = self.network_service.clone();
         let task = async move {
             Delay::new(time_out).await;
             let timeout = processor.remove_future(request_id).await;
@@ -170,6 +187,7 @@ impl NetworkService for NetworkAsyncService {
                 "send request to {} with id {} timeout",
                 peer_id_clone, request_id
             );
+            network_service.report_peer(peer_id_clone, reputation::SLOW_RESPONSE);
             if let Some(metrics) = metrics {
                 metrics.request_timeout_count.inc();
             }
@@ -252,9 +270,52 @@ impl NetworkService for NetworkAsyncService {
         }
         Ok(())
     }
+
+    fn report_peer(&self, peer_id: PeerId, change: ReputationChange) {
+        debug!(
+            "report peer {} with {}: {}",
+            peer_id, change.value, change.reason
+        );
+        self.network_service.report_peer(peer_id, change);
+    }
 }
 
 impl NetworkAsyncService {
+    /// Ban the peer until `unban_peer`, the peer is disconnected immediately.
+    pub fn ban_peer(&self, peer_id: PeerId) {
+        info!("ban peer {}", peer_id);
+        self.network_service.ban_peer(peer_id);
+    }
+
+    pub fn unban_peer(&self, peer_id: PeerId) {
+        info!("unban peer {}", peer_id);
+        self.network_service.unban_peer(peer_id);
+    }
+
+    pub async fn manage_peer(&self, msg: PeerManageMessage) -> Result<()> {
+        self.addr.send(msg).await?
+    }
+
+    /// Returns the reserved peers and whether the node is in reserved only mode.
+    pub async fn reserved_peers(&self) -> (Vec<types::peer_info::PeerId>, bool) {
+        let (peers, reserved_only) = self.network_service.reserved_peers().await;
+        (peers.into_iter().map(Into::into).collect(), reserved_only)
+    }
+
+    pub async fn peer_scores(&self) -> Vec<PeerScore> {
+        self.network_service
+            .peer_scores()
+            .await
+            .into_iter()
+            .map(|score| PeerScore {
+                peer_id: score.peer_id.into(),
+                reputation: score.reputation,
+                connected: score.connected,
+                banned: score.banned,
+            })
+            .collect()
+    }
+
     #[cfg(test)]
     pub fn network_actor_addr(&self) -> Addr<NetworkActor> {
         self.addr.clone()
@@ -406,8 +467,10 @@ impl Inner {
         // decode msg based on protocol name.
         // when protocol upgrade, we can decoded data based on the new protocol.
         if network_msg.protocol_name.as_ref() == TXN_PROTOCOL_NAME {
-            let txns: Vec<SignedUserTransaction> = scs::from_bytes(network_msg.data.as_slice())?;
-            inner.handle_txn_message(network_msg.peer_id, txns).await?;
+            let txn_ids: Vec<HashValue> = scs::from_bytes(network_msg.data.as_slice())?;
+            inner
+                .handle_txn_announcement(network_msg.peer_id, txn_ids)
+                .await?;
         } else {
             // Other peer message can be refactored in the similar way.
             let message = PeerMessage::decode(&network_msg.data);
@@ -425,25 +488,31 @@ impl Inner {
         Ok(())
     }
 
-    async fn handle_txn_message(
+    async fn handle_txn_announcement(
         &self,
         peer_id: PeerId,
-        txns: Vec<SignedUserTransaction>,
+        txn_ids: Vec<HashValue>,
     ) -> Result<()> {
-        debug!("receive new txn list from {:?} ", peer_id);
+        debug!(
+            "receive txn announcement from {:?}, len: {}",
+            peer_id,
+            txn_ids.len()
+        );
+        let mut unknown_ids = Vec::new();
         if let Some(peer_info) = self.peers.lock().await.get_mut(&peer_id) {
-            for txn in &txns {
-                let id = txn.crypto_hash();
-                if !peer_info.known_transactions.contains(&id) {
-                    peer_info.known_transactions.put(id, ());
-                } else {
-                    return Ok(());
+            for id in txn_ids {
+                // the peer already has the txn, so never announce it back.
+                if peer_info.known_transactions.put(id, ()).is_none() {
+                    unknown_ids.push(id);
                 }
             }
         }
+        if unknown_ids.is_empty() {
+            return Ok(());
+        }
         self.bus
             .clone()
-            .broadcast(PeerTransactions::new(txns))
+            .broadcast(PeerTxnAnnouncement::new(peer_id.into(), unknown_ids))
             .await?;
         Ok(())
     }
@@ -721,6 +790,28 @@ impl Handler<NetCmpctBlockMessage> for NetworkActor {
     }
 }
 
+impl Handler<PeerManageMessage> for NetworkActor {
+    type Result = Result<()>;
+
+    fn handle(&mut self, msg: PeerManageMessage, _ctx: &mut Self::Context) -> Self::Result {
+        info!("handle peer manage message: {:?}", msg);
+        match msg {
+            PeerManageMessage::AddPeer(peer) => self.network_service.add_peer(peer)?,
+            PeerManageMessage::RemovePeer(peer_id) => self.network_service.remove_peer(peer_id),
+            PeerManageMessage::AddReservedPeer(peer) => {
+                self.network_service.add_reserved_peer(peer)?
+            }
+            PeerManageMessage::RemoveReservedPeer(peer_id) => {
+                self.network_service.remove_reserved_peer(peer_id)
+            }
+            PeerManageMessage::SetReservedOnly(reserved_only) => {
+                self.network_service.set_reserved_only(reserved_only)
+            }
+        }
+        Ok(())
+    }
+}
+
 /// handler system events.
 impl Handler<BlockMessage> for NetworkActor {
     type Result = ();
@@ -794,22 +885,26 @@ impl Handler<PropagateNewTransactions> for NetworkActor {
 
         let peers = self.peers.clone();
         let network_service = self.network_service.clone();
-        let mut txn_map: HashMap<HashValue, SignedUserTransaction> = HashMap::new();
-        for txn in txns {
-            txn_map.insert(txn.crypto_hash(), txn);
-        }
+        let txn_ids: Vec<HashValue> = txns.iter().map(|txn| txn.crypto_hash()).collect();
         let self_peer_id = self.peer_id.clone();
         Arbiter::spawn(async move {
             for (peer_id, peer_info) in peers.lock().await.iter_mut() {
-                let mut txns_unhandled = Vec::new();
-                for (id, txn) in &txn_map {
-                    if !peer_info.known_transactions.contains(id) && !peer_id.eq(&self_peer_id) {
+                if peer_id.eq(&self_peer_id) {
+                    continue;
+                }
+                // only announce txn hashes, peer fetch the unknown txns by rpc.
+                let mut txn_ids_unknown = Vec::new();
+                for id in &txn_ids {
+                    if !peer_info.known_transactions.contains(id) {
                         peer_info.known_transactions.put(*id, ());
-                        txns_unhandled.push(txn.clone());
+                        txn_ids_unknown.push(*id);
                     }
                 }
+                if txn_ids_unknown.is_empty() {
+                    continue;
+                }
 
-                let bytes = scs::to_bytes(&txns_unhandled).expect("encode should succ");
+                let bytes = scs::to_bytes(&txn_ids_unknown).expect("encode should succ");
                 network_service
                     .send_message(peer_id.clone(), Cow::Borrowed(protocol_name), bytes)
                     .await
@@ -947,9 +1042,9 @@ mod tests {
             let response_actor2 = TestResponseActor::launch(network_clone2, tx2, rpc_rx_2, None);
             //let addr = response_actor.start();
 
-            // subscribe peer txns for network2
+            // subscribe peer txn announcements for network2
             bus.send(Subscription {
-                recipient: response_actor2.clone().recipient::<PeerTransactions>(),
+                recipient: response_actor2.clone().recipient::<PeerTxnAnnouncement>(),
             })
             .await
             .unwrap();
@@ -971,7 +1066,7 @@ mod tests {
                 .unwrap();
 
             let _ = rx2.next().await;
-            let txns = response_actor2.send(GetPeerTransactions).await.unwrap();
+            let txns = response_actor2.send(GetPeerTxnAnnouncements).await.unwrap();
             assert_eq!(1, txns.len());
 
             let request = TestRequest {
@@ -1015,7 +1110,7 @@ mod tests {
 
     struct TestResponseActor {
         _network_service: NetworkAsyncService,
-        peer_txns: Vec<PeerTransactions>,
+        peer_txn_announcements: Vec<PeerTxnAnnouncement>,
         event_tx: mpsc::UnboundedSender<()>,
         peer_event_tx: Option<mpsc::UnboundedSender<PeerEvent>>,
     }
@@ -1031,7 +1126,7 @@ mod tests {
                 ctx.add_stream(rpc_rx);
                 TestResponseActor {
                     _network_service: network_service,
-                    peer_txns: vec![],
+                    peer_txn_announcements: vec![],
                     event_tx,
                     peer_event_tx,
                 }
@@ -1047,26 +1142,30 @@ mod tests {
         }
     }
 
-    impl Handler<PeerTransactions> for TestResponseActor {
+    impl Handler<PeerTxnAnnouncement> for TestResponseActor {
         type Result = ();
 
-        fn handle(&mut self, msg: PeerTransactions, _ctx: &mut Self::Context) -> Self::Result {
-            self.peer_txns.push(msg);
+        fn handle(&mut self, msg: PeerTxnAnnouncement, _ctx: &mut Self::Context) -> Self::Result {
+            self.peer_txn_announcements.push(msg);
             self.event_tx.unbounded_send(()).unwrap();
         }
     }
 
-    struct GetPeerTransactions;
+    struct GetPeerTxnAnnouncements;
 
-    impl Message for GetPeerTransactions {
-        type Result = Vec<PeerTransactions>;
+    impl Message for GetPeerTxnAnnouncements {
+        type Result = Vec<PeerTxnAnnouncement>;
     }
 
-    impl Handler<GetPeerTransactions> for TestResponseActor {
-        type Result = MessageResult<GetPeerTransactions>;
+    impl Handler<GetPeerTxnAnnouncements> for TestResponseActor {
+        type Result = MessageResult<GetPeerTxnAnnouncements>;
 
-        fn handle(&mut self, _msg: GetPeerTransactions, _ctx: &mut Self::Context) -> Self::Result {
-            MessageResult(self.peer_txns.clone())
+        fn handle(
+            &mut self,
+            _msg: GetPeerTxnAnnouncements,
+            _ctx: &mut Self::Context,
+        ) -> Self::Result {
+            MessageResult(self.peer_txn_announcements.clone())
         }
     }
 
@@ -4,8 +4,8 @@
 use actix::prelude::*;
 use anyhow::{format_err, Result};
 use futures::executor::block_on;
-use starcoin_config::{ChainNetwork, NodeConfig, StarcoinOpt};
-use starcoin_consensus::{argon::ArgonConsensus, dev::DevConsensus};
+use starcoin_config::{ChainNetwork, ConsensusType, NodeConfig, StarcoinOpt};
+use starcoin_consensus::{argon::ArgonConsensus, dev::DevConsensus, keccak::KeccakConsensus};
 use starcoin_logger::prelude::*;
 use starcoin_traits::Consensus;
 use std::sync::Arc;
@@ -112,7 +112,11 @@ pub fn run_dev_node(config: Arc<NodeConfig>) -> NodeHandle {
 }
 
 pub fn run_normal_node(config: Arc<NodeConfig>) -> NodeHandle {
-    run_node::<ArgonConsensus>(config)
+    match config.net().get_config().consensus {
+        ConsensusType::Dev => run_node::<DevConsensus>(config),
+        ConsensusType::Argon => run_node::<ArgonConsensus>(config),
+        ConsensusType::Keccak => run_node::<KeccakConsensus>(config),
+    }
 }
 
 /// Run node in a new Thread, and return a NodeHandle.
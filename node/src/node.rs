@@ -14,7 +14,7 @@ use starcoin_logger::LoggerHandle;
 use starcoin_miner::MinerActor;
 use starcoin_miner::MinerClientActor;
 use starcoin_network::{NetworkActor, NetworkAsyncService, RawRpcRequestMessage};
-use starcoin_rpc_server::module::{DevPlaygroudService, PubSubService};
+use starcoin_rpc_server::module::{DevPlaygroudService, MinerRpcImpl, PubSubService};
 use starcoin_rpc_server::RpcActor;
 use starcoin_state_service::ChainStateActor;
 use starcoin_storage::block_info::BlockInfoStore;
@@ -22,6 +22,7 @@ use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::{storage::StorageInstance, BlockStore, Storage};
 use starcoin_sync::SyncActor;
+use starcoin_sync_api::SyncProgress;
 use starcoin_traits::Consensus;
 use starcoin_txpool::{TxPool, TxPoolService};
 use starcoin_txpool_api::TxPoolSyncService;
@@ -263,6 +264,8 @@ where
     let sync_txpool = txpool_service.clone();
     let sync_network = network.clone();
     let sync_storage = storage.clone();
+    let sync_progress = SyncProgress::default();
+    let sync_progress_clone = sync_progress.clone();
     let sync = Arbiter::new()
         .exec(move || -> Result<Addr<SyncActor<C>>> {
             SyncActor::launch(
@@ -273,6 +276,7 @@ where
                 sync_txpool,
                 sync_network,
                 sync_storage,
+                sync_progress_clone,
             )
         })
         .await??;
@@ -311,10 +315,18 @@ where
         let txn_receiver = txpool_service.subscribe_txns();
         let service = PubSubService::new();
         service.start_transaction_subscription_handler(txn_receiver);
-        service.start_chain_notify_handler(bus, storage.clone());
+        service.start_chain_notify_handler(bus.clone(), storage.clone());
         service
     };
 
+    let miner_api = MinerRpcImpl::<C, _, _>::new(
+        config.clone(),
+        storage.clone(),
+        bus,
+        chain.clone(),
+        txpool_service.clone(),
+    );
+
     let (json_rpc, _io_handler) = RpcActor::launch(
         config,
         txpool_service,
@@ -324,6 +336,8 @@ where
         Some(DevPlaygroudService::new(storage.clone())),
         Some(pubsub_service),
         Some(network),
+        Some(sync_progress),
+        Some(miner_api),
         Some(logger_handle),
     )?;
 
@@ -9,9 +9,11 @@ pub mod chain;
 pub mod debug;
 pub mod dev;
 pub mod errors;
+pub mod miner;
 pub mod node;
 pub mod pubsub;
 pub mod state;
+pub mod sync;
 pub mod txpool;
 pub mod types;
 pub mod wallet;
@@ -0,0 +1,41 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+pub use self::gen_client::Client as MinerClient;
+use crate::FutureResult;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::RawBlockHeader;
+use starcoin_types::U256;
+
+#[rpc]
+pub trait MinerApi {
+    /// Get a block template for `author` to mine on the current head, the `auth_key_prefix` of
+    /// `author` is required if the author account does not exist on chain.
+    #[rpc(name = "miner.get_block_template")]
+    fn get_block_template(
+        &self,
+        author: AccountAddress,
+        auth_key_prefix: Option<Vec<u8>>,
+    ) -> FutureResult<MintBlockTemplate>;
+
+    /// Submit the `nonce` solved for the template, returns the id of the mined block.
+    #[rpc(name = "miner.submit_block")]
+    fn submit_block(&self, template_id: HashValue, nonce: u64) -> FutureResult<HashValue>;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MintBlockTemplate {
+    /// Id of the template, used to submit the block.
+    pub template_id: HashValue,
+    /// The header to mine, without nonce.
+    pub raw_header: RawBlockHeader,
+    /// Count of the transactions in the block body.
+    pub txn_count: u64,
+    /// Target difficulty of the block.
+    pub difficulty: U256,
+    /// Hex of the header hash blob which the pow hash is calculated on.
+    pub header_hash: String,
+}
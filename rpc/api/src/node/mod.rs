@@ -8,7 +8,7 @@ pub use self::gen_client::Client as NodeClient;
 use crate::FutureResult;
 use serde::{Deserialize, Serialize};
 use starcoin_config::ChainNetwork;
-use starcoin_types::peer_info::PeerInfo;
+use starcoin_types::peer_info::{PeerId, PeerInfo, PeerScore};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -29,6 +29,13 @@ impl NodeInfo {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReservedPeers {
+    pub peers: Vec<PeerId>,
+    /// Only reserved peers are connected and accepted if true.
+    pub reserved_only: bool,
+}
+
 #[rpc]
 pub trait NodeApi {
     /// Get node run status, just for api available check.
@@ -45,4 +52,39 @@ pub trait NodeApi {
 
     #[rpc(name = "node.metrics")]
     fn metrics(&self) -> Result<HashMap<String, String>>;
+
+    /// Ban a peer, the peer is disconnected and refused until unban.
+    #[rpc(name = "node.ban_peer")]
+    fn ban_peer(&self, peer_id: PeerId) -> Result<()>;
+
+    /// Unban a banned peer.
+    #[rpc(name = "node.unban_peer")]
+    fn unban_peer(&self, peer_id: PeerId) -> Result<()>;
+
+    /// Get the reputation of all known peers.
+    #[rpc(name = "node.peer_scores")]
+    fn peer_scores(&self) -> FutureResult<Vec<PeerScore>>;
+
+    /// Connect to a peer by multiaddr, such as `/ip4/127.0.0.1/tcp/9840/p2p/<peer_id>`.
+    #[rpc(name = "node.add_peer")]
+    fn add_peer(&self, peer: String) -> FutureResult<()>;
+
+    /// Disconnect a peer and remove it from reserved peers.
+    #[rpc(name = "node.remove_peer")]
+    fn remove_peer(&self, peer_id: PeerId) -> FutureResult<()>;
+
+    /// Get reserved peers, the node always try to keep connection with reserved peers.
+    #[rpc(name = "node.reserved_peers")]
+    fn reserved_peers(&self) -> FutureResult<ReservedPeers>;
+
+    /// Add a reserved peer by multiaddr.
+    #[rpc(name = "node.add_reserved_peer")]
+    fn add_reserved_peer(&self, peer: String) -> FutureResult<()>;
+
+    #[rpc(name = "node.remove_reserved_peer")]
+    fn remove_reserved_peer(&self, peer_id: PeerId) -> FutureResult<()>;
+
+    /// Switch the reserved only mode, in reserved only mode, all unreserved peers are disconnected.
+    #[rpc(name = "node.set_reserved_only")]
+    fn set_reserved_only(&self, reserved_only: bool) -> FutureResult<()>;
 }
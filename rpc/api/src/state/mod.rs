@@ -8,19 +8,47 @@ use starcoin_state_api::StateWithProof;
 use starcoin_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
 };
+use starcoin_vm_types::abi::ModuleABI;
+use starcoin_vm_types::language_storage::ModuleId;
 
 pub use self::gen_client::Client as StateClient;
 
+/// The state query methods read the head state by default, or the state of the optional
+/// `block_id` or `state_root`, at most one of them can be specified.
 #[rpc]
 pub trait StateApi {
     #[rpc(name = "state.get")]
-    fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>>;
+    fn get(
+        &self,
+        access_path: AccessPath,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<Vec<u8>>>;
 
     #[rpc(name = "state.get_with_proof")]
-    fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProof>;
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<StateWithProof>;
 
     #[rpc(name = "state.get_account_state")]
-    fn get_account_state(&self, address: AccountAddress) -> FutureResult<Option<AccountState>>;
+    fn get_account_state(
+        &self,
+        address: AccountAddress,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<AccountState>>;
+
+    /// Get the ABI of the module, generated from the module's bytecode.
+    #[rpc(name = "state.get_module_abi")]
+    fn get_module_abi(
+        &self,
+        module_id: ModuleId,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<ModuleABI>>;
 
     #[rpc(name = "state.get_state_root")]
     fn get_state_root(&self) -> FutureResult<HashValue>;
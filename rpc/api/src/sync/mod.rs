@@ -0,0 +1,15 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+pub use self::gen_client::Client as SyncClient;
+pub use starcoin_sync_api::SyncStatus;
+
+#[rpc]
+pub trait SyncApi {
+    /// Get block sync progress of the node.
+    #[rpc(name = "sync.status")]
+    fn status(&self) -> Result<SyncStatus>;
+}
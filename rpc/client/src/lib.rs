@@ -12,20 +12,22 @@ use jsonrpc_core::{MetaIoHandler, Metadata};
 use jsonrpc_core_client::{transports::ipc, transports::local, transports::ws, RpcChannel};
 use starcoin_crypto::HashValue;
 use starcoin_logger::{prelude::*, LogPattern};
-use starcoin_rpc_api::node::NodeInfo;
+use starcoin_rpc_api::miner::MintBlockTemplate;
+use starcoin_rpc_api::node::{NodeInfo, ReservedPeers};
+use starcoin_rpc_api::sync::SyncStatus;
 use starcoin_rpc_api::types::event::Event;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::pubsub::ThinBlock;
 use starcoin_rpc_api::{
-    chain::ChainClient, debug::DebugClient, dev::DevClient, node::NodeClient, state::StateClient,
-    txpool::TxPoolClient, wallet::WalletClient,
+    chain::ChainClient, debug::DebugClient, dev::DevClient, miner::MinerClient, node::NodeClient,
+    state::StateClient, sync::SyncClient, txpool::TxPoolClient, wallet::WalletClient,
 };
 use starcoin_state_api::StateWithProof;
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
 use starcoin_types::block::{Block, BlockNumber};
-use starcoin_types::peer_info::PeerInfo;
+use starcoin_types::peer_info::{PeerId, PeerInfo, PeerScore};
 use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::{
     RawUserTransaction, SignedUserTransaction, Transaction, TransactionInfo, TransactionOutput,
@@ -47,6 +49,8 @@ mod pubsub_client;
 mod remote_state_reader;
 pub use crate::remote_state_reader::RemoteStateReader;
 use starcoin_types::contract_event::ContractEvent;
+use starcoin_vm_types::abi::ModuleABI;
+use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::on_chain_config::EpochInfo;
 
 #[derive(Debug, Clone)]
@@ -203,6 +207,112 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn node_ban_peer(&self, peer_id: PeerId) -> anyhow::Result<()> {
+        self.call_rpc_blocking(
+            |inner| async move { inner.node_client.ban_peer(peer_id).compat().await },
+        )
+        .map_err(map_err)
+    }
+
+    pub fn node_unban_peer(&self, peer_id: PeerId) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| async move {
+            inner.node_client.unban_peer(peer_id).compat().await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn node_add_peer(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(
+            |inner| async move { inner.node_client.add_peer(peer).compat().await },
+        )
+        .map_err(map_err)
+    }
+
+    pub fn node_remove_peer(&self, peer_id: PeerId) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| async move {
+            inner.node_client.remove_peer(peer_id).compat().await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn node_reserved_peers(&self) -> anyhow::Result<ReservedPeers> {
+        self.call_rpc_blocking(
+            |inner| async move { inner.node_client.reserved_peers().compat().await },
+        )
+        .map_err(map_err)
+    }
+
+    pub fn node_add_reserved_peer(&self, peer: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| async move {
+            inner.node_client.add_reserved_peer(peer).compat().await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn node_remove_reserved_peer(&self, peer_id: PeerId) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| async move {
+            inner
+                .node_client
+                .remove_reserved_peer(peer_id)
+                .compat()
+                .await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn node_set_reserved_only(&self, reserved_only: bool) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| async move {
+            inner
+                .node_client
+                .set_reserved_only(reserved_only)
+                .compat()
+                .await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn node_peer_scores(&self) -> anyhow::Result<Vec<PeerScore>> {
+        self.call_rpc_blocking(
+            |inner| async move { inner.node_client.peer_scores().compat().await },
+        )
+        .map_err(map_err)
+    }
+
+    pub fn sync_status(&self) -> anyhow::Result<SyncStatus> {
+        self.call_rpc_blocking(|inner| async move { inner.sync_client.status().compat().await })
+            .map_err(map_err)
+    }
+
+    pub fn miner_get_block_template(
+        &self,
+        author: AccountAddress,
+        auth_key_prefix: Option<Vec<u8>>,
+    ) -> anyhow::Result<MintBlockTemplate> {
+        self.call_rpc_blocking(|inner| async move {
+            inner
+                .miner_client
+                .get_block_template(author, auth_key_prefix)
+                .compat()
+                .await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn miner_submit_block(
+        &self,
+        template_id: HashValue,
+        nonce: u64,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| async move {
+            inner
+                .miner_client
+                .submit_block(template_id, nonce)
+                .compat()
+                .await
+        })
+        .map_err(map_err)
+    }
+
     pub fn next_sequence_number_in_txpool(
         &self,
         address: AccountAddress,
@@ -322,17 +432,51 @@ impl RpcClient {
     }
 
     pub fn state_get(&self, access_path: AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
-        self.call_rpc_blocking(
-            |inner| async move { inner.state_client.get(access_path).compat().await },
-        )
+        self.state_get_at(access_path, None, None)
+    }
+
+    /// Get the state at the `block_id` or `state_root`, default is the head state.
+    pub fn state_get_at(
+        &self,
+        access_path: AccessPath,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        self.call_rpc_blocking(|inner| async move {
+            inner
+                .state_client
+                .get(access_path, block_id, state_root)
+                .compat()
+                .await
+        })
         .map_err(map_err)
     }
 
     pub fn state_get_with_proof(&self, access_path: AccessPath) -> anyhow::Result<StateWithProof> {
+        self.state_get_with_proof_at(access_path, None, None)
+    }
+
+    pub fn state_get_with_proof_at(
+        &self,
+        access_path: AccessPath,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<StateWithProof> {
+        self.call_rpc_blocking(|inner| async move {
+            inner
+                .state_client
+                .get_with_proof(access_path, block_id, state_root)
+                .compat()
+                .await
+        })
+        .map_err(map_err)
+    }
+
+    pub fn state_get_module_abi(&self, module_id: ModuleId) -> anyhow::Result<Option<ModuleABI>> {
         self.call_rpc_blocking(|inner| async move {
             inner
                 .state_client
-                .get_with_proof(access_path)
+                .get_module_abi(module_id, None, None)
                 .compat()
                 .await
         })
@@ -349,9 +493,22 @@ impl RpcClient {
     pub fn state_get_account_state(
         &self,
         address: AccountAddress,
+    ) -> anyhow::Result<Option<AccountState>> {
+        self.state_get_account_state_at(address, None, None)
+    }
+
+    pub fn state_get_account_state_at(
+        &self,
+        address: AccountAddress,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
     ) -> anyhow::Result<Option<AccountState>> {
         self.call_rpc_blocking(|inner| async move {
-            inner.state_client.get_account_state(address).compat().await
+            inner
+                .state_client
+                .get_account_state(address, block_id, state_root)
+                .compat()
+                .await
         })
         .map_err(map_err)
     }
@@ -610,6 +767,8 @@ pub(crate) struct RpcClientInner {
     chain_client: ChainClient,
     pubsub_client: PubSubClient,
     dev_client: DevClient,
+    sync_client: SyncClient,
+    miner_client: MinerClient,
 }
 
 impl RpcClientInner {
@@ -622,6 +781,8 @@ impl RpcClientInner {
             debug_client: channel.clone().into(),
             chain_client: channel.clone().into(),
             dev_client: channel.clone().into(),
+            sync_client: channel.clone().into(),
+            miner_client: channel.clone().into(),
             pubsub_client: channel.into(),
         }
     }
@@ -13,24 +13,41 @@ use starcoin_types::state_set::ChainStateSet;
 pub struct RemoteStateReader<'a> {
     //TODO add cache.
     client: &'a RpcClient,
+    state_root: Option<HashValue>,
 }
 
 impl<'a> RemoteStateReader<'a> {
     pub fn new(client: &'a RpcClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            state_root: None,
+        }
+    }
+
+    /// Read the state at `state_root` rather than the head state.
+    pub fn new_with_root(client: &'a RpcClient, state_root: HashValue) -> Self {
+        Self {
+            client,
+            state_root: Some(state_root),
+        }
     }
 }
 
 impl<'a> ChainStateReader for RemoteStateReader<'a> {
     fn get_with_proof(&self, access_path: &AccessPath) -> Result<StateWithProof> {
-        self.client.state_get_with_proof(access_path.clone())
+        self.client
+            .state_get_with_proof_at(access_path.clone(), None, self.state_root)
     }
 
     fn get_account_state(&self, address: &AccountAddress) -> Result<Option<AccountState>> {
-        self.client.state_get_account_state(*address)
+        self.client
+            .state_get_account_state_at(*address, None, self.state_root)
     }
 
     fn state_root(&self) -> HashValue {
+        if let Some(state_root) = self.state_root {
+            return state_root;
+        }
         //TODO change trait api to return Result<HashValue>
         self.client
             .state_get_state_root()
@@ -44,7 +61,8 @@ impl<'a> ChainStateReader for RemoteStateReader<'a> {
 
 impl<'a> StateView for RemoteStateReader<'a> {
     fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
-        self.client.state_get(access_path.clone())
+        self.client
+            .state_get_at(access_path.clone(), None, self.state_root)
     }
 
     fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
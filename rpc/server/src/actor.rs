@@ -4,7 +4,7 @@
 use crate::metadata::Metadata;
 use crate::module::{
     ChainRpcImpl, DebugRpcImpl, DevPlaygroudService, DevRpcImpl, NodeRpcImpl, PubSubImpl,
-    PubSubService, StateRpcImpl, TxPoolRpcImpl, WalletRpcImpl,
+    PubSubService, StateRpcImpl, SyncRpcImpl, TxPoolRpcImpl, WalletRpcImpl,
 };
 use crate::service::RpcService;
 use actix::prelude::*;
@@ -16,12 +16,15 @@ use starcoin_logger::LoggerHandle;
 use starcoin_network::NetworkAsyncService;
 use starcoin_rpc_api::chain::ChainApi;
 use starcoin_rpc_api::debug::DebugApi;
+use starcoin_rpc_api::miner::MinerApi;
+use starcoin_rpc_api::sync::SyncApi;
 use starcoin_rpc_api::wallet::WalletApi;
 use starcoin_rpc_api::{
     dev::DevApi, node::NodeApi, pubsub::StarcoinPubSub, state::StateApi, txpool::TxPoolApi,
 };
 use starcoin_rpc_middleware::MetricMiddleware;
 use starcoin_state_api::ChainStateAsyncService;
+use starcoin_sync_api::SyncProgress;
 use starcoin_traits::ChainAsyncService;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_wallet_api::WalletAsyncService;
@@ -34,7 +37,7 @@ pub struct RpcActor {
 }
 
 impl RpcActor {
-    pub fn launch<CS, TS, AS, SS>(
+    pub fn launch<CS, TS, AS, SS, M>(
         config: Arc<NodeConfig>,
         txpool_service: TS,
         chain_service: CS,
@@ -44,6 +47,8 @@ impl RpcActor {
         pubsub_service: Option<PubSubService>,
         //TODO after network async service provide trait, remove Option.
         network_service: Option<NetworkAsyncService>,
+        sync_progress: Option<SyncProgress>,
+        miner_api: Option<M>,
         logger_handle: Option<Arc<LoggerHandle>>,
     ) -> Result<(Addr<RpcActor>, MetaIoHandler<Metadata, MetricMiddleware>)>
     where
@@ -51,6 +56,7 @@ impl RpcActor {
         TS: TxPoolSyncService + 'static,
         AS: WalletAsyncService + 'static,
         SS: ChainStateAsyncService + 'static,
+        M: MinerApi,
     {
         let config_clone = config.clone();
         let mut io_handler = Self::extend_apis(
@@ -58,8 +64,13 @@ impl RpcActor {
             Some(ChainRpcImpl::new(chain_service)),
             Some(TxPoolRpcImpl::new(txpool_service)),
             Some(WalletRpcImpl::new(account_service)),
-            Some(StateRpcImpl::new(state_service.clone())),
+            Some(StateRpcImpl::new(
+                state_service.clone(),
+                chain_service.clone(),
+            )),
             pubsub_service.map(PubSubImpl::new),
+            sync_progress.map(SyncRpcImpl::new),
+            miner_api,
             logger_handle.map(|logger_handle| DebugRpcImpl::new(config_clone, logger_handle)),
         )?;
 
@@ -73,13 +84,15 @@ impl RpcActor {
         Self::launch_with_handler(config, io_handler)
     }
 
-    pub fn extend_apis<C, N, T, A, S, D, P>(
+    pub fn extend_apis<C, N, T, A, S, D, P, SY, M>(
         node_api: N,
         chain_api: Option<C>,
         txpool_api: Option<T>,
         account_api: Option<A>,
         state_api: Option<S>,
         pubsub_api: Option<P>,
+        sync_api: Option<SY>,
+        miner_api: Option<M>,
         debug_api: Option<D>,
     ) -> Result<MetaIoHandler<Metadata, MetricMiddleware>>
     where
@@ -90,6 +103,8 @@ impl RpcActor {
         S: StateApi,
         P: StarcoinPubSub<Metadata = Metadata>,
         D: DebugApi,
+        SY: SyncApi,
+        M: MinerApi,
     {
         let mut io_handler =
             MetaIoHandler::<Metadata, MetricMiddleware>::with_middleware(MetricMiddleware);
@@ -109,6 +124,12 @@ impl RpcActor {
         if let Some(pubsub_api) = pubsub_api {
             io_handler.extend_with(StarcoinPubSub::to_delegate(pubsub_api));
         }
+        if let Some(sync_api) = sync_api {
+            io_handler.extend_with(SyncApi::to_delegate(sync_api));
+        }
+        if let Some(miner_api) = miner_api {
+            io_handler.extend_with(MinerApi::to_delegate(miner_api));
+        }
         if let Some(debug_api) = debug_api {
             io_handler.extend_with(DebugApi::to_delegate(debug_api));
         }
@@ -178,7 +199,9 @@ impl Supervised for RpcActor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::module::MinerRpcImpl;
     use starcoin_chain::mock::mock_chain_service::MockChainService;
+    use starcoin_consensus::dev::DevConsensus;
     use starcoin_state_api::mock::MockChainStateService;
     use starcoin_state_tree::mock::MockStateNodeStore;
     use starcoin_txpool_mock_service::MockTxPoolService;
@@ -201,6 +224,8 @@ mod tests {
             Some(playground_service),
             None,
             None,
+            None,
+            None::<MinerRpcImpl<DevConsensus, MockChainService, MockTxPoolService>>,
             Some(logger_handle),
         )
         .unwrap();
@@ -0,0 +1,256 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::module::{map_err, to_invalid_param_err};
+use actix::Addr;
+use anyhow::format_err;
+use futures::future::{FutureExt, TryFutureExt};
+use parking_lot::Mutex;
+use starcoin_bus::{Broadcast, BusActor};
+use starcoin_chain::BlockChain;
+use starcoin_config::NodeConfig;
+use starcoin_crypto::hash::PlainCryptoHash;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_miner::txn_selector::select_txns;
+use starcoin_rpc_api::miner::{MinerApi, MintBlockTemplate};
+use starcoin_rpc_api::FutureResult;
+use starcoin_storage::Store;
+use starcoin_traits::{ChainAsyncService, Consensus};
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockTemplate;
+use starcoin_types::system_events::MinedBlock;
+use starcoin_types::U256;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Max count of templates kept for submit.
+const MAX_TEMPLATES: usize = 32;
+
+struct TemplateEntry {
+    template_id: HashValue,
+    block_template: BlockTemplate,
+    difficulty: U256,
+}
+
+pub struct MinerRpcImpl<C, CS, TS>
+where
+    C: Consensus + 'static,
+    CS: ChainAsyncService + 'static,
+    TS: TxPoolSyncService + 'static,
+{
+    config: Arc<NodeConfig>,
+    storage: Arc<dyn Store>,
+    bus: Addr<BusActor>,
+    chain_service: CS,
+    txpool_service: TS,
+    templates: Arc<Mutex<Vec<TemplateEntry>>>,
+    phantom: PhantomData<C>,
+}
+
+impl<C, CS, TS> MinerRpcImpl<C, CS, TS>
+where
+    C: Consensus + 'static,
+    CS: ChainAsyncService + 'static,
+    TS: TxPoolSyncService + 'static,
+{
+    pub fn new(
+        config: Arc<NodeConfig>,
+        storage: Arc<dyn Store>,
+        bus: Addr<BusActor>,
+        chain_service: CS,
+        txpool_service: TS,
+    ) -> Self {
+        Self {
+            config,
+            storage,
+            bus,
+            chain_service,
+            txpool_service,
+            templates: Arc::new(Mutex::new(Vec::new())),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<C, CS, TS> MinerApi for MinerRpcImpl<C, CS, TS>
+where
+    C: Consensus + 'static,
+    CS: ChainAsyncService + 'static,
+    TS: TxPoolSyncService + 'static,
+{
+    fn get_block_template(
+        &self,
+        author: AccountAddress,
+        auth_key_prefix: Option<Vec<u8>>,
+    ) -> FutureResult<MintBlockTemplate> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let chain_service = self.chain_service.clone();
+        let txpool_service = self.txpool_service.clone();
+        let templates = self.templates.clone();
+        let fut = async move {
+            let txns = select_txns(
+                txpool_service.get_pending_txns(None, None),
+                config.miner.block_gas_limit,
+                config.miner.min_gas_price,
+            );
+            let block_template = chain_service
+                .create_block_template(author, auth_key_prefix, None, txns)
+                .await?;
+            let chain = BlockChain::<C>::new(config, block_template.parent_hash, storage)?;
+            let difficulty = C::calculate_next_difficulty(&chain)?;
+            let raw_header = block_template.as_raw_block_header(difficulty);
+            let template_id = raw_header.crypto_hash();
+            let mint_template = MintBlockTemplate {
+                template_id,
+                raw_header,
+                txn_count: block_template.body.transactions().len() as u64,
+                difficulty,
+                header_hash: hex::encode(template_id.to_vec()),
+            };
+
+            let mut templates = templates.lock();
+            // Templates on other parents can not be mined any more.
+            templates
+                .retain(|entry| entry.block_template.parent_hash == block_template.parent_hash);
+            if templates.len() >= MAX_TEMPLATES {
+                templates.remove(0);
+            }
+            templates.push(TemplateEntry {
+                template_id,
+                block_template,
+                difficulty,
+            });
+            Ok(mint_template)
+        }
+        .map_err(map_err);
+        Box::new(fut.boxed().compat())
+    }
+
+    fn submit_block(&self, template_id: HashValue, nonce: u64) -> FutureResult<HashValue> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let bus = self.bus.clone();
+        let templates = self.templates.clone();
+        let fut = async move {
+            let (block_template, difficulty) = {
+                let templates = templates.lock();
+                let entry = templates
+                    .iter()
+                    .find(|entry| entry.template_id == template_id)
+                    .ok_or_else(|| {
+                        to_invalid_param_err(format_err!(
+                            "Block template {:?} not found or stale.",
+                            template_id
+                        ))
+                    })?;
+                (entry.block_template.clone(), entry.difficulty)
+            };
+            let block = block_template.into_block(nonce, difficulty);
+            let chain = BlockChain::<C>::new(config, block.header().parent_hash(), storage)
+                .map_err(map_err)?;
+            C::verify(&chain, block.header()).map_err(to_invalid_param_err)?;
+            templates
+                .lock()
+                .retain(|entry| entry.template_id != template_id);
+
+            let block_id = block.id();
+            info!("Miner rpc submit new block with id: {:?}", block_id);
+            bus.do_send(Broadcast {
+                msg: MinedBlock(Arc::new(block)),
+            });
+            Ok::<_, jsonrpc_core::Error>(block_id)
+        };
+        Box::new(fut.boxed().compat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::compat::Future01CompatExt;
+    use starcoin_chain::ChainActor;
+    use starcoin_consensus::dev::DevConsensus;
+    use starcoin_genesis::Genesis;
+    use starcoin_storage::cache_storage::CacheStorage;
+    use starcoin_storage::storage::StorageInstance;
+    use starcoin_storage::Storage;
+    use starcoin_wallet_api::WalletAccount;
+    use std::time::Duration;
+    use txpool::TxPool;
+
+    #[actix_rt::test]
+    async fn test_mine_block_by_rpc() {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let storage = Arc::new(
+            Storage::new(StorageInstance::new_cache_instance(CacheStorage::new())).unwrap(),
+        );
+        let genesis = Genesis::load(config.net()).unwrap();
+        let startup_info = genesis
+            .execute_genesis_block(config.net(), storage.clone())
+            .unwrap();
+        let bus = BusActor::launch();
+        let txpool = TxPool::start(
+            config.tx_pool.clone(),
+            storage.clone(),
+            *startup_info.get_master(),
+            bus.clone(),
+        );
+        let chain = ChainActor::<DevConsensus>::launch(
+            config.clone(),
+            startup_info,
+            storage.clone(),
+            bus.clone(),
+            txpool.get_service(),
+        )
+        .unwrap();
+        let miner_rpc = MinerRpcImpl::<DevConsensus, _, _>::new(
+            config,
+            storage,
+            bus,
+            chain.clone(),
+            txpool.get_service(),
+        );
+
+        let account = WalletAccount::random();
+        let template = miner_rpc
+            .get_block_template(
+                *account.address(),
+                Some(account.get_auth_key().prefix().to_vec()),
+            )
+            .compat()
+            .await
+            .unwrap();
+        let nonce = DevConsensus::solve_consensus_nonce(
+            hex::decode(&template.header_hash).unwrap().as_slice(),
+            template.difficulty,
+        );
+        let block_id = miner_rpc
+            .submit_block(template.template_id, nonce)
+            .compat()
+            .await
+            .unwrap();
+        // The template can not be submitted again.
+        assert!(miner_rpc
+            .submit_block(template.template_id, nonce)
+            .compat()
+            .await
+            .is_err());
+
+        // The mined block is connected to the master chain.
+        let mut head = None;
+        for _ in 0..50 {
+            head = chain.clone().master_head_header().await.unwrap();
+            if head.as_ref().map(|header| header.id()) == Some(block_id) {
+                break;
+            }
+            tokio::time::delay_for(Duration::from_millis(100)).await;
+        }
+        let head = head.unwrap();
+        assert_eq!(block_id, head.id());
+        assert_eq!(*account.address(), head.author());
+        assert_eq!(template.raw_header.number, head.number());
+    }
+}
@@ -6,9 +6,11 @@ use starcoin_logger::prelude::*;
 mod chain_rpc;
 mod debug_rpc;
 mod dev_rpc;
+mod miner_rpc;
 mod node_rpc;
 mod pubsub;
 mod state_rpc;
+mod sync_rpc;
 #[cfg(test)]
 mod test_helper;
 mod txpool_rpc;
@@ -16,9 +18,11 @@ mod wallet_rpc;
 pub use self::chain_rpc::ChainRpcImpl;
 pub use self::debug_rpc::DebugRpcImpl;
 pub use self::dev_rpc::{DevPlaygroudService, DevRpcImpl};
+pub use self::miner_rpc::MinerRpcImpl;
 pub use self::node_rpc::NodeRpcImpl;
 pub use self::pubsub::{PubSubImpl, PubSubService};
 pub use self::state_rpc::StateRpcImpl;
+pub use self::sync_rpc::SyncRpcImpl;
 pub use self::txpool_rpc::TxPoolRpcImpl;
 pub use self::wallet_rpc::WalletRpcImpl;
 
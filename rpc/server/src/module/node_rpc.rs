@@ -7,10 +7,10 @@ use futures::FutureExt;
 use jsonrpc_core::Result;
 use network_api::NetworkService;
 use starcoin_config::NodeConfig;
-use starcoin_network::NetworkAsyncService;
-use starcoin_rpc_api::node::{NodeApi, NodeInfo};
+use starcoin_network::{NetworkAsyncService, PeerManageMessage};
+use starcoin_rpc_api::node::{NodeApi, NodeInfo, ReservedPeers};
 use starcoin_rpc_api::FutureResult;
-use starcoin_types::peer_info::PeerInfo;
+use starcoin_types::peer_info::{PeerId, PeerInfo, PeerScore};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -25,6 +25,14 @@ impl NodeRpcImpl {
     }
 }
 
+impl NodeRpcImpl {
+    fn manage_peer(&self, msg: PeerManageMessage) -> FutureResult<()> {
+        let service = self.service.clone().unwrap();
+        let fut = async move { service.manage_peer(msg).await };
+        Box::new(fut.map_err(map_err).boxed().compat())
+    }
+}
+
 impl NodeApi for NodeRpcImpl {
     fn status(&self) -> Result<bool> {
         //TODO check service status.
@@ -58,4 +66,54 @@ impl NodeApi for NodeRpcImpl {
     fn metrics(&self) -> Result<HashMap<String, String>> {
         Ok(starcoin_metrics::get_all_metrics())
     }
+
+    fn ban_peer(&self, peer_id: PeerId) -> Result<()> {
+        let service = self.service.clone().unwrap();
+        service.ban_peer(peer_id.into());
+        Ok(())
+    }
+
+    fn unban_peer(&self, peer_id: PeerId) -> Result<()> {
+        let service = self.service.clone().unwrap();
+        service.unban_peer(peer_id.into());
+        Ok(())
+    }
+
+    fn peer_scores(&self) -> FutureResult<Vec<PeerScore>> {
+        let service = self.service.clone().unwrap();
+        let fut = async move { Ok::<_, anyhow::Error>(service.peer_scores().await) };
+        Box::new(fut.map_err(map_err).boxed().compat())
+    }
+
+    fn add_peer(&self, peer: String) -> FutureResult<()> {
+        self.manage_peer(PeerManageMessage::AddPeer(peer))
+    }
+
+    fn remove_peer(&self, peer_id: PeerId) -> FutureResult<()> {
+        self.manage_peer(PeerManageMessage::RemovePeer(peer_id.into()))
+    }
+
+    fn reserved_peers(&self) -> FutureResult<ReservedPeers> {
+        let service = self.service.clone().unwrap();
+        let fut = async move {
+            let (peers, reserved_only) = service.reserved_peers().await;
+            Ok::<_, anyhow::Error>(ReservedPeers {
+                peers,
+                reserved_only,
+            })
+        };
+        Box::new(fut.map_err(map_err).boxed().compat())
+    }
+
+    fn add_reserved_peer(&self, peer: String) -> FutureResult<()> {
+        self.manage_peer(PeerManageMessage::AddReservedPeer(peer))
+    }
+
+    fn remove_reserved_peer(&self, peer_id: PeerId) -> FutureResult<()> {
+        self.manage_peer(PeerManageMessage::RemoveReservedPeer(peer_id.into()))
+    }
+
+    fn set_reserved_only(&self, reserved_only: bool) -> FutureResult<()> {
+        self.manage_peer(PeerManageMessage::SetReservedOnly(reserved_only))
+    }
 }
@@ -2,56 +2,140 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::map_err;
+use anyhow::{bail, format_err, Result};
 use futures::future::TryFutureExt;
+use futures::FutureExt;
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::state::StateApi;
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::{ChainStateAsyncService, StateWithProof};
+use starcoin_traits::ChainAsyncService;
 use starcoin_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
 };
+use starcoin_vm_types::abi::ModuleABI;
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::language_storage::ModuleId;
 
-pub struct StateRpcImpl<S>
+pub struct StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService + 'static,
+    C: ChainAsyncService + 'static,
 {
     service: S,
+    chain_service: C,
 }
 
-impl<S> StateRpcImpl<S>
+impl<S, C> StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    pub fn new(service: S) -> Self {
-        Self { service }
+    pub fn new(service: S, chain_service: C) -> Self {
+        Self {
+            service,
+            chain_service,
+        }
     }
 }
 
-impl<S> StateApi for StateRpcImpl<S>
+/// Resolve the state root to query, `None` means the head state root.
+async fn resolve_state_root<C>(
+    chain_service: C,
+    block_id: Option<HashValue>,
+    state_root: Option<HashValue>,
+) -> Result<Option<HashValue>>
+where
+    C: ChainAsyncService,
+{
+    match (block_id, state_root) {
+        (Some(_), Some(_)) => bail!("Only one of block_id and state_root can be specified."),
+        (Some(block_id), None) => {
+            let header = chain_service
+                .get_header_by_hash(&block_id)
+                .await?
+                .ok_or_else(|| format_err!("Can not find block header by {:?}", block_id))?;
+            Ok(Some(header.state_root()))
+        }
+        (None, state_root) => Ok(state_root),
+    }
+}
+
+impl<S, C> StateApi for StateRpcImpl<S, C>
 where
     S: ChainStateAsyncService,
+    C: ChainAsyncService,
 {
-    fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>> {
-        let fut = self.service.clone().get(access_path).map_err(map_err);
-        Box::new(fut.compat())
+    fn get(
+        &self,
+        access_path: AccessPath,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<Vec<u8>>> {
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(chain_service, block_id, state_root).await?;
+            service.get(access_path, state_root).await
+        }
+        .map_err(map_err);
+        Box::new(fut.boxed().compat())
     }
 
-    fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProof> {
-        let fut = self
-            .service
-            .clone()
-            .get_with_proof(access_path)
-            .map_err(map_err);
-        Box::new(fut.compat())
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<StateWithProof> {
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(chain_service, block_id, state_root).await?;
+            service.get_with_proof(access_path, state_root).await
+        }
+        .map_err(map_err);
+        Box::new(fut.boxed().compat())
     }
 
-    fn get_account_state(&self, address: AccountAddress) -> FutureResult<Option<AccountState>> {
-        let fut = self
-            .service
-            .clone()
-            .get_account_state(address)
-            .map_err(map_err);
-        Box::new(fut.compat())
+    fn get_account_state(
+        &self,
+        address: AccountAddress,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<AccountState>> {
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(chain_service, block_id, state_root).await?;
+            service.get_account_state(address, state_root).await
+        }
+        .map_err(map_err);
+        Box::new(fut.boxed().compat())
+    }
+
+    fn get_module_abi(
+        &self,
+        module_id: ModuleId,
+        block_id: Option<HashValue>,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<ModuleABI>> {
+        let service = self.service.clone();
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            let state_root = resolve_state_root(chain_service, block_id, state_root).await?;
+            let code = service
+                .get(AccessPath::code_access_path(&module_id), state_root)
+                .await?;
+            code.map(|code| {
+                let module = CompiledModule::deserialize(code.as_slice())
+                    .map_err(|e| format_err!("Deserialize module {} fail: {:?}", module_id, e))?;
+                Ok(ModuleABI::new(&module))
+            })
+            .transpose()
+        }
+        .map_err(map_err);
+        Box::new(fut.boxed().compat())
     }
 
     fn get_state_root(&self) -> FutureResult<HashValue> {
@@ -59,3 +143,101 @@ where
         Box::new(fut.compat())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::ensure;
+    use futures::compat::Future01CompatExt;
+    use jsonrpc_core::IoHandler;
+    use serde_json::{json, Value};
+    use starcoin_bus::BusActor;
+    use starcoin_chain::mock::mock_chain_service::MockChainService;
+    use starcoin_config::NodeConfig;
+    use starcoin_genesis::Genesis;
+    use starcoin_state_service::ChainStateActor;
+    use starcoin_storage::cache_storage::CacheStorage;
+    use starcoin_storage::storage::StorageInstance;
+    use starcoin_storage::{BlockStore, Storage};
+    use starcoin_vm_types::abi::{ArgumentABI, KindABI, TypeABI, TypeParameterABI};
+    use starcoin_vm_types::identifier::Identifier;
+    use starcoin_vm_types::language_storage::CORE_CODE_ADDRESS;
+    use std::sync::Arc;
+
+    async fn get_module_abi(io: &IoHandler, module_id: ModuleId) -> Result<Option<ModuleABI>> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": "state.get_module_abi",
+            "params": [module_id, null, null],
+            "id": 0,
+        });
+        let response = io
+            .handle_request(request.to_string().as_str())
+            .compat()
+            .await
+            .map_err(|_| format_err!("Handle rpc request fail."))?
+            .ok_or_else(|| format_err!("Rpc response is empty."))?;
+        let mut response: Value = serde_json::from_str(response.as_str())?;
+        ensure!(
+            response.get("error").is_none(),
+            "Rpc error: {}",
+            response["error"]
+        );
+        Ok(serde_json::from_value(response["result"].take())?)
+    }
+
+    #[stest::test]
+    async fn test_get_module_abi() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance(
+            CacheStorage::new(),
+        ))?);
+        let startup_info =
+            Genesis::load(config.net())?.execute_genesis_block(config.net(), storage.clone())?;
+        let head_block = storage
+            .get_block(*startup_info.get_master())?
+            .expect("Head block must exist.");
+        let state_service = ChainStateActor::launch(
+            config,
+            BusActor::launch(),
+            storage,
+            Some(head_block.header().state_root()),
+        )?;
+        let mut io = IoHandler::new();
+        io.extend_with(StateRpcImpl::new(state_service, MockChainService::default()).to_delegate());
+
+        let abi = get_module_abi(
+            &io,
+            ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Account")?),
+        )
+        .await?
+        .expect("Account module must exist.");
+        assert_eq!(abi.address, CORE_CODE_ADDRESS);
+        assert_eq!(abi.name, "Account");
+        let balance_type_parameters = vec![TypeParameterABI {
+            name: "T0".to_string(),
+            kind: KindABI::All,
+        }];
+        let balance_struct = abi.find_struct("Balance").expect("Balance must exist.");
+        assert!(balance_struct.is_resource);
+        assert_eq!(balance_struct.type_parameters, balance_type_parameters);
+        let balance = abi.find_function("balance").expect("balance must exist.");
+        assert_eq!(balance.type_parameters, balance_type_parameters);
+        assert_eq!(
+            balance.parameters,
+            vec![ArgumentABI {
+                name: "arg0".to_string(),
+                type_abi: TypeABI::Address,
+            }]
+        );
+        assert_eq!(balance.returns, vec![TypeABI::U128]);
+
+        let abi = get_module_abi(
+            &io,
+            ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("NotExist")?),
+        )
+        .await?;
+        assert!(abi.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpc_core::Result;
+use starcoin_rpc_api::sync::{SyncApi, SyncStatus};
+use starcoin_sync_api::SyncProgress;
+
+pub struct SyncRpcImpl {
+    progress: SyncProgress,
+}
+
+impl SyncRpcImpl {
+    pub fn new(progress: SyncProgress) -> Self {
+        Self { progress }
+    }
+}
+
+impl SyncApi for SyncRpcImpl {
+    fn status(&self) -> Result<SyncStatus> {
+        Ok(self.progress.status())
+    }
+}
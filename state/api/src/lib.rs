@@ -25,11 +25,24 @@ pub trait ChainStateService: ChainStateReader {
 
 #[async_trait::async_trait]
 pub trait ChainStateAsyncService: Clone + std::marker::Unpin + Send + Sync {
-    async fn get(self, access_path: AccessPath) -> Result<Option<Vec<u8>>>;
-
-    async fn get_with_proof(self, access_path: AccessPath) -> Result<StateWithProof>;
-
-    async fn get_account_state(self, address: AccountAddress) -> Result<Option<AccountState>>;
+    /// Get the state at `state_root`, or at the head state root if `state_root` is none.
+    async fn get(
+        self,
+        access_path: AccessPath,
+        state_root: Option<HashValue>,
+    ) -> Result<Option<Vec<u8>>>;
+
+    async fn get_with_proof(
+        self,
+        access_path: AccessPath,
+        state_root: Option<HashValue>,
+    ) -> Result<StateWithProof>;
+
+    async fn get_account_state(
+        self,
+        address: AccountAddress,
+        state_root: Option<HashValue>,
+    ) -> Result<Option<AccountState>>;
 
     async fn state_root(self) -> Result<HashValue>;
 }
@@ -20,15 +20,27 @@ impl MockChainStateService {
 
 #[async_trait::async_trait]
 impl ChainStateAsyncService for MockChainStateService {
-    async fn get(self, _access_path: AccessPath) -> Result<Option<Vec<u8>>> {
+    async fn get(
+        self,
+        _access_path: AccessPath,
+        _state_root: Option<HashValue>,
+    ) -> Result<Option<Vec<u8>>> {
         unimplemented!()
     }
 
-    async fn get_with_proof(self, _access_path: AccessPath) -> Result<StateWithProof> {
+    async fn get_with_proof(
+        self,
+        _access_path: AccessPath,
+        _state_root: Option<HashValue>,
+    ) -> Result<StateWithProof> {
         unimplemented!()
     }
 
-    async fn get_account_state(self, _address: AccountAddress) -> Result<Option<AccountState>> {
+    async fn get_account_state(
+        self,
+        _address: AccountAddress,
+        _state_root: Option<HashValue>,
+    ) -> Result<Option<AccountState>> {
         unimplemented!()
     }
 
@@ -64,13 +64,20 @@ impl Handler<StateRequest> for ChainStateActor {
 
     fn handle(&mut self, msg: StateRequest, _ctx: &mut Self::Context) -> Self::Result {
         let response = match msg {
-            StateRequest::Get(access_path) => StateResponse::State(self.service.get(&access_path)?),
-            StateRequest::GetWithProof(access_path) => {
-                StateResponse::StateWithProof(Box::new(self.service.get_with_proof(&access_path)?))
-            }
-            StateRequest::GetAccountState(address) => {
-                StateResponse::AccountState(self.service.get_account_state(&address)?)
+            StateRequest::Get(access_path, state_root) => StateResponse::State(
+                self.service
+                    .read_at(state_root, |reader| reader.get(&access_path))?,
+            ),
+            StateRequest::GetWithProof(access_path, state_root) => {
+                StateResponse::StateWithProof(Box::new(
+                    self.service
+                        .read_at(state_root, |reader| reader.get_with_proof(&access_path))?,
+                ))
             }
+            StateRequest::GetAccountState(address, state_root) => StateResponse::AccountState(
+                self.service
+                    .read_at(state_root, |reader| reader.get_account_state(&address))?,
+            ),
             StateRequest::StateRoot() => StateResponse::StateRoot(self.service.state_root()),
         };
         Ok(response)
@@ -106,10 +113,14 @@ impl Into<ChainStateActorRef> for Addr<ChainStateActor> {
 
 #[async_trait::async_trait]
 impl ChainStateAsyncService for ChainStateActorRef {
-    async fn get(self, access_path: AccessPath) -> Result<Option<Vec<u8>>> {
+    async fn get(
+        self,
+        access_path: AccessPath,
+        state_root: Option<HashValue>,
+    ) -> Result<Option<Vec<u8>>> {
         let response = self
             .0
-            .send(StateRequest::Get(access_path))
+            .send(StateRequest::Get(access_path, state_root))
             .await
             .map_err(Into::<Error>::into)??;
         if let StateResponse::State(state) = response {
@@ -119,10 +130,14 @@ impl ChainStateAsyncService for ChainStateActorRef {
         }
     }
 
-    async fn get_with_proof(self, access_path: AccessPath) -> Result<StateWithProof> {
+    async fn get_with_proof(
+        self,
+        access_path: AccessPath,
+        state_root: Option<HashValue>,
+    ) -> Result<StateWithProof> {
         let response = self
             .0
-            .send(StateRequest::GetWithProof(access_path))
+            .send(StateRequest::GetWithProof(access_path, state_root))
             .await
             .map_err(Into::<Error>::into)??;
         if let StateResponse::StateWithProof(state) = response {
@@ -132,10 +147,14 @@ impl ChainStateAsyncService for ChainStateActorRef {
         }
     }
 
-    async fn get_account_state(self, address: AccountAddress) -> Result<Option<AccountState>> {
+    async fn get_account_state(
+        self,
+        address: AccountAddress,
+        state_root: Option<HashValue>,
+    ) -> Result<Option<AccountState>> {
         let response = self
             .0
-            .send(StateRequest::GetAccountState(address))
+            .send(StateRequest::GetAccountState(address, state_root))
             .await
             .map_err(Into::<Error>::into)??;
         if let StateResponse::AccountState(state) = response {
@@ -164,6 +183,9 @@ mod tests {
     use super::*;
     use starcoin_bus::BusActor;
     use starcoin_state_api::mock::MockStateNodeStore;
+    use starcoin_state_api::ChainStateWriter;
+    use starcoin_statedb::ChainStateDB;
+    use starcoin_types::access_path::random_resource;
 
     #[stest::test]
     async fn test_actor_launch() -> Result<()> {
@@ -175,4 +197,37 @@ mod tests {
         //assert!(account.is_some());
         Ok(())
     }
+
+    #[stest::test]
+    async fn test_get_state_at_history_root() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let mock_store = Arc::new(MockStateNodeStore::new());
+        let state_db = ChainStateDB::new(mock_store.clone(), None);
+        let access_path = random_resource();
+        state_db.set(&access_path, vec![0])?;
+        let history_root = state_db.commit()?;
+        state_db.flush()?;
+        state_db.set(&access_path, vec![1])?;
+        let head_root = state_db.commit()?;
+        state_db.flush()?;
+
+        let bus_actor = BusActor::launch();
+        let actor = ChainStateActor::launch(config, bus_actor, mock_store, Some(head_root))?;
+        let state = actor.clone().get(access_path.clone(), None).await?;
+        assert_eq!(state, Some(vec![1]));
+        let state = actor
+            .clone()
+            .get(access_path.clone(), Some(history_root))
+            .await?;
+        assert_eq!(state, Some(vec![0]));
+        let state_with_proof = actor
+            .get_with_proof(access_path.clone(), Some(history_root))
+            .await?;
+        state_with_proof.proof.verify(
+            history_root,
+            access_path,
+            state_with_proof.state.as_deref(),
+        )?;
+        Ok(())
+    }
 }
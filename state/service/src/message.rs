@@ -11,9 +11,10 @@ use starcoin_types::{
 
 #[derive(Debug, Clone)]
 pub enum StateRequest {
-    Get(AccessPath),
-    GetWithProof(AccessPath),
-    GetAccountState(AccountAddress),
+    /// Get state at the optional state root, default is the head state root.
+    Get(AccessPath, Option<HashValue>),
+    GetWithProof(AccessPath, Option<HashValue>),
+    GetAccountState(AccountAddress, Option<HashValue>),
     StateRoot(),
 }
 
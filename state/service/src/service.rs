@@ -27,6 +27,23 @@ impl ChainStateServiceImpl {
     }
 }
 
+impl ChainStateServiceImpl {
+    /// Read state by `f` at `state_root`, or at the current state root if `state_root` is none.
+    /// The state of a history root is readable until its nodes are pruned.
+    pub fn read_at<T>(
+        &self,
+        state_root: Option<HashValue>,
+        f: impl FnOnce(&dyn ChainStateReader) -> Result<T>,
+    ) -> Result<T> {
+        match state_root {
+            Some(state_root) if state_root != self.reader.state_root() => {
+                f(&self.reader.change_root(state_root))
+            }
+            _ => f(self),
+        }
+    }
+}
+
 impl ChainStateService for ChainStateServiceImpl {
     fn change_root(&mut self, state_root: HashValue) {
         self.reader = self.reader.change_root(state_root);
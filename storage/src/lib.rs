@@ -22,7 +22,7 @@ use starcoin_types::contract_event::ContractEvent;
 use starcoin_types::transaction::Transaction;
 use starcoin_types::{
     block::{Block, BlockBody, BlockHeader, BlockInfo},
-    startup_info::StartupInfo,
+    startup_info::{StartupInfo, StateSyncProgress},
     transaction::TransactionInfo,
 };
 use std::collections::BTreeMap;
@@ -61,6 +61,8 @@ pub const TRANSACTION_PREFIX_NAME: ColumnFamilyName = "transaction";
 pub const TRANSACTION_INFO_PREFIX_NAME: ColumnFamilyName = "transaction_info";
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
+/// Key of the state sync progress, saved in the startup info column family.
+const STATE_SYNC_PROGRESS_KEY: &str = "state_sync_progress";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -87,6 +89,10 @@ pub trait BlockStore {
     fn get_startup_info(&self) -> Result<Option<StartupInfo>>;
     fn save_startup_info(&self, startup_info: StartupInfo) -> Result<()>;
 
+    fn get_state_sync_progress(&self) -> Result<Option<StateSyncProgress>>;
+    fn save_state_sync_progress(&self, progress: StateSyncProgress) -> Result<()>;
+    fn delete_state_sync_progress(&self) -> Result<()>;
+
     fn get_headers(&self) -> Result<Vec<HashValue>>;
 
     fn get_block(&self, block_id: HashValue) -> Result<Option<Block>>;
@@ -219,6 +225,27 @@ impl BlockStore for Storage {
         )
     }
 
+    fn get_state_sync_progress(&self) -> Result<Option<StateSyncProgress>> {
+        self.startup_info_storage
+            .get(STATE_SYNC_PROGRESS_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(Some(bytes.try_into()?)),
+                None => Ok(None),
+            })
+    }
+
+    fn save_state_sync_progress(&self, progress: StateSyncProgress) -> Result<()> {
+        self.startup_info_storage.put(
+            STATE_SYNC_PROGRESS_KEY.as_bytes().to_vec(),
+            progress.try_into()?,
+        )
+    }
+
+    fn delete_state_sync_progress(&self) -> Result<()> {
+        self.startup_info_storage
+            .remove(STATE_SYNC_PROGRESS_KEY.as_bytes().to_vec())
+    }
+
     fn get_headers(&self) -> Result<Vec<HashValue>> {
         self.block_storage.get_headers()
     }
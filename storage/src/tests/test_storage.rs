@@ -6,9 +6,12 @@ extern crate chrono;
 use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
 use crate::storage::{InnerStore, StorageInstance, ValueCodec, CACHE_NONE_OBJECT};
-use crate::{Storage, TransactionInfoStore, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME};
+use crate::{
+    BlockStore, Storage, TransactionInfoStore, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
+};
 use anyhow::Result;
 use crypto::HashValue;
+use starcoin_types::startup_info::StateSyncProgress;
 use starcoin_types::transaction::TransactionInfo;
 use starcoin_types::vm_error::KeptVMStatus;
 use std::sync::Arc;
@@ -78,6 +81,29 @@ fn test_storage() {
     assert!(transaction_info2.is_some());
     assert_eq!(transaction_info1, transaction_info2.unwrap());
 }
+#[test]
+fn test_state_sync_progress() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let db_storage = Arc::new(DBStorage::new(tmpdir.path()));
+    let storage = Storage::new(StorageInstance::new_db_instance(db_storage.clone()))?;
+    assert!(storage.get_state_sync_progress()?.is_none());
+    let progress = StateSyncProgress {
+        pivot_id: HashValue::random(),
+        state_root: HashValue::random(),
+        block_accumulator_root: HashValue::random(),
+        state_nodes: vec![(HashValue::random(), true), (HashValue::random(), false)],
+        accumulator_nodes: vec![HashValue::random()],
+        txn_info_blocks: vec![],
+    };
+    storage.save_state_sync_progress(progress.clone())?;
+    drop(storage);
+    let storage = Storage::new(StorageInstance::new_db_instance(db_storage))?;
+    assert_eq!(Some(progress), storage.get_state_sync_progress()?);
+    storage.delete_state_sync_progress()?;
+    assert!(storage.get_state_sync_progress()?.is_none());
+    Ok(())
+}
+
 #[test]
 fn test_two_level_storage() {
     let cache_storage = Arc::new(CacheStorage::new());
@@ -13,6 +13,9 @@ use starcoin_types::{
 };
 use std::cmp::Ordering;
 
+mod sync_status;
+pub use sync_status::{SyncProgress, SyncStatus};
+
 #[derive(Message, Clone, Debug)]
 #[rtype(result = "()")]
 pub struct StartSyncTxnEvent;
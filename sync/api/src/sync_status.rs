@@ -0,0 +1,119 @@
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::peer_info::PeerId;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Status of block sync.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub syncing: bool,
+    pub current_number: BlockNumber,
+    pub target_number: BlockNumber,
+    /// Peers which are used for download blocks.
+    pub peers: Vec<PeerId>,
+    pub blocks_per_second: f64,
+    /// Estimated seconds to reach target number, None if the speed is unknown.
+    pub eta_secs: Option<u64>,
+}
+
+#[derive(Default)]
+struct ProgressInner {
+    syncing: bool,
+    begin_number: BlockNumber,
+    current_number: BlockNumber,
+    target_number: BlockNumber,
+    peers: Vec<PeerId>,
+    begin_time: Option<Instant>,
+}
+
+/// Progress of block sync, updated by block sync task and read by rpc.
+#[derive(Clone, Default)]
+pub struct SyncProgress {
+    inner: Arc<RwLock<ProgressInner>>,
+}
+
+impl SyncProgress {
+    pub fn begin(&self, current_number: BlockNumber, target_number: BlockNumber) {
+        let mut inner = self.inner.write();
+        inner.syncing = true;
+        inner.begin_number = current_number;
+        inner.current_number = current_number;
+        inner.target_number = target_number;
+        inner.begin_time = Some(Instant::now());
+    }
+
+    pub fn update_peers(&self, peers: Vec<PeerId>) {
+        self.inner.write().peers = peers;
+    }
+
+    pub fn update_current(&self, current_number: BlockNumber) {
+        let mut inner = self.inner.write();
+        if current_number > inner.current_number {
+            inner.current_number = current_number;
+        }
+    }
+
+    pub fn finish(&self) {
+        let mut inner = self.inner.write();
+        inner.syncing = false;
+        inner.peers.clear();
+        inner.begin_time = None;
+    }
+
+    pub fn status(&self) -> SyncStatus {
+        let inner = self.inner.read();
+        let synced = inner.current_number.saturating_sub(inner.begin_number);
+        let blocks_per_second = match inner.begin_time {
+            Some(begin_time) if inner.syncing => {
+                let elapsed = begin_time.elapsed().as_secs_f64();
+                if elapsed > 0f64 {
+                    synced as f64 / elapsed
+                } else {
+                    0f64
+                }
+            }
+            _ => 0f64,
+        };
+        let eta_secs = if blocks_per_second > 0f64 {
+            let remain = inner.target_number.saturating_sub(inner.current_number);
+            Some((remain as f64 / blocks_per_second).ceil() as u64)
+        } else {
+            None
+        };
+        SyncStatus {
+            syncing: inner.syncing,
+            current_number: inner.current_number,
+            target_number: inner.target_number,
+            peers: inner.peers.clone(),
+            blocks_per_second,
+            eta_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_progress() {
+        let progress = SyncProgress::default();
+        assert!(!progress.status().syncing);
+        progress.begin(10, 110);
+        progress.update_peers(vec![PeerId::random()]);
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        progress.update_current(60);
+        let status = progress.status();
+        assert!(status.syncing);
+        assert_eq!(60, status.current_number);
+        assert_eq!(1, status.peers.len());
+        assert!(status.blocks_per_second > 0f64);
+        assert!(status.eta_secs.is_some());
+        progress.finish();
+        let status = progress.status();
+        assert!(!status.syncing);
+        assert_eq!(None, status.eta_secs);
+    }
+}
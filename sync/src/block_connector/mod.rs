@@ -1,4 +1,5 @@
 use crate::state_sync::StateSyncTaskRef;
+use anyhow::Result;
 use chain::ChainActorRef;
 use crypto::HashValue;
 use logger::prelude::*;
@@ -109,6 +110,17 @@ impl FutureBlockPool {
     }
 }
 
+/// Outcome of connecting a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectOutcome {
+    Connected,
+    /// The block is kept as future block, mismatches the local pivot, or connect failed by
+    /// local error, it can be retried later and the peer should not be punished.
+    Pending,
+    /// The block fails verification, the peer which send it should be punished.
+    Invalid,
+}
+
 pub struct BlockConnector<C>
 where
     C: Consensus + Sync + Send + 'static + Clone,
@@ -166,18 +178,21 @@ where
         })
     }
 
-    pub async fn do_block_and_child(&self, block: Block) {
+    /// Connect the block and its cached descendants, return the outcome of the block itself.
+    pub async fn do_block_and_child(&self, block: Block) -> ConnectOutcome {
         let block_id = block.header().id();
-        if self.do_block_connect(block).await {
+        let outcome = self.do_block_connect(block).await;
+        if outcome == ConnectOutcome::Connected {
             if let Some(child) = self.future_blocks.take_child(&block_id) {
                 for son_block in child {
                     let _ = self.do_block_connect(son_block).await;
                 }
             }
         }
+        outcome
     }
 
-    async fn do_block_connect(&self, block: Block) -> bool {
+    async fn do_block_connect(&self, block: Block) -> ConnectOutcome {
         let pivot = self.get_pivot();
         let mut _state_sync_address = None;
         let current_block_id = block.id();
@@ -235,54 +250,70 @@ where
             }
         };
 
-        match connect_result {
-            Ok(connect) => {
-                match connect {
-                    ConnectBlockResult::SUCCESS | ConnectBlockResult::DuplicateConn => {
-                        return true;
-                    }
-                    ConnectBlockResult::FutureBlock => self.future_blocks.add_future_block(block),
-                    ConnectBlockResult::VerifyBlockIdFailed => {
-                        //TODO
-                        error!(
-                            "Connect block {:?} verify block id failed.",
-                            current_block_id
-                        );
-                    }
-                    ConnectBlockResult::VerifyConsensusFailed => {
-                        error!("Connect block {:?} verify nonce failed.", current_block_id);
-                        //TODO: remove child block
-                    }
-                    ConnectBlockResult::VerifyBodyFailed => {
-                        error!("Connect block {:?} verify body failed.", current_block_id);
-                        //TODO:
-                    }
-                    ConnectBlockResult::VerifyTxnInfoFailed => {
-                        error!(
-                            "Connect block {:?} verify txn info failed.",
-                            current_block_id
-                        );
-                        //todo: state_sync_address.expect("").reset();
-                    }
-                    ConnectBlockResult::UncleBlockIllegal => {
-                        error!(
-                            "Connect block {:?} verify txn info failed.",
-                            current_block_id
-                        );
-                        //todo: state_sync_address.expect("").reset();
-                    }
-                    ConnectBlockResult::DuplicateUncles => {
-                        error!(
-                            "Connect block {:?} verify txn info failed.",
-                            current_block_id
-                        );
-                        //todo: state_sync_address.expect("").reset();
-                    }
-                }
-            }
+        match &connect_result {
+            Ok(ConnectBlockResult::FutureBlock) => self.future_blocks.add_future_block(block),
+            Ok(ConnectBlockResult::SUCCESS) | Ok(ConnectBlockResult::DuplicateConn) => {}
+            Ok(result) => error!("Connect block {:?} failed : {:?}", current_block_id, result),
             Err(e) => error!("Connect block {:?} failed : {:?}", current_block_id, e),
         }
+        connect_outcome(&connect_result)
+    }
+}
+
+/// Map the result of connecting a block to the outcome. Only the failures of verifying the block
+/// itself are `Invalid`, a block id mismatch with the local pivot, or a local error is retryable.
+fn connect_outcome(connect_result: &Result<ConnectBlockResult>) -> ConnectOutcome {
+    match connect_result {
+        Ok(ConnectBlockResult::SUCCESS) | Ok(ConnectBlockResult::DuplicateConn) => {
+            ConnectOutcome::Connected
+        }
+        Ok(ConnectBlockResult::FutureBlock) | Ok(ConnectBlockResult::VerifyBlockIdFailed) => {
+            ConnectOutcome::Pending
+        }
+        Ok(ConnectBlockResult::VerifyConsensusFailed)
+        | Ok(ConnectBlockResult::VerifyBodyFailed)
+        | Ok(ConnectBlockResult::VerifyTxnInfoFailed)
+        | Ok(ConnectBlockResult::UncleBlockIllegal)
+        | Ok(ConnectBlockResult::DuplicateUncles) => ConnectOutcome::Invalid,
+        Err(_) => ConnectOutcome::Pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::format_err;
 
-        false
+    #[test]
+    fn test_connect_outcome() {
+        assert_eq!(
+            connect_outcome(&Ok(ConnectBlockResult::SUCCESS)),
+            ConnectOutcome::Connected
+        );
+        assert_eq!(
+            connect_outcome(&Ok(ConnectBlockResult::DuplicateConn)),
+            ConnectOutcome::Connected
+        );
+        assert_eq!(
+            connect_outcome(&Ok(ConnectBlockResult::FutureBlock)),
+            ConnectOutcome::Pending
+        );
+        assert_eq!(
+            connect_outcome(&Ok(ConnectBlockResult::VerifyBlockIdFailed)),
+            ConnectOutcome::Pending
+        );
+        assert_eq!(
+            connect_outcome(&Err(format_err!("storage error"))),
+            ConnectOutcome::Pending
+        );
+        for result in vec![
+            ConnectBlockResult::VerifyConsensusFailed,
+            ConnectBlockResult::VerifyBodyFailed,
+            ConnectBlockResult::VerifyTxnInfoFailed,
+            ConnectBlockResult::UncleBlockIllegal,
+            ConnectBlockResult::DuplicateUncles,
+        ] {
+            assert_eq!(connect_outcome(&Ok(result)), ConnectOutcome::Invalid);
+        }
     }
 }
@@ -1,5 +1,5 @@
 use crate::download::DownloadActor;
-use crate::helper::{get_body_by_hash, get_headers, get_headers_msg_for_common};
+use crate::helper::{get_body_by_hash_with_peer, get_headers_by_number};
 use crate::sync_metrics::{LABEL_BLOCK_BODY, LABEL_HASH, SYNC_METRICS};
 use crate::sync_task::{
     SyncTaskAction, SyncTaskRequest, SyncTaskResponse, SyncTaskState, SyncTaskType,
@@ -7,21 +7,36 @@ use crate::sync_task::{
 use crate::Downloader;
 use actix::prelude::*;
 use actix::{Actor, ActorContext, Addr, Context, Handler};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use crypto::hash::HashValue;
 use futures_timer::Delay;
 use logger::prelude::*;
 use network::NetworkAsyncService;
-use network_rpc::{gen_client::NetworkRpcClient, BlockBody};
-use std::collections::{HashMap, VecDeque};
+use network_api::NetworkService;
+use network_rpc::{gen_client::NetworkRpcClient, BlockBody, GetBlockHeadersByNumber};
+use starcoin_sync_api::SyncProgress;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Formatter, Result as FmtResult};
 use std::sync::Arc;
 use std::time::Duration;
 use traits::Consensus;
 use types::block::{Block, BlockBody as RealBlockBody, BlockHeader, BlockNumber};
+use types::peer_info::PeerId;
 
+/// Max count of headers which wait for bodies.
 const MAX_LEN: usize = 100;
+/// Max count of bodies in one request.
 const MAX_SIZE: usize = 10;
+/// Max count of headers in one request.
+const HEADER_CHUNK_SIZE: u64 = 50;
+/// Max count of requests in flight for one peer.
+const MAX_IN_FLIGHT_PER_PEER: usize = 2;
+/// Max count of peers used for sync.
+const MAX_SYNC_PEERS: usize = 8;
+/// Max count of retry for a failed chunk.
+const MAX_RETRY: usize = 5;
+/// Max count of received header chunks which wait for link.
+const MAX_HEADER_CHUNKS: usize = 10;
 
 #[derive(Default, Debug, Message)]
 #[rtype(result = "Result<()>")]
@@ -31,39 +46,44 @@ pub struct BlockSyncBeginEvent;
 #[rtype(result = "Result<()>")]
 pub struct NextTimeEvent;
 
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct PeersEvent {
+    peers: Vec<PeerId>,
+}
+
+/// A chunk of sync data, retry on other peers if failed.
 #[derive(Debug, Clone)]
-enum DataType {
-    Header,
-    Body,
+enum SyncChunk {
+    /// headers of the number range [start, end].
+    Header(BlockNumber, BlockNumber),
+    Body(Vec<HashValue>),
 }
 
-#[derive(Debug, Message)]
-#[rtype(result = "()")]
-struct SyncDataEvent {
-    data_type: DataType,
-    hashs: Vec<HashValue>,
-    headers: Vec<BlockHeader>,
-    bodies: Vec<BlockBody>,
+#[derive(Debug, Clone)]
+struct ChunkTask {
+    chunk: SyncChunk,
+    /// peers which fail to provide the chunk.
+    failed_peers: HashSet<PeerId>,
 }
 
-impl SyncDataEvent {
-    fn new_header_event(headers: Vec<BlockHeader>) -> Self {
-        SyncDataEvent {
-            data_type: DataType::Header,
-            hashs: Vec::new(),
-            headers,
-            bodies: Vec::new(),
+impl ChunkTask {
+    fn new(chunk: SyncChunk) -> Self {
+        ChunkTask {
+            chunk,
+            failed_peers: HashSet::new(),
         }
     }
+}
 
-    fn new_body_event(bodies: Vec<BlockBody>, hashs: Vec<HashValue>) -> Self {
-        SyncDataEvent {
-            data_type: DataType::Body,
-            hashs,
-            headers: Vec::new(),
-            bodies,
-        }
-    }
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct SyncDataEvent {
+    peer_id: PeerId,
+    task: ChunkTask,
+    headers: Vec<BlockHeader>,
+    bodies: Vec<BlockBody>,
+    success: bool,
 }
 
 struct BlockSyncTask {
@@ -89,12 +109,6 @@ impl BlockSyncTask {
         self.wait_2_sync.push_back(hash)
     }
 
-    pub fn push_hashs(&mut self, hashs: Vec<HashValue>) {
-        for hash in hashs {
-            self.wait_2_sync.push_back(hash)
-        }
-    }
-
     fn take_hashs(&mut self) -> Option<Vec<HashValue>> {
         let mut hashs = Vec::new();
         for _ in 0..MAX_SIZE {
@@ -113,20 +127,75 @@ impl BlockSyncTask {
     }
 }
 
+/// Choose the peer with the least requests in flight, skip the failed peers and the busy peers.
+fn choose_peer(
+    peers: &[PeerId],
+    in_flight: &HashMap<PeerId, usize>,
+    failed_peers: &HashSet<PeerId>,
+) -> Option<PeerId> {
+    peers
+        .iter()
+        .filter(|peer_id| !failed_peers.contains(peer_id))
+        .map(|peer_id| (peer_id, in_flight.get(peer_id).copied().unwrap_or(0)))
+        .filter(|(_, count)| *count < MAX_IN_FLIGHT_PER_PEER)
+        .min_by_key(|(_, count)| *count)
+        .map(|(peer_id, _)| peer_id.clone())
+}
+
+/// Check the headers are the linked headers of the number range [start, end].
+fn verify_header_chunk(
+    headers: &[BlockHeader],
+    start: BlockNumber,
+    end: BlockNumber,
+) -> Result<()> {
+    ensure!(
+        headers.len() as u64 == end - start + 1,
+        "header chunk len miss match."
+    );
+    for (i, header) in headers.iter().enumerate() {
+        ensure!(
+            header.number() == start + i as u64,
+            "header number miss match."
+        );
+        if i > 0 {
+            ensure!(
+                header.parent_hash() == headers[i - 1].id(),
+                "header parent hash miss match."
+            );
+        }
+    }
+    Ok(())
+}
+
 pub struct BlockSyncTaskActor<C>
 where
     C: Consensus + Sync + Send + 'static + Clone,
 {
     ancestor_number: BlockNumber,
     target_number: BlockNumber,
+    /// The last header which is linked to the chain of ancestor.
     next: (HashValue, BlockNumber),
+    /// The start number of next header chunk to request.
+    next_header_number: BlockNumber,
+    /// Received header chunks which are not linked with the peer and task of the chunk, keyed by
+    /// start number.
+    header_chunks: BTreeMap<BlockNumber, (PeerId, ChunkTask, Vec<BlockHeader>)>,
     headers: HashMap<HashValue, BlockHeader>,
     body_task: BlockSyncTask,
+    /// Received bodies which wait for connect.
+    bodies: HashMap<HashValue, BlockBody>,
+    /// Linked headers in number order, blocks are connected in this order.
+    connect_queue: VecDeque<HashValue>,
+    retry_tasks: VecDeque<ChunkTask>,
+    peers: Vec<PeerId>,
+    in_flight: HashMap<PeerId, usize>,
+    refreshing_peers: bool,
     downloader: Arc<Downloader<C>>,
     network: NetworkAsyncService,
     rpc_client: NetworkRpcClient<NetworkAsyncService>,
     state: SyncTaskState,
     download_address: Addr<DownloadActor<C>>,
+    progress: SyncProgress,
 }
 
 impl<C> Debug for BlockSyncTaskActor<C>
@@ -140,6 +209,7 @@ where
             .field(&self.next.clone())
             .field(&self.headers.len())
             .field(&self.body_task.len())
+            .field(&self.peers.len())
             .finish()
     }
 }
@@ -155,14 +225,24 @@ where
         network: NetworkAsyncService,
         start: bool,
         download_address: Addr<DownloadActor<C>>,
+        progress: SyncProgress,
     ) -> BlockSyncTaskRef<C> {
         debug_assert!(ancestor_header.number() < target_number);
+        progress.begin(ancestor_header.number(), target_number);
         let address = BlockSyncTaskActor::create(move |_ctx| Self {
             ancestor_number: ancestor_header.number(),
             target_number,
             next: (ancestor_header.id(), ancestor_header.number()),
+            next_header_number: ancestor_header.number() + 1,
+            header_chunks: BTreeMap::new(),
             headers: HashMap::new(),
             body_task: BlockSyncTask::new(),
+            bodies: HashMap::new(),
+            connect_queue: VecDeque::new(),
+            retry_tasks: VecDeque::new(),
+            peers: Vec::new(),
+            in_flight: HashMap::new(),
+            refreshing_peers: false,
             downloader,
             network: network.clone(),
             rpc_client: NetworkRpcClient::new(network),
@@ -172,6 +252,7 @@ where
                 SyncTaskState::NotReady
             },
             download_address,
+            progress,
         });
         BlockSyncTaskRef { address }
     }
@@ -182,6 +263,9 @@ where
             if self.next.1 >= self.target_number
                 && self.headers.is_empty()
                 && self.body_task.is_empty()
+                && self.connect_queue.is_empty()
+                && self.retry_tasks.is_empty()
+                && self.in_flight.values().all(|count| *count == 0)
             {
                 info!("Block sync task finish.");
                 self.state = SyncTaskState::Finish;
@@ -191,94 +275,192 @@ where
         self.state.is_finish()
     }
 
-    fn sync_blocks(&mut self, address: Addr<BlockSyncTaskActor<C>>) {
-        let sync_header_flag =
-            !(self.body_task.len() > MAX_LEN || self.next.1 >= self.target_number);
-
-        let body_hashs = self.body_task.take_hashs();
-
-        let next = self.next.0;
+    fn refresh_peers(&mut self, address: Addr<BlockSyncTaskActor<C>>) {
+        if self.refreshing_peers {
+            return;
+        }
+        self.refreshing_peers = true;
         let network = self.network.clone();
-        let rpc_client = self.rpc_client.clone();
+        let ancestor_number = self.ancestor_number;
         Arbiter::spawn(async move {
-            // sync header
-            if sync_header_flag {
-                let get_headers_req = get_headers_msg_for_common(next);
-                let hash_timer = SYNC_METRICS
-                    .sync_done_time
-                    .with_label_values(&[LABEL_HASH])
-                    .start_timer();
-                let event = match get_headers(&network, &rpc_client, get_headers_req).await {
-                    Ok(headers) => SyncDataEvent::new_header_event(headers),
-                    Err(e) => {
-                        error!("Sync headers err: {:?}", e);
-                        Delay::new(Duration::from_secs(1)).await;
-                        SyncDataEvent::new_header_event(Vec::new())
-                    }
-                };
-
-                address.clone().do_send(event);
-                hash_timer.observe_duration();
-            }
-
-            // sync body
-            if let Some(hashs) = body_hashs {
-                let block_body_timer = SYNC_METRICS
-                    .sync_done_time
-                    .with_label_values(&[LABEL_BLOCK_BODY])
-                    .start_timer();
-                let event = match get_body_by_hash(&rpc_client, &network, hashs.clone()).await {
-                    Ok(bodies) => SyncDataEvent::new_body_event(bodies, Vec::new()),
-                    Err(e) => {
-                        error!("Sync bodies err: {:?}", e);
-                        Delay::new(Duration::from_secs(1)).await;
-                        SyncDataEvent::new_body_event(Vec::new(), hashs)
-                    }
-                };
-
-                address.clone().do_send(event);
-                block_body_timer.observe_duration();
-            }
-
-            if let Err(err) = address.try_send(NextTimeEvent {}) {
-                error!("Send NextTimeEvent failed when sync : {:?}", err);
+            let self_peer_id: PeerId = network.identify().clone().into();
+            let peers = match network.best_peer_set().await {
+                Ok(peers) => peers
+                    .into_iter()
+                    .filter(|peer| {
+                        peer.get_peer_id() != self_peer_id
+                            && peer.get_block_number() > ancestor_number
+                    })
+                    .take(MAX_SYNC_PEERS)
+                    .map(|peer| peer.get_peer_id())
+                    .collect(),
+                Err(e) => {
+                    error!("Get best peer set err: {:?}", e);
+                    Vec::new()
+                }
             };
+            if peers.is_empty() {
+                Delay::new(Duration::from_secs(1)).await;
+            }
+            address.do_send(PeersEvent { peers });
         });
     }
 
-    fn _sync_headers(&mut self, address: Addr<BlockSyncTaskActor<C>>) {
-        if self.body_task.len() > MAX_LEN || self.next.1 >= self.target_number {
+    fn next_task(&mut self) -> Option<ChunkTask> {
+        if let Some(task) = self.retry_tasks.pop_front() {
+            return Some(task);
+        }
+        if let Some(hashs) = self.body_task.take_hashs() {
+            return Some(ChunkTask::new(SyncChunk::Body(hashs)));
+        }
+        if self.headers.len() < MAX_LEN
+            && self.header_chunks.len() < MAX_HEADER_CHUNKS
+            && self.next_header_number <= self.target_number
+        {
+            let start = self.next_header_number;
+            let end = std::cmp::min(start + HEADER_CHUNK_SIZE - 1, self.target_number);
+            self.next_header_number = end + 1;
+            return Some(ChunkTask::new(SyncChunk::Header(start, end)));
+        }
+        None
+    }
+
+    /// Dispatch the chunks to peers until all peers are busy.
+    fn sync_blocks(&mut self, address: Addr<BlockSyncTaskActor<C>>) {
+        if self.peers.is_empty() {
+            self.refresh_peers(address);
             return;
         }
+        while let Some(task) = self.next_task() {
+            let peer_id = match choose_peer(&self.peers, &self.in_flight, &task.failed_peers) {
+                Some(peer_id) => peer_id,
+                None => {
+                    if self
+                        .peers
+                        .iter()
+                        .all(|peer| task.failed_peers.contains(peer))
+                    {
+                        // all peers failed, refresh peers and try again.
+                        self.peers.clear();
+                    }
+                    self.retry_tasks.push_front(task);
+                    break;
+                }
+            };
+            *self.in_flight.entry(peer_id.clone()).or_insert(0) += 1;
+            self.fetch_chunk(peer_id, task, address.clone());
+        }
+        if self.peers.is_empty() {
+            self.refresh_peers(address);
+        }
+    }
 
-        let next = self.next.0;
-        let network = self.network.clone();
+    fn fetch_chunk(&self, peer_id: PeerId, task: ChunkTask, address: Addr<BlockSyncTaskActor<C>>) {
         let rpc_client = self.rpc_client.clone();
         Arbiter::spawn(async move {
-            let get_headers_req = get_headers_msg_for_common(next);
-            let hash_timer = SYNC_METRICS
-                .sync_done_time
-                .with_label_values(&[LABEL_HASH])
-                .start_timer();
-            let event = match get_headers(&network, &rpc_client, get_headers_req).await {
-                Ok(headers) => SyncDataEvent::new_header_event(headers),
-                Err(e) => {
-                    error!("Sync headers err: {:?}", e);
-                    Delay::new(Duration::from_secs(1)).await;
-                    SyncDataEvent::new_header_event(Vec::new())
+            let event = match &task.chunk {
+                SyncChunk::Header(start, end) => {
+                    let hash_timer = SYNC_METRICS
+                        .sync_done_time
+                        .with_label_values(&[LABEL_HASH])
+                        .start_timer();
+                    let req = GetBlockHeadersByNumber::new(*end, 1, (*end - *start + 1) as usize);
+                    let result = get_headers_by_number(&rpc_client, peer_id.clone(), req)
+                        .await
+                        .and_then(|mut headers| {
+                            headers.reverse();
+                            verify_header_chunk(&headers, *start, *end)?;
+                            Ok(headers)
+                        });
+                    hash_timer.observe_duration();
+                    match result {
+                        Ok(headers) => SyncDataEvent {
+                            peer_id,
+                            task,
+                            headers,
+                            bodies: Vec::new(),
+                            success: true,
+                        },
+                        Err(e) => {
+                            error!("Sync headers from peer {} err: {:?}", peer_id, e);
+                            SyncDataEvent {
+                                peer_id,
+                                task,
+                                headers: Vec::new(),
+                                bodies: Vec::new(),
+                                success: false,
+                            }
+                        }
+                    }
+                }
+                SyncChunk::Body(hashs) => {
+                    let block_body_timer = SYNC_METRICS
+                        .sync_done_time
+                        .with_label_values(&[LABEL_BLOCK_BODY])
+                        .start_timer();
+                    let result =
+                        get_body_by_hash_with_peer(&rpc_client, peer_id.clone(), hashs.clone())
+                            .await
+                            .and_then(|bodies| {
+                                let body_hashs: HashSet<HashValue> =
+                                    bodies.iter().map(|body| body.hash).collect();
+                                ensure!(
+                                    bodies.len() == hashs.len()
+                                        && hashs.iter().all(|hash| body_hashs.contains(hash)),
+                                    "block bodies miss match."
+                                );
+                                Ok(bodies)
+                            });
+                    block_body_timer.observe_duration();
+                    match result {
+                        Ok(bodies) => SyncDataEvent {
+                            peer_id,
+                            task,
+                            headers: Vec::new(),
+                            bodies,
+                            success: true,
+                        },
+                        Err(e) => {
+                            error!("Sync bodies from peer {} err: {:?}", peer_id, e);
+                            SyncDataEvent {
+                                peer_id,
+                                task,
+                                headers: Vec::new(),
+                                bodies: Vec::new(),
+                                success: false,
+                            }
+                        }
+                    }
                 }
             };
-
-            address.clone().do_send(event);
-            hash_timer.observe_duration();
+            address.do_send(event);
         });
     }
 
-    fn handle_headers(&mut self, headers: Vec<BlockHeader>) {
-        if !headers.is_empty() {
+    /// Link the received header chunks to the chain of ancestor.
+    fn handle_headers(
+        &mut self,
+        peer_id: PeerId,
+        task: ChunkTask,
+        start: BlockNumber,
+        headers: Vec<BlockHeader>,
+    ) {
+        self.header_chunks.insert(start, (peer_id, task, headers));
+        while let Some((peer_id, task, headers)) = self.header_chunks.remove(&(self.next.1 + 1)) {
+            let first = headers.first().expect("header chunk is empty.");
+            if first.parent_hash() != self.next.0 {
+                // the peer may be on another fork, request the chunk again from other peers.
+                debug!(
+                    "Header chunk {:?} from peer {} does not link to {:?}.",
+                    task.chunk, peer_id, self.next
+                );
+                self.handle_failed(peer_id, task);
+                break;
+            }
             let len = headers.len();
             for block_header in headers {
                 self.body_task.push_back(block_header.id());
+                self.connect_queue.push_back(block_header.id());
                 self.next = (block_header.id(), block_header.number());
                 self.headers.insert(block_header.id(), block_header);
             }
@@ -289,67 +471,60 @@ where
         }
     }
 
-    fn _sync_bodies(&mut self, address: Addr<BlockSyncTaskActor<C>>) {
-        if let Some(hashs) = self.body_task.take_hashs() {
-            let network = self.network.clone();
-            let rpc_client = self.rpc_client.clone();
-            Arbiter::spawn(async move {
-                let block_body_timer = SYNC_METRICS
-                    .sync_done_time
-                    .with_label_values(&[LABEL_BLOCK_BODY])
-                    .start_timer();
-                let event = match get_body_by_hash(&rpc_client, &network, hashs.clone()).await {
-                    Ok(bodies) => SyncDataEvent::new_body_event(bodies, Vec::new()),
-                    Err(e) => {
-                        error!("Sync bodies err: {:?}", e);
-                        Delay::new(Duration::from_secs(1)).await;
-                        SyncDataEvent::new_body_event(Vec::new(), hashs)
-                    }
-                };
-
-                address.do_send(event);
-                block_body_timer.observe_duration();
-            });
+    fn handle_bodies(&mut self, bodies: Vec<BlockBody>) -> Option<Box<impl Future<Output = ()>>> {
+        let len = bodies.len();
+        for block_body in bodies {
+            self.bodies.insert(block_body.hash, block_body);
         }
-    }
-
-    fn handle_bodies(
-        &mut self,
-        bodies: Vec<BlockBody>,
-        hashs: Vec<HashValue>,
-    ) -> Option<Box<impl Future<Output = ()>>> {
-        if !bodies.is_empty() {
-            let len = bodies.len();
-            let mut blocks: Vec<Block> = Vec::new();
-            for block_body in bodies {
-                let block_header = self.headers.remove(&block_body.hash);
+        SYNC_METRICS
+            .sync_total_count
+            .with_label_values(&[LABEL_BLOCK_BODY])
+            .inc_by(len as i64);
+
+        // connect blocks in order.
+        let mut blocks: Vec<Block> = Vec::new();
+        while let Some(block_id) = self.connect_queue.front() {
+            if let Some(block_body) = self.bodies.remove(block_id) {
+                let block_header = self
+                    .headers
+                    .remove(block_id)
+                    .expect("block_header is none.");
                 let body = RealBlockBody::new(block_body.transactions, block_body.uncles);
-                let block =
-                    Block::new_with_body(block_header.expect("block_header is none."), body);
-                blocks.push(block);
+                blocks.push(Block::new_with_body(block_header, body));
+                self.connect_queue.pop_front();
+            } else {
+                break;
             }
-
-            SYNC_METRICS
-                .sync_total_count
-                .with_label_values(&[LABEL_BLOCK_BODY])
-                .inc_by(len as i64);
-
-            Some(self.connect_blocks(blocks))
-        } else {
-            self.body_task.push_hashs(hashs);
+        }
+        if blocks.is_empty() {
             None
+        } else {
+            Some(self.connect_blocks(blocks))
+        }
+    }
+
+    fn handle_failed(&mut self, peer_id: PeerId, mut task: ChunkTask) {
+        task.failed_peers.insert(peer_id);
+        if task.failed_peers.len() > MAX_RETRY {
+            error!("Block sync chunk {:?} failed too many times.", task.chunk);
+            self.state = SyncTaskState::Failed;
+            return;
         }
+        self.retry_tasks.push_back(task);
     }
 
     fn connect_blocks(&self, blocks: Vec<Block>) -> Box<impl Future<Output = ()>> {
         let downloader = self.downloader.clone();
+        let progress = self.progress.clone();
         let fut = async move {
             let mut blocks = blocks;
             blocks.reverse();
             loop {
                 let block = blocks.pop();
                 if let Some(b) = block {
+                    let number = b.header().number();
                     downloader.connect_block_and_child(b).await;
+                    progress.update_current(number);
                 } else {
                     break;
                 }
@@ -359,8 +534,6 @@ where
     }
 
     fn block_sync(&mut self, address: Addr<BlockSyncTaskActor<C>>) {
-        // self.sync_headers(address.clone());
-        // self.sync_bodies(address);
         self.sync_blocks(address);
     }
 
@@ -385,6 +558,26 @@ where
     }
 }
 
+impl<C> Handler<PeersEvent> for BlockSyncTaskActor<C>
+where
+    C: Consensus + Sync + Send + 'static + Clone,
+{
+    type Result = ();
+
+    fn handle(&mut self, event: PeersEvent, ctx: &mut Self::Context) -> Self::Result {
+        self.refreshing_peers = false;
+        debug!("Block sync peers: {:?}", event.peers);
+        self.progress.update_peers(event.peers.clone());
+        self.peers = event.peers;
+        for task in self.retry_tasks.iter_mut() {
+            task.failed_peers.clear();
+        }
+        if let Err(err) = ctx.address().try_send(NextTimeEvent {}) {
+            error!("Send NextTimeEvent failed when refresh peers : {:?}", err);
+        };
+    }
+}
+
 impl<C> Handler<SyncDataEvent> for BlockSyncTaskActor<C>
 where
     C: Consensus + Sync + Send + 'static + Clone,
@@ -392,19 +585,29 @@ where
     type Result = ();
 
     fn handle(&mut self, data: SyncDataEvent, ctx: &mut Self::Context) -> Self::Result {
-        match data.data_type {
-            DataType::Header => {
-                self.handle_headers(data.headers);
-            }
-            DataType::Body => {
-                if let Some(fut) = self.handle_bodies(data.bodies, data.hashs) {
-                    (*fut)
-                        .into_actor(self)
-                        .then(|_result, act, _ctx| async {}.into_actor(act))
-                        .wait(ctx);
+        if let Some(count) = self.in_flight.get_mut(&data.peer_id) {
+            *count = count.saturating_sub(1);
+        }
+        if !data.success {
+            self.handle_failed(data.peer_id, data.task);
+        } else {
+            match data.task.chunk {
+                SyncChunk::Header(start, _) => {
+                    self.handle_headers(data.peer_id, data.task, start, data.headers);
+                }
+                SyncChunk::Body(_) => {
+                    if let Some(fut) = self.handle_bodies(data.bodies) {
+                        (*fut)
+                            .into_actor(self)
+                            .then(|_result, act, _ctx| async {}.into_actor(act))
+                            .wait(ctx);
+                    }
                 }
             }
         }
+        if let Err(err) = ctx.address().try_send(NextTimeEvent {}) {
+            error!("Send NextTimeEvent failed when sync : {:?}", err);
+        };
     }
 }
 
@@ -415,6 +618,11 @@ where
     type Result = Result<()>;
 
     fn handle(&mut self, _event: NextTimeEvent, ctx: &mut Self::Context) -> Self::Result {
+        if self.state.is_failed() {
+            self.download_address.do_send(SyncTaskType::BLOCK);
+            ctx.stop();
+            return Ok(());
+        }
         let finish = self.do_finish();
         if !finish {
             self.block_sync(ctx.address());
@@ -477,3 +685,87 @@ where
 }
 
 impl<C> SyncTaskAction for BlockSyncTaskRef<C> where C: Consensus + Sync + Send + 'static + Clone {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{account_address::AccountAddress, U256};
+
+    fn gen_headers(parent_hash: HashValue, start: BlockNumber, len: u64) -> Vec<BlockHeader> {
+        let mut parent_hash = parent_hash;
+        (start..start + len)
+            .map(|number| {
+                let header = BlockHeader::new(
+                    parent_hash,
+                    HashValue::zero(),
+                    number,
+                    number,
+                    AccountAddress::random(),
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    0,
+                    0,
+                    U256::zero(),
+                    0,
+                    None,
+                );
+                parent_hash = header.id();
+                header
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_header_chunk() {
+        let headers = gen_headers(HashValue::random(), 10, 5);
+        assert!(verify_header_chunk(&headers, 10, 14).is_ok());
+        // len miss match
+        assert!(verify_header_chunk(&headers, 10, 15).is_err());
+        assert!(verify_header_chunk(&headers[..4], 10, 14).is_err());
+        // number miss match
+        assert!(verify_header_chunk(&headers, 11, 15).is_err());
+        // parent hash miss match
+        let mut unlinked = headers.clone();
+        unlinked[2] = gen_headers(HashValue::random(), 12, 1).pop().unwrap();
+        assert!(verify_header_chunk(&unlinked, 10, 14).is_err());
+        // not in order
+        let mut reversed = headers;
+        reversed.reverse();
+        assert!(verify_header_chunk(&reversed, 10, 14).is_err());
+    }
+
+    #[test]
+    fn test_choose_peer() {
+        let peers: Vec<PeerId> = (0..3).map(|_| PeerId::random()).collect();
+        let mut in_flight = HashMap::new();
+        let mut failed_peers = HashSet::new();
+        assert_eq!(
+            None,
+            choose_peer(&[], &in_flight, &failed_peers),
+            "no peer to choose"
+        );
+
+        // the peer with the least requests in flight is chosen.
+        in_flight.insert(peers[0].clone(), 1);
+        in_flight.insert(peers[1].clone(), 0);
+        in_flight.insert(peers[2].clone(), 1);
+        assert_eq!(
+            Some(peers[1].clone()),
+            choose_peer(&peers, &in_flight, &failed_peers)
+        );
+
+        // the failed peers are skipped.
+        failed_peers.insert(peers[1].clone());
+        let peer = choose_peer(&peers, &in_flight, &failed_peers).unwrap();
+        assert_ne!(peers[1], peer);
+
+        // the busy peers are skipped.
+        in_flight.insert(peers[0].clone(), MAX_IN_FLIGHT_PER_PEER);
+        assert_eq!(
+            Some(peers[2].clone()),
+            choose_peer(&peers, &in_flight, &failed_peers)
+        );
+        in_flight.insert(peers[2].clone(), MAX_IN_FLIGHT_PER_PEER);
+        assert_eq!(None, choose_peer(&peers, &in_flight, &failed_peers));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::block_connector::{BlockConnector, PivotBlock};
+use crate::block_connector::{BlockConnector, ConnectOutcome, PivotBlock};
 /// Sync message which outbound
 use crate::block_sync::BlockSyncTaskActor;
 use crate::helper::{
@@ -18,10 +18,10 @@ use futures::channel::mpsc;
 use futures_timer::Delay;
 use logger::prelude::*;
 use network::NetworkAsyncService;
-use network_api::NetworkService;
+use network_api::{reputation, NetworkService};
 use network_rpc::{gen_client::NetworkRpcClient, BlockBody, GetBlockHeaders};
 use starcoin_storage::Store;
-use starcoin_sync_api::SyncNotify;
+use starcoin_sync_api::{SyncNotify, SyncProgress};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -58,6 +58,7 @@ where
     sync_task: SyncTask,
     need_sync_state: Arc<AtomicBool>,
     node_config: Arc<NodeConfig>,
+    sync_progress: SyncProgress,
 }
 
 impl<C> DownloadActor<C>
@@ -71,6 +72,7 @@ where
         network: NetworkAsyncService,
         bus: Addr<BusActor>,
         storage: Arc<dyn Store>,
+        sync_progress: SyncProgress,
     ) -> Result<Addr<DownloadActor<C>>> {
         let download_actor = DownloadActor::create(move |ctx| {
             let (sync_event_sender, sync_event_receiver) = mpsc::channel(100);
@@ -95,6 +97,7 @@ where
                     },
                 )),
                 node_config,
+                sync_progress,
             }
         });
 
@@ -144,6 +147,7 @@ where
             self.need_sync_state.store(false, Ordering::Relaxed);
             self.syncing.store(false, Ordering::Relaxed);
             self.downloader.set_pivot(None);
+            self.sync_progress.finish();
         }
         Ok(())
     }
@@ -184,6 +188,7 @@ where
                         sync_task,
                         self.syncing.clone(),
                         ctx.address(),
+                        self.sync_progress.clone(),
                     );
                 } else {
                     Self::sync_block_from_best_peer(
@@ -193,6 +198,7 @@ where
                         sync_task,
                         self.syncing.clone(),
                         ctx.address(),
+                        self.sync_progress.clone(),
                     );
                 }
             }
@@ -214,7 +220,7 @@ where
                 self.sync_task.activate_tasks();
                 debug!("new peer: {:?}", peer_id);
             }
-            SyncNotify::NewHeadBlock(_peer_id, block) => self.do_block_and_child(*block),
+            SyncNotify::NewHeadBlock(peer_id, block) => self.do_block_and_child(peer_id, *block),
             SyncNotify::ClosePeerMsg(peer_id) => {
                 debug!("close peer: {:?}", peer_id);
             }
@@ -236,6 +242,7 @@ where
         sync_task: SyncTask,
         syncing: Arc<AtomicBool>,
         download_address: Addr<DownloadActor<C>>,
+        sync_progress: SyncProgress,
     ) {
         Arbiter::spawn(async move {
             SYNC_METRICS
@@ -252,6 +259,7 @@ where
                 storage.clone(),
                 sync_task.clone(),
                 download_address.clone(),
+                sync_progress.clone(),
             )
             .await
             {
@@ -268,6 +276,7 @@ where
                         sync_task,
                         syncing.clone(),
                         download_address,
+                        sync_progress,
                     );
                 }
                 Ok(flag) => {
@@ -293,6 +302,7 @@ where
         storage: Arc<dyn Store>,
         sync_task: SyncTask,
         download_address: Addr<DownloadActor<C>>,
+        sync_progress: SyncProgress,
     ) -> Result<bool> {
         if let Some(best_peer) = network.best_peer().await? {
             //1. ancestor
@@ -333,13 +343,25 @@ where
                 }
 
                 // 3. sync task
-                let (root, block_info) = Downloader::<C>::get_pivot(
+                let (root, block_info) = match Downloader::<C>::get_saved_pivot(
                     &rpc_client,
                     best_peer.get_peer_id(),
-                    (latest_block_id, latest_number),
-                    min_behind as usize,
+                    storage.as_ref(),
+                    ancestor,
                 )
-                .await?;
+                .await
+                {
+                    Some(pivot) => pivot,
+                    None => {
+                        Downloader::<C>::get_pivot(
+                            &rpc_client,
+                            best_peer.get_peer_id(),
+                            (latest_block_id, latest_number),
+                            min_behind as usize,
+                        )
+                        .await?
+                    }
+                };
                 let block_sync_task = BlockSyncTaskActor::launch(
                     &ancestor_header,
                     latest_number,
@@ -347,6 +369,7 @@ where
                     network.clone(),
                     false,
                     download_address.clone(),
+                    sync_progress,
                 );
                 sync_task.push_task(SyncTaskType::BLOCK, Box::new(block_sync_task.clone()));
 
@@ -395,6 +418,7 @@ where
         sync_task: SyncTask,
         syncing: Arc<AtomicBool>,
         download_address: Addr<DownloadActor<C>>,
+        sync_progress: SyncProgress,
     ) {
         if !syncing.load(Ordering::Relaxed) {
             syncing.store(true, Ordering::Relaxed);
@@ -409,6 +433,7 @@ where
                     network,
                     sync_task,
                     download_address,
+                    sync_progress,
                 )
                 .await
                 {
@@ -432,6 +457,7 @@ where
         network: NetworkAsyncService,
         sync_task: SyncTask,
         download_address: Addr<DownloadActor<C>>,
+        sync_progress: SyncProgress,
     ) -> Result<bool> {
         if let Some(best_peer) = network.best_peer().await? {
             if let Some(header) = downloader.chain_reader.clone().master_head_header().await? {
@@ -458,6 +484,7 @@ where
                                 network.clone(),
                                 true,
                                 download_address,
+                                sync_progress,
                             );
                             sync_task.push_task(SyncTaskType::BLOCK, Box::new(block_sync_task));
                             Ok(false)
@@ -478,10 +505,13 @@ where
         }
     }
 
-    pub fn do_block_and_child(&self, block: Block) {
+    pub fn do_block_and_child(&self, peer_id: PeerId, block: Block) {
         let downloader = self.downloader.clone();
+        let network = self.network.clone();
         Arbiter::spawn(async move {
-            downloader.connect_block_and_child(block).await;
+            if downloader.connect_block_and_child(block).await == ConnectOutcome::Invalid {
+                network.report_peer(peer_id.into(), reputation::INVALID_BLOCK);
+            }
         });
     }
 }
@@ -639,6 +669,53 @@ where
         }
     }
 
+    /// Get the pivot of the unfinished state sync, so the state sync can resume from the saved
+    /// progress. Return None if there is no saved progress or the pivot is not on the master chain
+    /// of the peer any more.
+    async fn get_saved_pivot(
+        rpc_client: &NetworkRpcClient<NetworkAsyncService>,
+        peer_id: PeerId,
+        storage: &dyn Store,
+        ancestor: BlockNumber,
+    ) -> Option<(BlockHeader, BlockInfo)> {
+        let pivot_id = match storage.get_state_sync_progress() {
+            Ok(Some(progress)) => progress.pivot_id,
+            Ok(None) => return None,
+            Err(e) => {
+                warn!("load state sync progress failed : {:?}", e);
+                return None;
+            }
+        };
+        let get_headers_req = GetBlockHeaders::new(pivot_id, 0, true, 1);
+        let pivot = match get_headers_with_peer(rpc_client, peer_id.clone(), get_headers_req).await
+        {
+            Ok(mut headers) => headers.pop()?,
+            Err(e) => {
+                debug!("get saved pivot {:?} failed : {:?}", pivot_id, e);
+                return None;
+            }
+        };
+        if pivot.id() != pivot_id || pivot.number() <= ancestor {
+            info!(
+                "saved pivot {:?} is not available, sync state from new pivot.",
+                pivot_id
+            );
+            return None;
+        }
+        match get_info_by_hash(rpc_client, peer_id, vec![pivot.parent_hash()]).await {
+            Ok(mut infos) => match infos.pop() {
+                Some(block_info) if Self::verify_pivot(&pivot, &block_info) => {
+                    Some((pivot, block_info))
+                }
+                _ => None,
+            },
+            Err(e) => {
+                debug!("get saved pivot info failed : {:?}", e);
+                None
+            }
+        }
+    }
+
     pub async fn do_blocks(&self, headers: Vec<BlockHeader>, bodies: Vec<BlockBody>) {
         assert_eq!(headers.len(), bodies.len());
         for i in 0..headers.len() {
@@ -651,8 +728,8 @@ where
         }
     }
 
-    pub async fn connect_block_and_child(&self, block: Block) {
-        self.block_connector.do_block_and_child(block).await;
+    pub async fn connect_block_and_child(&self, block: Block) -> ConnectOutcome {
+        self.block_connector.do_block_and_child(block).await
     }
 
     fn set_pivot(&self, pivot: Option<PivotBlock<C>>) {
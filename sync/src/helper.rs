@@ -3,8 +3,9 @@ use crypto::hash::HashValue;
 use network::NetworkAsyncService;
 use network_api::NetworkService;
 use network_rpc::{
-    gen_client::NetworkRpcClient, BlockBody, GetAccumulatorNodeByNodeHash, GetBlockHeaders,
-    GetBlockHeadersByNumber, GetTxns, TransactionsData,
+    gen_client::NetworkRpcClient, BlockBody, BlockTxns, GetAccumulatorNodeByNodeHash,
+    GetAccumulatorNodes, GetBlockHeaders, GetBlockHeadersByNumber, GetBlockTxns, GetStateNodes,
+    GetTxnHashes, GetTxns, TransactionsData,
 };
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorNode;
@@ -19,24 +20,32 @@ use types::{
 const HEAD_CT: usize = 10;
 // TODO: those rpc info is not used in network layout. consider remove them.
 const GET_TXNS_STR: &str = "GetTxns";
+const GET_TXN_HASHES_STR: &str = "GetTxnHashes";
 const GET_TXN_INFOS_STR: &str = "GetTxnInfos";
+const GET_BLOCK_TXNS_STR: &str = "GetBlockTxns";
 const GET_BLOCK_HEADERS_BY_NUM_STR: &str = "GetBlockHeadersByNumber";
 const GET_BLOCK_HEADERS_STR: &str = "GetBlockHeaders";
 const GET_BLOCK_INFOS_STR: &str = "GetBlockInfos";
 const GET_BLOCK_BODIES_STR: &str = "GetBlockBodies";
 const GET_STATE_NODE_BY_NODE_HASH_STR: &str = "GetStateNodeByNodeHash";
 const GET_ACCUMULATOR_NODE_BY_NODE_HASH_STR: &str = "GetAccumulatorNodeByNodeHash";
+const GET_STATE_NODES_STR: &str = "GetStateNodes";
+const GET_ACCUMULATOR_NODES_STR: &str = "GetAccumulatorNodes";
 
 pub fn sync_rpc_info() -> (&'static [u8], RpcInfo) {
     let mut paths = Vec::new();
     paths.push(GET_TXNS_STR.to_string());
+    paths.push(GET_TXN_HASHES_STR.to_string());
     paths.push(GET_TXN_INFOS_STR.to_string());
+    paths.push(GET_BLOCK_TXNS_STR.to_string());
     paths.push(GET_BLOCK_HEADERS_BY_NUM_STR.to_string());
     paths.push(GET_BLOCK_HEADERS_STR.to_string());
     paths.push(GET_BLOCK_INFOS_STR.to_string());
     paths.push(GET_BLOCK_BODIES_STR.to_string());
     paths.push(GET_STATE_NODE_BY_NODE_HASH_STR.to_string());
     paths.push(GET_ACCUMULATOR_NODE_BY_NODE_HASH_STR.to_string());
+    paths.push(GET_STATE_NODES_STR.to_string());
+    paths.push(GET_ACCUMULATOR_NODES_STR.to_string());
     let rpc_info = RpcInfo::new(paths);
     (CHAIN_PROTOCOL_NAME, rpc_info)
 }
@@ -49,6 +58,22 @@ pub async fn get_txns(
     client.get_txns(peer_id, req).await
 }
 
+pub async fn get_txn_hashes(
+    client: &NetworkRpcClient<NetworkAsyncService>,
+    peer_id: PeerId,
+    req: GetTxnHashes,
+) -> Result<Vec<HashValue>> {
+    client.get_txn_hashes(peer_id, req).await
+}
+
+pub async fn get_block_txns(
+    client: &NetworkRpcClient<NetworkAsyncService>,
+    peer_id: PeerId,
+    req: GetBlockTxns,
+) -> Result<BlockTxns> {
+    client.get_block_txns(peer_id, req).await
+}
+
 pub async fn get_txn_infos(
     client: &NetworkRpcClient<NetworkAsyncService>,
     peer_id: PeerId,
@@ -99,6 +124,14 @@ pub async fn get_body_by_hash(
     }
 }
 
+pub async fn get_body_by_hash_with_peer(
+    client: &NetworkRpcClient<NetworkAsyncService>,
+    peer_id: PeerId,
+    hashes: Vec<HashValue>,
+) -> Result<Vec<BlockBody>> {
+    client.get_body_by_hash(peer_id, hashes).await
+}
+
 pub async fn get_info_by_hash(
     client: &NetworkRpcClient<NetworkAsyncService>,
     peer_id: PeerId,
@@ -132,6 +165,38 @@ pub async fn get_accumulator_node_by_node_hash(
         .await
 }
 
+pub async fn get_state_nodes_by_node_hash(
+    client: &NetworkRpcClient<NetworkAsyncService>,
+    peer_id: PeerId,
+    node_keys: Vec<HashValue>,
+) -> Result<Vec<Option<StateNode>>> {
+    client
+        .get_state_nodes_by_node_hash(
+            peer_id,
+            GetStateNodes {
+                node_hashes: node_keys,
+            },
+        )
+        .await
+}
+
+pub async fn get_accumulator_nodes_by_node_hash(
+    client: &NetworkRpcClient<NetworkAsyncService>,
+    peer_id: PeerId,
+    node_keys: Vec<HashValue>,
+    accumulator_type: AccumulatorStoreType,
+) -> Result<Vec<Option<AccumulatorNode>>> {
+    client
+        .get_accumulator_nodes_by_node_hash(
+            peer_id,
+            GetAccumulatorNodes {
+                node_hashes: node_keys,
+                accumulator_storage_type: accumulator_type,
+            },
+        )
+        .await
+}
+
 /// for common
 pub fn get_headers_msg_for_common(block_id: HashValue) -> GetBlockHeaders {
     GetBlockHeaders::new(block_id, 1, false, HEAD_CT)
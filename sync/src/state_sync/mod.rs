@@ -1,7 +1,7 @@
 use crate::block_sync::BlockSyncTaskRef;
 use crate::download::DownloadActor;
 use crate::helper::{
-    get_accumulator_node_by_node_hash, get_state_node_by_node_hash, get_txn_infos,
+    get_accumulator_nodes_by_node_hash, get_state_nodes_by_node_hash, get_txn_infos,
 };
 use crate::sync_metrics::{LABEL_ACCUMULATOR, LABEL_STATE, LABEL_TXN_INFO, SYNC_METRICS};
 use crate::sync_task::{
@@ -17,27 +17,43 @@ use forkable_jellyfish_merkle::SPARSE_MERKLE_PLACEHOLDER_HASH;
 use futures::executor::block_on;
 use logger::prelude::*;
 use network::NetworkAsyncService;
-use network_api::NetworkService;
-use network_rpc::gen_client::NetworkRpcClient;
+use network_api::{reputation, NetworkService};
+use network_rpc::{gen_client::NetworkRpcClient, MAX_BATCH_NODE_SIZE};
 use starcoin_accumulator::node::{AccumulatorStoreType, ACCUMULATOR_PLACEHOLDER_HASH};
 use starcoin_accumulator::AccumulatorNode;
 use starcoin_state_tree::StateNode;
 use starcoin_storage::Store;
 use std::collections::{HashMap, VecDeque};
 use std::convert::TryFrom;
+use std::hash::Hash;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use traits::Consensus;
 use types::{
     account_state::AccountState,
     peer_info::{PeerId, PeerInfo},
+    startup_info::StateSyncProgress,
     transaction::TransactionInfo,
 };
 
+/// Persist the state sync progress after every `PERSIST_PROGRESS_INTERVAL` handled nodes.
+const PERSIST_PROGRESS_INTERVAL: u64 = 256;
+/// Max count of peers which state nodes are synced from in parallel.
+const MAX_STATE_SYNC_PEERS: usize = 8;
+/// Max count of nodes found in db which are visited in one message, the rest of the walk is
+/// continued by `ContinueSyncEvent`, so that the actor is not blocked by a long walk.
+const MAX_DB_NODES_PER_ROUND: usize = 4096;
+/// A node is retried at most `MAX_NODE_RETRIES` times if no peer provides it, then the task fails.
+const MAX_NODE_RETRIES: usize = 16;
+
 #[derive(Default, Debug, Message)]
 #[rtype(result = "Result<()>")]
 struct TxnInfoEvent(Option<HashValue>);
 
+#[derive(Debug, Message)]
+#[rtype(result = "Result<()>")]
+struct ContinueSyncEvent(TaskType);
+
 struct Roots {
     state: HashValue,
     block_accumulator: HashValue,
@@ -66,9 +82,10 @@ impl Roots {
     }
 }
 
-async fn sync_accumulator_node<C>(
-    node_key: HashValue,
+async fn sync_accumulator_nodes<C>(
+    node_keys: Vec<HashValue>,
     peer_id: PeerId,
+    network_service: NetworkAsyncService,
     rpc_client: NetworkRpcClient<NetworkAsyncService>,
     address: Addr<StateSyncTaskActor<C>>,
 ) where
@@ -78,32 +95,41 @@ async fn sync_accumulator_node<C>(
         .sync_done_time
         .with_label_values(&[LABEL_ACCUMULATOR])
         .start_timer();
-    let accumulator_node = match get_accumulator_node_by_node_hash(
+    let accumulator_nodes = match get_accumulator_nodes_by_node_hash(
         &rpc_client,
         peer_id.clone(),
-        node_key,
+        node_keys.clone(),
         AccumulatorStoreType::Block,
     )
     .await
     {
-        Ok(accumulator_node) => {
-            if node_key == accumulator_node.hash() {
+        Ok(accumulator_nodes) => {
+            let verified = accumulator_nodes.len() <= node_keys.len()
+                && accumulator_nodes.iter().zip(node_keys.iter()).all(
+                    |(node, node_key)| match node {
+                        Some(node) => node.hash() == *node_key,
+                        None => true,
+                    },
+                );
+            if verified {
                 SYNC_METRICS
                     .sync_succ_count
                     .with_label_values(&[LABEL_ACCUMULATOR])
-                    .inc();
-                Some(accumulator_node)
+                    .inc_by(
+                        accumulator_nodes
+                            .iter()
+                            .filter(|node| node.is_some())
+                            .count() as i64,
+                    );
+                accumulator_nodes
             } else {
                 SYNC_METRICS
                     .sync_verify_fail_count
                     .with_label_values(&[LABEL_ACCUMULATOR])
                     .inc();
-                warn!(
-                    "accumulator node hash miss match {} :{:?}",
-                    node_key,
-                    accumulator_node.hash()
-                );
-                None
+                warn!("accumulator nodes from peer {:?} miss match.", peer_id);
+                network_service.report_peer(peer_id.clone().into(), reputation::INVALID_PROOF);
+                Vec::new()
             }
         }
         Err(e) => {
@@ -112,23 +138,24 @@ async fn sync_accumulator_node<C>(
                 .with_label_values(&[LABEL_ACCUMULATOR])
                 .inc();
             debug!("{:?}", e);
-            None
+            Vec::new()
         }
     };
     accumulator_timer.observe_duration();
 
     if let Err(err) = address.try_send(StateSyncTaskEvent::new_accumulator(
         peer_id,
-        node_key,
-        accumulator_node,
+        node_keys,
+        accumulator_nodes,
     )) {
         error!("Send accumulator StateSyncTaskEvent failed : {:?}", err);
     };
 }
 
-async fn sync_state_node<C>(
-    node_key: HashValue,
+async fn sync_state_nodes<C>(
+    node_keys: Vec<HashValue>,
     peer_id: PeerId,
+    network_service: NetworkAsyncService,
     rpc_client: NetworkRpcClient<NetworkAsyncService>,
     address: Addr<StateSyncTaskActor<C>>,
 ) where
@@ -138,41 +165,48 @@ async fn sync_state_node<C>(
         .sync_done_time
         .with_label_values(&[LABEL_STATE])
         .start_timer();
-    let state_node = match get_state_node_by_node_hash(&rpc_client, peer_id.clone(), node_key).await
-    {
-        Ok(state_node) => {
-            if node_key == state_node.0.hash() {
-                SYNC_METRICS
-                    .sync_succ_count
-                    .with_label_values(&[LABEL_STATE])
-                    .inc();
-                Some(state_node)
-            } else {
+    let state_nodes =
+        match get_state_nodes_by_node_hash(&rpc_client, peer_id.clone(), node_keys.clone()).await {
+            Ok(state_nodes) => {
+                let verified = state_nodes.len() <= node_keys.len()
+                    && state_nodes.iter().zip(node_keys.iter()).all(
+                        |(node, node_key)| match node {
+                            Some(node) => node.0.hash() == *node_key,
+                            None => true,
+                        },
+                    );
+                if verified {
+                    SYNC_METRICS
+                        .sync_succ_count
+                        .with_label_values(&[LABEL_STATE])
+                        .inc_by(state_nodes.iter().filter(|node| node.is_some()).count() as i64);
+                    state_nodes
+                } else {
+                    SYNC_METRICS
+                        .sync_verify_fail_count
+                        .with_label_values(&[LABEL_STATE])
+                        .inc();
+                    warn!("state nodes from peer {:?} miss match.", peer_id);
+                    network_service.report_peer(peer_id.clone().into(), reputation::INVALID_PROOF);
+                    Vec::new()
+                }
+            }
+            Err(e) => {
                 SYNC_METRICS
-                    .sync_verify_fail_count
+                    .sync_fail_count
                     .with_label_values(&[LABEL_STATE])
                     .inc();
-                warn!(
-                    "state node hash miss match {} :{:?}",
-                    node_key,
-                    state_node.0.hash()
-                );
-                None
+                debug!("{:?}", e);
+                Vec::new()
             }
-        }
-        Err(e) => {
-            SYNC_METRICS
-                .sync_fail_count
-                .with_label_values(&[LABEL_STATE])
-                .inc();
-            debug!("{:?}", e);
-            None
-        }
-    };
+        };
     state_timer.observe_duration();
 
-    if let Err(err) = address.try_send(StateSyncTaskEvent::new_state(peer_id, node_key, state_node))
-    {
+    if let Err(err) = address.try_send(StateSyncTaskEvent::new_state(
+        peer_id,
+        node_keys,
+        state_nodes,
+    )) {
         error!("Send state StateSyncTaskEvent failed : {:?}", err);
     };
 }
@@ -271,20 +305,25 @@ enum TaskType {
 #[rtype(result = "Result<()>")]
 struct StateSyncTaskEvent {
     peer_id: PeerId,
-    key: HashValue,
-    state_node: Option<StateNode>,
-    accumulator_node: Option<AccumulatorNode>,
+    keys: Vec<HashValue>,
+    /// Nodes in the same order as `keys`, may be shorter than `keys`.
+    state_nodes: Vec<Option<StateNode>>,
+    accumulator_nodes: Vec<Option<AccumulatorNode>>,
     txn_infos: Option<Vec<TransactionInfo>>,
     task_type: TaskType,
 }
 
 impl StateSyncTaskEvent {
-    pub fn new_state(peer_id: PeerId, node_key: HashValue, state_node: Option<StateNode>) -> Self {
+    pub fn new_state(
+        peer_id: PeerId,
+        node_keys: Vec<HashValue>,
+        state_nodes: Vec<Option<StateNode>>,
+    ) -> Self {
         StateSyncTaskEvent {
             peer_id,
-            key: node_key,
-            state_node,
-            accumulator_node: None,
+            keys: node_keys,
+            state_nodes,
+            accumulator_nodes: Vec::new(),
             txn_infos: None,
             task_type: TaskType::STATE,
         }
@@ -292,14 +331,14 @@ impl StateSyncTaskEvent {
 
     pub fn new_accumulator(
         peer_id: PeerId,
-        node_key: HashValue,
-        accumulator_node: Option<AccumulatorNode>,
+        node_keys: Vec<HashValue>,
+        accumulator_nodes: Vec<Option<AccumulatorNode>>,
     ) -> Self {
         StateSyncTaskEvent {
             peer_id,
-            key: node_key,
-            state_node: None,
-            accumulator_node,
+            keys: node_keys,
+            state_nodes: Vec::new(),
+            accumulator_nodes,
             txn_infos: None,
             task_type: TaskType::BlockAccumulator,
         }
@@ -312,9 +351,9 @@ impl StateSyncTaskEvent {
     ) -> Self {
         StateSyncTaskEvent {
             peer_id,
-            key: block_id,
-            state_node: None,
-            accumulator_node: None,
+            keys: vec![block_id],
+            state_nodes: Vec::new(),
+            accumulator_nodes: Vec::new(),
             txn_infos,
             task_type: TaskType::TxnInfo,
         }
@@ -337,12 +376,16 @@ where
     state: SyncTaskState,
     download_address: Addr<DownloadActor<C>>,
     total_txn_info_task: AtomicU64,
+    unsaved_tasks: u64,
 }
 
 pub struct StateSyncTask<T> {
     wait_2_sync: VecDeque<T>,
-    syncing_nodes: HashMap<PeerId, T>,
+    /// Tasks requested from every peer, only one request is in flight for each peer.
+    syncing_nodes: HashMap<PeerId, Vec<T>>,
     done_tasks: AtomicU64,
+    /// Count of the failed requests of the tasks which are not provided by peers.
+    retries: HashMap<T, usize>,
 }
 
 impl<T> StateSyncTask<T> {
@@ -351,6 +394,7 @@ impl<T> StateSyncTask<T> {
             wait_2_sync: VecDeque::new(),
             syncing_nodes: HashMap::new(),
             done_tasks: AtomicU64::new(0),
+            retries: HashMap::new(),
         }
     }
 
@@ -382,21 +426,145 @@ impl<T> StateSyncTask<T> {
         self.wait_2_sync.clear();
         self.syncing_nodes.clear();
         self.done_tasks = AtomicU64::new(0);
+        self.retries.clear();
     }
 
-    pub fn insert(&mut self, peer_id: PeerId, value: T) -> Option<T> {
-        self.syncing_nodes.insert(peer_id, value)
+    pub fn insert(&mut self, peer_id: PeerId, values: Vec<T>) -> Option<Vec<T>> {
+        self.syncing_nodes.insert(peer_id, values)
     }
 
-    pub fn get(&self, peer_id: &PeerId) -> Option<&T> {
+    pub fn get(&self, peer_id: &PeerId) -> Option<&Vec<T>> {
         self.syncing_nodes.get(peer_id)
     }
 
-    pub fn remove(&mut self, peer_id: &PeerId) -> Option<T> {
+    pub fn remove(&mut self, peer_id: &PeerId) -> Option<Vec<T>> {
         self.syncing_nodes.remove(peer_id)
     }
 }
 
+impl<T: Clone + Hash + Eq> StateSyncTask<T> {
+    /// Put back the task which is not provided by the peer. Return false if the task has failed
+    /// `MAX_NODE_RETRIES` times, its retry count is reset then, so it can be retried after the
+    /// sync task is activated again.
+    fn retry(&mut self, value: T) -> bool {
+        let retries = self.retries.entry(value.clone()).or_insert(0);
+        *retries += 1;
+        let exceeded = *retries > MAX_NODE_RETRIES;
+        if exceeded {
+            self.retries.remove(&value);
+        }
+        self.push_back(value);
+        !exceeded
+    }
+
+    /// The task is done, forget its retry count.
+    fn done(&mut self, value: &T) {
+        self.retries.remove(value);
+        self.do_one_task();
+    }
+
+    /// All the tasks which are not done, include the syncing tasks.
+    fn frontier(&self) -> Vec<T> {
+        self.syncing_nodes
+            .values()
+            .flatten()
+            .chain(self.wait_2_sync.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Load the saved progress if it belongs to the same pivot.
+fn load_progress(storage: &dyn Store, roots: &Roots) -> Option<StateSyncProgress> {
+    match storage.get_state_sync_progress() {
+        Ok(Some(progress)) => {
+            if progress.pivot_id == *roots.pivot_id()
+                && progress.state_root == *roots.state_root()
+                && progress.block_accumulator_root == *roots.block_accumulator_root()
+            {
+                Some(progress)
+            } else {
+                info!(
+                    "discard state sync progress of pivot {:?}, new pivot is {:?}.",
+                    progress.pivot_id,
+                    roots.pivot_id()
+                );
+                None
+            }
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!("load state sync progress failed : {:?}", e);
+            None
+        }
+    }
+}
+
+/// Init the sync tasks from the saved progress, so the nodes synced before restart are not synced
+/// again, or from the roots if there is no saved progress of the roots.
+fn load_tasks(
+    storage: &dyn Store,
+    roots: &Roots,
+) -> (
+    StateSyncTask<(HashValue, bool)>,
+    StateSyncTask<HashValue>,
+    StateSyncTask<HashValue>,
+) {
+    let mut state_sync_task = StateSyncTask::new();
+    let mut block_accumulator_sync_task = StateSyncTask::new();
+    let mut txn_info_sync_task = StateSyncTask::new();
+    match load_progress(storage, roots) {
+        Some(progress) => {
+            info!(
+                "resume state sync of pivot {:?}, state nodes : {}, accumulator nodes : {}, txn infos : {}.",
+                progress.pivot_id,
+                progress.state_nodes.len(),
+                progress.accumulator_nodes.len(),
+                progress.txn_info_blocks.len()
+            );
+            progress
+                .state_nodes
+                .into_iter()
+                .for_each(|node| state_sync_task.push_back(node));
+            progress
+                .accumulator_nodes
+                .into_iter()
+                .for_each(|node| block_accumulator_sync_task.push_back(node));
+            progress
+                .txn_info_blocks
+                .into_iter()
+                .for_each(|block_id| txn_info_sync_task.push_back(block_id));
+        }
+        None => {
+            state_sync_task.push_back((*roots.state_root(), true));
+            block_accumulator_sync_task.push_back(*roots.block_accumulator_root());
+            txn_info_sync_task.push_back(*roots.pivot_id());
+        }
+    }
+    (
+        state_sync_task,
+        block_accumulator_sync_task,
+        txn_info_sync_task,
+    )
+}
+
+/// The progress of the sync tasks, all the tasks which are not done are saved.
+fn sync_progress(
+    roots: &Roots,
+    state_sync_task: &StateSyncTask<(HashValue, bool)>,
+    block_accumulator_sync_task: &StateSyncTask<HashValue>,
+    txn_info_sync_task: &StateSyncTask<HashValue>,
+) -> StateSyncProgress {
+    StateSyncProgress {
+        pivot_id: *roots.pivot_id(),
+        state_root: *roots.state_root(),
+        block_accumulator_root: *roots.block_accumulator_root(),
+        state_nodes: state_sync_task.frontier(),
+        accumulator_nodes: block_accumulator_sync_task.frontier(),
+        txn_info_blocks: txn_info_sync_task.frontier(),
+    }
+}
+
 impl<C> StateSyncTaskActor<C>
 where
     C: Consensus + Sync + Send + 'static + Clone,
@@ -410,12 +578,9 @@ where
         download_address: Addr<DownloadActor<C>>,
     ) -> StateSyncTaskRef<C> {
         let roots = Roots::new(root.0, root.1, root.2);
-        let mut state_sync_task = StateSyncTask::new();
-        state_sync_task.push_back((*roots.state_root(), true));
-        let mut block_accumulator_sync_task = StateSyncTask::new();
-        block_accumulator_sync_task.push_back(*roots.block_accumulator_root());
-        let mut txn_info_sync_task = StateSyncTask::new();
-        txn_info_sync_task.push_back(*roots.pivot_id());
+        let (state_sync_task, block_accumulator_sync_task, txn_info_sync_task) =
+            load_tasks(storage.as_ref(), &roots);
+        let total_txn_info_task = txn_info_sync_task.wait_2_sync.len() as u64;
         let rpc_client = NetworkRpcClient::new(network_service.clone());
         let address = StateSyncTaskActor::create(move |_ctx| Self {
             self_peer_id,
@@ -429,11 +594,32 @@ where
             block_sync_address,
             state: SyncTaskState::Ready,
             download_address,
-            total_txn_info_task: AtomicU64::new(1),
+            total_txn_info_task: AtomicU64::new(total_txn_info_task),
+            unsaved_tasks: 0,
         });
         StateSyncTaskRef { address }
     }
 
+    fn save_progress(&mut self) {
+        let progress = sync_progress(
+            &self.roots,
+            &self.state_sync_task,
+            &self.block_accumulator_sync_task,
+            &self.txn_info_sync_task,
+        );
+        if let Err(e) = self.storage.save_state_sync_progress(progress) {
+            warn!("save state sync progress failed : {:?}", e);
+        }
+        self.unsaved_tasks = 0;
+    }
+
+    fn try_save_progress(&mut self) {
+        self.unsaved_tasks += 1;
+        if self.unsaved_tasks >= PERSIST_PROGRESS_INTERVAL {
+            self.save_progress();
+        }
+    }
+
     fn do_finish(&mut self) -> bool {
         if !self.state.is_finish() {
             info!(
@@ -453,158 +639,299 @@ where
         self.state.is_finish()
     }
 
+    fn check_finish(&mut self, ctx: &mut Context<Self>) {
+        if self.accumulator_sync_finish() {
+            self.block_sync_address.start();
+        }
+
+        if self.do_finish()
+            && self.total_txn_info_task.load(Ordering::Relaxed)
+                == self.txn_info_sync_task.done_tasks.load(Ordering::Relaxed)
+        {
+            if let Err(e) = self.storage.delete_state_sync_progress() {
+                warn!("delete state sync progress failed : {:?}", e);
+            }
+            self.download_address.do_send(SyncTaskType::STATE);
+            ctx.stop();
+        }
+    }
+
     fn accumulator_sync_finish(&self) -> bool {
         self.block_accumulator_sync_task.is_empty() && self.txn_info_sync_task.is_empty()
     }
 
+    fn sync_peers(&self) -> Vec<PeerId> {
+        get_best_peer_set(self.network_service.clone())
+            .into_iter()
+            .map(|peer| peer.get_peer_id())
+            .filter(|peer_id| peer_id != &self.self_peer_id)
+            .take(MAX_STATE_SYNC_PEERS)
+            .collect()
+    }
+
     fn exe_state_sync_task(&mut self, address: Addr<StateSyncTaskActor<C>>) {
-        let value = self.state_sync_task.pop_front();
-        if let Some((node_key, is_global)) = value {
+        if self.state.is_failed() || self.state_sync_task.wait_2_sync.is_empty() {
+            return;
+        }
+        let peers = self.sync_peers();
+        if peers.is_empty() {
+            warn!("best peer is none, state sync may be failed.");
+            self.state = SyncTaskState::Failed;
+            return;
+        }
+        let mut db_nodes = 0;
+        for peer_id in peers {
+            if self.state_sync_task.get(&peer_id).is_some() {
+                continue;
+            }
+            let batch = self.next_state_batch(&mut db_nodes);
+            if batch.is_empty() {
+                break;
+            }
             SYNC_METRICS
                 .sync_total_count
                 .with_label_values(&[LABEL_STATE])
-                .inc();
-            if let Ok(Some(state_node)) = self.storage.get(&node_key) {
-                debug!("find state_node {:?} in db.", node_key);
-                self.state_sync_task
-                    .insert(self.self_peer_id.clone(), (node_key, is_global));
-                if let Err(err) = address.try_send(StateSyncTaskEvent::new_state(
-                    self.self_peer_id.clone(),
-                    node_key,
-                    Some(state_node),
-                )) {
-                    error!("Send state StateSyncTaskEvent failed : {:?}", err);
-                };
-            } else {
-                let best_peer_info = get_best_peer_info(self.network_service.clone());
-                debug!(
-                    "sync state node {:?} from peer {:?}.",
-                    node_key, best_peer_info
-                );
-                if let Some(best_peer) = best_peer_info {
-                    if self.self_peer_id == best_peer.get_peer_id() {
-                        return;
+                .inc_by(batch.len() as i64);
+            debug!("sync {} state nodes from peer {:?}.", batch.len(), peer_id);
+            let node_keys = batch.iter().map(|(node_key, _)| *node_key).collect();
+            self.state_sync_task.insert(peer_id.clone(), batch);
+            let network_service = self.network_service.clone();
+            let rpc_client = self.rpc_client.clone();
+            let address = address.clone();
+            Arbiter::spawn(async move {
+                sync_state_nodes(node_keys, peer_id, network_service, rpc_client, address).await;
+            });
+        }
+        if db_nodes >= MAX_DB_NODES_PER_ROUND {
+            address.do_send(ContinueSyncEvent(TaskType::STATE));
+        }
+    }
+
+    /// Take the next level of state nodes to sync, the nodes already in db are visited directly,
+    /// so the subtrees synced before restart are skipped. At most `MAX_DB_NODES_PER_ROUND` nodes
+    /// in db are visited in one round, counted by `db_nodes`.
+    fn next_state_batch(&mut self, db_nodes: &mut usize) -> Vec<(HashValue, bool)> {
+        let mut batch = Vec::new();
+        while batch.len() < MAX_BATCH_NODE_SIZE && *db_nodes < MAX_DB_NODES_PER_ROUND {
+            match self.state_sync_task.pop_front() {
+                Some((node_key, is_global)) => match self.get_state_node_from_db(&node_key) {
+                    Some(state_node) => {
+                        debug!("find state_node {:?} in db.", node_key);
+                        *db_nodes += 1;
+                        self.state_sync_task.done(&(node_key, is_global));
+                        self.visit_state_node(&node_key, &state_node, is_global);
                     }
-                    let rpc_client = self.rpc_client.clone();
-                    self.state_sync_task
-                        .insert(best_peer.get_peer_id(), (node_key, is_global));
-                    Arbiter::spawn(async move {
-                        sync_state_node(node_key, best_peer.get_peer_id(), rpc_client, address)
-                            .await;
-                    });
-                } else {
-                    warn!("best peer is none, state sync may be failed.");
-                    self.state = SyncTaskState::Failed;
-                }
+                    None => batch.push((node_key, is_global)),
+                },
+                None => break,
             }
         }
+        batch
     }
 
-    fn handle_state_sync(&mut self, task_event: StateSyncTaskEvent) {
-        if let Some((state_node_hash, is_global)) = self.state_sync_task.get(&task_event.peer_id) {
-            let is_global = *is_global;
-            //1. push back
-            let current_node_key = task_event.key;
-            if state_node_hash != &current_node_key {
-                debug!(
-                    "hash miss match {:} : {:?}",
-                    state_node_hash, current_node_key
-                );
-                return;
+    fn get_state_node_from_db(&self, node_key: &HashValue) -> Option<StateNode> {
+        match self.storage.get(node_key) {
+            Ok(Some(state_node)) if state_node.0.hash() == *node_key => Some(state_node),
+            Ok(Some(_)) => {
+                warn!("state_node {:?} in db is broken, sync it again.", node_key);
+                None
             }
-            let _ = self.state_sync_task.remove(&task_event.peer_id);
-            if let Some(state_node) = task_event.state_node {
-                if let Err(e) = self.storage.put(current_node_key, state_node.clone()) {
-                    debug!("{:?}, retry {:?}.", e, current_node_key);
-                    self.state_sync_task
-                        .push_back((current_node_key, is_global));
-                } else {
-                    self.state_sync_task.do_one_task();
-                    match state_node.inner() {
-                        Node::Leaf(leaf) => {
-                            if !is_global {
-                                return;
-                            }
-                            match AccountState::try_from(leaf.blob().as_ref()) {
-                                Err(e) => {
-                                    error!("AccountState decode from blob failed : {:?}", e);
-                                }
-                                Ok(account_state) => {
-                                    account_state.storage_roots().iter().for_each(|key| {
-                                        if let Some(hash) = key {
-                                            if *hash != *SPARSE_MERKLE_PLACEHOLDER_HASH {
-                                                self.state_sync_task.push_back((*hash, false));
-                                            }
-                                        }
-                                    });
+            _ => None,
+        }
+    }
+
+    fn visit_state_node(&mut self, node_key: &HashValue, state_node: &StateNode, is_global: bool) {
+        match state_node.inner() {
+            Node::Leaf(leaf) => {
+                if !is_global {
+                    return;
+                }
+                match AccountState::try_from(leaf.blob().as_ref()) {
+                    Err(e) => {
+                        error!("AccountState decode from blob failed : {:?}", e);
+                    }
+                    Ok(account_state) => {
+                        account_state.storage_roots().iter().for_each(|key| {
+                            if let Some(hash) = key {
+                                if *hash != *SPARSE_MERKLE_PLACEHOLDER_HASH {
+                                    self.state_sync_task.push_back((*hash, false));
                                 }
                             }
-                        }
-                        Node::Internal(n) => {
-                            for child in n.all_child() {
-                                self.state_sync_task.push_back((child, is_global));
-                            }
-                        }
-                        _ => {
-                            debug!("node {:?} is null.", current_node_key);
-                        }
+                        });
                     }
                 }
-            } else {
-                self.state_sync_task
-                    .push_back((current_node_key, is_global));
             }
-        } else {
+            Node::Internal(n) => {
+                for child in n.all_child() {
+                    self.state_sync_task.push_back((child, is_global));
+                }
+            }
+            _ => {
+                debug!("node {:?} is null.", node_key);
+            }
+        }
+    }
+
+    fn handle_state_sync(&mut self, task_event: StateSyncTaskEvent) {
+        let matched = self
+            .state_sync_task
+            .get(&task_event.peer_id)
+            .map(|batch| {
+                batch
+                    .iter()
+                    .map(|(node_key, _)| node_key)
+                    .eq(task_event.keys.iter())
+            })
+            .unwrap_or(false);
+        if !matched {
             debug!("discard state event : {:?}", task_event);
+            return;
+        }
+        let batch = self
+            .state_sync_task
+            .remove(&task_event.peer_id)
+            .unwrap_or_default();
+        // The request failed if no node is responded, otherwise the response may be truncated.
+        let failed = task_event.state_nodes.is_empty();
+        let mut state_nodes = task_event.state_nodes.into_iter();
+        for (node_key, is_global) in batch {
+            match state_nodes.next() {
+                Some(Some(state_node)) => {
+                    if let Err(e) = self.storage.put(node_key, state_node.clone()) {
+                        debug!("{:?}, retry {:?}.", e, node_key);
+                        self.state_sync_task.push_back((node_key, is_global));
+                    } else {
+                        self.state_sync_task.done(&(node_key, is_global));
+                        self.visit_state_node(&node_key, &state_node, is_global);
+                    }
+                }
+                Some(None) => self.retry_state_node(node_key, is_global),
+                None if failed => self.retry_state_node(node_key, is_global),
+                None => self.state_sync_task.push_back((node_key, is_global)),
+            }
+        }
+    }
+
+    fn retry_state_node(&mut self, node_key: HashValue, is_global: bool) {
+        if !self.state_sync_task.retry((node_key, is_global)) {
+            error!(
+                "state node {:?} is not provided by peers after {} retries, state sync failed.",
+                node_key, MAX_NODE_RETRIES
+            );
+            self.state = SyncTaskState::Failed;
         }
     }
 
     fn exe_accumulator_sync_task(&mut self, address: Addr<StateSyncTaskActor<C>>) {
-        let value = self.block_accumulator_sync_task.pop_front();
-        if let Some(node_key) = value {
+        if self.state.is_failed() || self.block_accumulator_sync_task.wait_2_sync.is_empty() {
+            return;
+        }
+        let peers = self.sync_peers();
+        if peers.is_empty() {
+            warn!("best peer is none.");
+            self.state = SyncTaskState::Failed;
+            return;
+        }
+        let mut db_nodes = 0;
+        for peer_id in peers {
+            if self.block_accumulator_sync_task.get(&peer_id).is_some() {
+                continue;
+            }
+            let batch = self.next_accumulator_batch(address.clone(), &mut db_nodes);
+            if batch.is_empty() {
+                break;
+            }
             SYNC_METRICS
                 .sync_total_count
                 .with_label_values(&[LABEL_ACCUMULATOR])
-                .inc();
-            if let Ok(Some(accumulator_node)) =
-                self.storage.get_node(AccumulatorStoreType::Block, node_key)
-            {
-                debug!("find accumulator_node {:?} in db.", node_key);
-                self.block_accumulator_sync_task
-                    .insert(self.self_peer_id.clone(), node_key);
-                if let Err(err) = address.try_send(StateSyncTaskEvent::new_accumulator(
-                    self.self_peer_id.clone(),
-                    node_key,
-                    Some(accumulator_node),
-                )) {
-                    error!("Send accumulator StateSyncTaskEvent failed : {:?}", err);
-                };
-            } else {
-                let best_peer_info = get_best_peer_info(self.network_service.clone());
-                debug!(
-                    "sync accumulator node {:?} from peer {:?}.",
-                    node_key, best_peer_info
-                );
-                if let Some(best_peer) = best_peer_info {
-                    if self.self_peer_id != best_peer.get_peer_id() {
-                        self.block_accumulator_sync_task
-                            .insert(best_peer.get_peer_id(), node_key);
-                        let rpc_client = self.rpc_client.clone();
-                        Arbiter::spawn(async move {
-                            sync_accumulator_node(
-                                node_key,
-                                best_peer.get_peer_id(),
-                                rpc_client,
-                                address,
-                            )
-                            .await;
-                        });
+                .inc_by(batch.len() as i64);
+            debug!(
+                "sync {} accumulator nodes from peer {:?}.",
+                batch.len(),
+                peer_id
+            );
+            self.block_accumulator_sync_task
+                .insert(peer_id.clone(), batch.clone());
+            let network_service = self.network_service.clone();
+            let rpc_client = self.rpc_client.clone();
+            let address = address.clone();
+            Arbiter::spawn(async move {
+                sync_accumulator_nodes(batch, peer_id, network_service, rpc_client, address).await;
+            });
+        }
+        if db_nodes >= MAX_DB_NODES_PER_ROUND {
+            address.do_send(ContinueSyncEvent(TaskType::BlockAccumulator));
+        }
+    }
+
+    /// Take the next level of accumulator nodes to sync, the nodes already in db are visited
+    /// directly. At most `MAX_DB_NODES_PER_ROUND` nodes in db are visited in one round, counted
+    /// by `db_nodes`.
+    fn next_accumulator_batch(
+        &mut self,
+        address: Addr<StateSyncTaskActor<C>>,
+        db_nodes: &mut usize,
+    ) -> Vec<HashValue> {
+        let mut batch = Vec::new();
+        while batch.len() < MAX_BATCH_NODE_SIZE && *db_nodes < MAX_DB_NODES_PER_ROUND {
+            match self.block_accumulator_sync_task.pop_front() {
+                Some(node_key) => match self.get_accumulator_node_from_db(&node_key) {
+                    Some(accumulator_node) => {
+                        debug!("find accumulator_node {:?} in db.", node_key);
+                        *db_nodes += 1;
+                        self.block_accumulator_sync_task.done(&node_key);
+                        self.visit_accumulator_node(&node_key, accumulator_node, address.clone());
                     }
-                } else {
-                    warn!("best peer is none.");
-                    self.state = SyncTaskState::Failed;
+                    None => batch.push(node_key),
+                },
+                None => break,
+            }
+        }
+        batch
+    }
+
+    fn get_accumulator_node_from_db(&self, node_key: &HashValue) -> Option<AccumulatorNode> {
+        match self
+            .storage
+            .get_node(AccumulatorStoreType::Block, *node_key)
+        {
+            Ok(Some(accumulator_node)) if accumulator_node.hash() == *node_key => {
+                Some(accumulator_node)
+            }
+            Ok(Some(_)) => {
+                warn!(
+                    "accumulator_node {:?} in db is broken, sync it again.",
+                    node_key
+                );
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn visit_accumulator_node(
+        &mut self,
+        node_key: &HashValue,
+        accumulator_node: AccumulatorNode,
+        address: Addr<StateSyncTaskActor<C>>,
+    ) {
+        match accumulator_node {
+            AccumulatorNode::Leaf(leaf) => {
+                self.txn_info_sync_task.push_back(leaf.value());
+                self.total_txn_info_task.fetch_add(1, Ordering::Relaxed);
+                address.do_send(TxnInfoEvent(None));
+            }
+            AccumulatorNode::Internal(n) => {
+                if n.left() != *ACCUMULATOR_PLACEHOLDER_HASH {
+                    self.block_accumulator_sync_task.push_back(n.left());
+                }
+                if n.right() != *ACCUMULATOR_PLACEHOLDER_HASH {
+                    self.block_accumulator_sync_task.push_back(n.right());
                 }
             }
+            _ => {
+                debug!("node {:?} is null.", node_key);
+            }
         }
     }
 
@@ -613,57 +940,58 @@ where
         task_event: StateSyncTaskEvent,
         address: Addr<StateSyncTaskActor<C>>,
     ) {
-        if let Some(accumulator_node_hash) =
-            self.block_accumulator_sync_task.get(&task_event.peer_id)
-        {
-            //1. push back
-            let current_node_key = task_event.key;
-            if accumulator_node_hash != &current_node_key {
-                warn!(
-                    "hash miss match {:} : {:?}",
-                    accumulator_node_hash, current_node_key
-                );
-                return;
-            }
-            let _ = self.block_accumulator_sync_task.remove(&task_event.peer_id);
-            if let Some(accumulator_node) = task_event.accumulator_node {
-                if let Err(e) = self
-                    .storage
-                    .save_node(AccumulatorStoreType::Block, accumulator_node.clone())
-                {
-                    debug!("{:?}", e);
-                    self.block_accumulator_sync_task.push_back(current_node_key);
-                } else {
-                    debug!("receive accumulator_node: {:?}", accumulator_node);
-                    self.block_accumulator_sync_task.do_one_task();
-                    match accumulator_node {
-                        AccumulatorNode::Leaf(leaf) => {
-                            self.txn_info_sync_task.push_back(leaf.value());
-                            self.total_txn_info_task.fetch_add(1, Ordering::Relaxed);
-                            address.do_send(TxnInfoEvent(None));
-                        }
-                        AccumulatorNode::Internal(n) => {
-                            if n.left() != *ACCUMULATOR_PLACEHOLDER_HASH {
-                                self.block_accumulator_sync_task.push_back(n.left());
-                            }
-                            if n.right() != *ACCUMULATOR_PLACEHOLDER_HASH {
-                                self.block_accumulator_sync_task.push_back(n.right());
-                            }
-                        }
-                        _ => {
-                            debug!("node {:?} is null.", current_node_key);
-                        }
+        let matched = self
+            .block_accumulator_sync_task
+            .get(&task_event.peer_id)
+            .map(|batch| batch.iter().eq(task_event.keys.iter()))
+            .unwrap_or(false);
+        if !matched {
+            debug!("discard accumulator event : {:?}", task_event);
+            return;
+        }
+        let batch = self
+            .block_accumulator_sync_task
+            .remove(&task_event.peer_id)
+            .unwrap_or_default();
+        // The request failed if no node is responded, otherwise the response may be truncated.
+        let failed = task_event.accumulator_nodes.is_empty();
+        let mut accumulator_nodes = task_event.accumulator_nodes.into_iter();
+        for node_key in batch {
+            match accumulator_nodes.next() {
+                Some(Some(accumulator_node)) => {
+                    if let Err(e) = self
+                        .storage
+                        .save_node(AccumulatorStoreType::Block, accumulator_node.clone())
+                    {
+                        debug!("{:?}", e);
+                        self.block_accumulator_sync_task.push_back(node_key);
+                    } else {
+                        debug!("receive accumulator_node: {:?}", accumulator_node);
+                        self.block_accumulator_sync_task.done(&node_key);
+                        self.visit_accumulator_node(&node_key, accumulator_node, address.clone());
                     }
                 }
-            } else {
-                self.block_accumulator_sync_task.push_back(current_node_key);
+                Some(None) => self.retry_accumulator_node(node_key),
+                None if failed => self.retry_accumulator_node(node_key),
+                None => self.block_accumulator_sync_task.push_back(node_key),
             }
-        } else {
-            debug!("discard state event : {:?}", task_event);
+        }
+    }
+
+    fn retry_accumulator_node(&mut self, node_key: HashValue) {
+        if !self.block_accumulator_sync_task.retry(node_key) {
+            error!(
+                "accumulator node {:?} is not provided by peers after {} retries, state sync failed.",
+                node_key, MAX_NODE_RETRIES
+            );
+            self.state = SyncTaskState::Failed;
         }
     }
 
     fn exe_txn_info_sync_task(&mut self, address: Addr<StateSyncTaskActor<C>>) {
+        if self.state.is_failed() {
+            return;
+        }
         let value = self.txn_info_sync_task.pop_front();
         if let Some(block_id) = value {
             SYNC_METRICS
@@ -673,7 +1001,7 @@ where
             if let Ok(txn_infos) = self.storage.get_block_transaction_infos(block_id) {
                 debug!("find txn info {:?} in db.", block_id);
                 self.txn_info_sync_task
-                    .insert(self.self_peer_id.clone(), block_id);
+                    .insert(self.self_peer_id.clone(), vec![block_id]);
                 if let Err(err) = address.try_send(StateSyncTaskEvent::new_txn_info(
                     self.self_peer_id.clone(),
                     block_id,
@@ -693,7 +1021,7 @@ where
                     }
                     let rpc_client = self.rpc_client.clone();
                     self.txn_info_sync_task
-                        .insert(best_peer.get_peer_id(), block_id);
+                        .insert(best_peer.get_peer_id(), vec![block_id]);
                     Arbiter::spawn(async move {
                         sync_txn_info(block_id, best_peer.get_peer_id(), rpc_client, address).await;
                     });
@@ -712,7 +1040,10 @@ where
     ) {
         // if let Some(block_id) = self.txn_info_sync_task.get(&task_event.peer_id) {
         //1. push back
-        let current_block_id = task_event.key;
+        let current_block_id = match task_event.keys.first() {
+            Some(block_id) => *block_id,
+            None => return,
+        };
         // if block_id != &current_block_id {
         //     debug!("hash miss match {:} : {:?}", block_id, current_block_id);
         //     return;
@@ -724,11 +1055,16 @@ where
                 self.txn_info_sync_task.push_back(current_block_id);
                 address.do_send(TxnInfoEvent(None));
             } else {
-                self.txn_info_sync_task.do_one_task();
+                self.txn_info_sync_task.done(&current_block_id);
             }
-        } else {
-            self.txn_info_sync_task.push_back(current_block_id);
+        } else if self.txn_info_sync_task.retry(current_block_id) {
             address.do_send(TxnInfoEvent(None));
+        } else {
+            error!(
+                "txn infos of block {:?} are not provided by peers after {} retries, state sync failed.",
+                current_block_id, MAX_NODE_RETRIES
+            );
+            self.state = SyncTaskState::Failed;
         }
         // } else {
         //     info!("discard state event : {:?}", task_event);
@@ -772,6 +1108,8 @@ where
         let old_txn_info_is_empty = self.txn_info_sync_task.is_empty();
         self.txn_info_sync_task.clear();
         self.txn_info_sync_task.push_back(*self.roots.pivot_id());
+        self.total_txn_info_task = AtomicU64::new(1);
+        self.save_progress();
 
         if old_state_is_empty {
             self.exe_state_sync_task(address.clone());
@@ -802,9 +1140,12 @@ where
     type Context = Context<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
+        self.save_progress();
         self.exe_state_sync_task(ctx.address());
         self.exe_accumulator_sync_task(ctx.address());
         self.exe_txn_info_sync_task(ctx.address());
+        // all the nodes may be found in db when resume from the saved progress.
+        self.check_finish(ctx);
     }
 }
 
@@ -824,6 +1165,23 @@ where
     }
 }
 
+impl<C> Handler<ContinueSyncEvent> for StateSyncTaskActor<C>
+where
+    C: Consensus + Sync + Send + 'static + Clone,
+{
+    type Result = Result<()>;
+
+    fn handle(&mut self, event: ContinueSyncEvent, ctx: &mut Self::Context) -> Self::Result {
+        match event.0 {
+            TaskType::STATE => self.exe_state_sync_task(ctx.address()),
+            TaskType::BlockAccumulator => self.exe_accumulator_sync_task(ctx.address()),
+            TaskType::TxnInfo => {}
+        }
+        self.check_finish(ctx);
+        Ok(())
+    }
+}
+
 impl<C> Handler<StateSyncTaskEvent> for StateSyncTaskActor<C>
 where
     C: Consensus + Sync + Send + 'static + Clone,
@@ -837,23 +1195,14 @@ where
             TaskType::TxnInfo => self.handle_txn_info_sync(task_event, ctx.address()),
             TaskType::BlockAccumulator => self.handle_accumulator_sync(task_event, ctx.address()),
         }
+        self.try_save_progress();
 
-        if self.accumulator_sync_finish() {
-            self.block_sync_address.start();
-        }
-
-        if !self.do_finish() {
-            match task_type {
-                TaskType::STATE => self.exe_state_sync_task(ctx.address()),
-                TaskType::BlockAccumulator => self.exe_accumulator_sync_task(ctx.address()),
-                TaskType::TxnInfo => {}
-            }
-        } else if self.total_txn_info_task.load(Ordering::Relaxed)
-            == self.txn_info_sync_task.done_tasks.load(Ordering::Relaxed)
-        {
-            self.download_address.do_send(SyncTaskType::STATE);
-            ctx.stop();
+        match task_type {
+            TaskType::STATE => self.exe_state_sync_task(ctx.address()),
+            TaskType::BlockAccumulator => self.exe_accumulator_sync_task(ctx.address()),
+            TaskType::TxnInfo => {}
         }
+        self.check_finish(ctx);
         Ok(())
     }
 }
@@ -909,6 +1258,15 @@ where
     }
 }
 
+fn get_best_peer_set(network_service: NetworkAsyncService) -> Vec<PeerInfo> {
+    block_on(async move {
+        network_service
+            .best_peer_set()
+            .await
+            .unwrap_or_else(|_| Vec::new())
+    })
+}
+
 fn get_best_peer_info(network_service: NetworkAsyncService) -> Option<PeerInfo> {
     block_on(async move {
         if let Ok(peer_info) = network_service.best_peer().await {
@@ -918,3 +1276,102 @@ fn get_best_peer_info(network_service: NetworkAsyncService) -> Option<PeerInfo>
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_storage::{
+        cache_storage::CacheStorage, storage::StorageInstance, BlockStore, Storage,
+    };
+
+    #[test]
+    fn test_resume_state_sync_tasks() {
+        let storage =
+            Storage::new(StorageInstance::new_cache_instance(CacheStorage::new())).unwrap();
+        let roots = Roots::new(
+            HashValue::random(),
+            HashValue::random(),
+            HashValue::random(),
+        );
+        let peer_id = PeerId::random();
+
+        // first sync, start from the roots.
+        let (mut state_sync_task, mut block_accumulator_sync_task, txn_info_sync_task) =
+            load_tasks(&storage, &roots);
+        assert_eq!(
+            Some((*roots.state_root(), true)),
+            state_sync_task.pop_front()
+        );
+        // the state root is synced, one child is syncing and the other is waiting.
+        state_sync_task.do_one_task();
+        let (syncing_node, waiting_node) = (HashValue::random(), HashValue::random());
+        state_sync_task.insert(peer_id.clone(), vec![(syncing_node, false)]);
+        state_sync_task.push_back((waiting_node, false));
+        // the accumulator root is syncing.
+        let accumulator_root = block_accumulator_sync_task.pop_front().unwrap();
+        block_accumulator_sync_task.insert(peer_id, vec![accumulator_root]);
+        storage
+            .save_state_sync_progress(sync_progress(
+                &roots,
+                &state_sync_task,
+                &block_accumulator_sync_task,
+                &txn_info_sync_task,
+            ))
+            .unwrap();
+
+        // restart the sync, the synced state root is not synced again.
+        let (mut state_sync_task, mut block_accumulator_sync_task, mut txn_info_sync_task) =
+            load_tasks(&storage, &roots);
+        let mut state_nodes = vec![];
+        while let Some(node) = state_sync_task.pop_front() {
+            state_nodes.push(node);
+        }
+        assert_eq!(
+            vec![(syncing_node, false), (waiting_node, false)],
+            state_nodes
+        );
+        assert_eq!(
+            Some(*roots.block_accumulator_root()),
+            block_accumulator_sync_task.pop_front()
+        );
+        assert!(block_accumulator_sync_task.is_empty());
+        assert_eq!(Some(*roots.pivot_id()), txn_info_sync_task.pop_front());
+        assert!(txn_info_sync_task.is_empty());
+
+        // the progress of another pivot is discarded.
+        let new_roots = Roots::new(
+            HashValue::random(),
+            HashValue::random(),
+            HashValue::random(),
+        );
+        let (mut state_sync_task, _, _) = load_tasks(&storage, &new_roots);
+        assert_eq!(
+            Some((*new_roots.state_root(), true)),
+            state_sync_task.pop_front()
+        );
+        assert!(state_sync_task.is_empty());
+    }
+
+    #[test]
+    fn test_node_retry_limit() {
+        let mut task = StateSyncTask::new();
+        let node = HashValue::random();
+        for _ in 0..MAX_NODE_RETRIES {
+            assert!(task.retry(node));
+            assert_eq!(Some(node), task.pop_front());
+        }
+        // the node is put back even if it fails too many times, and the retry count is reset.
+        assert!(!task.retry(node));
+        assert_eq!(Some(node), task.pop_front());
+        assert!(task.retry(node));
+        assert_eq!(Some(node), task.pop_front());
+
+        // the retry count is forgot once the node is done.
+        task.done(&node);
+        for _ in 0..MAX_NODE_RETRIES {
+            assert!(task.retry(node));
+        }
+        assert_eq!(MAX_NODE_RETRIES, task.wait_2_sync.len());
+        assert_eq!(1, task.task_info().2);
+    }
+}
@@ -9,7 +9,7 @@ use logger::prelude::*;
 use network::NetworkAsyncService;
 use network::PeerEvent;
 use starcoin_storage::Store;
-use starcoin_sync_api::{PeerNewBlock, SyncNotify};
+use starcoin_sync_api::{PeerNewBlock, SyncNotify, SyncProgress};
 use std::sync::Arc;
 use traits::Consensus;
 use txpool::TxPoolService;
@@ -37,6 +37,7 @@ where
         txpool: TxPoolService,
         network: NetworkAsyncService,
         storage: Arc<dyn Store>,
+        sync_progress: SyncProgress,
     ) -> Result<Addr<SyncActor<C>>> {
         let txn_sync_addr = TxnSyncActor::launch(txpool, network.clone(), bus.clone());
         let download_address = DownloadActor::launch(
@@ -46,6 +47,7 @@ where
             network,
             bus.clone(),
             storage.clone(),
+            sync_progress,
         )?;
 
         let actor = SyncActor {
@@ -2,19 +2,27 @@ use crate::helper;
 use actix::prelude::*;
 use anyhow::{bail, Result};
 use bus::{Bus, BusActor};
+use crypto::{hash::PlainCryptoHash, HashValue};
 use logger::prelude::*;
 use network::NetworkAsyncService;
-use network_api::NetworkService;
-use network_rpc::{gen_client::NetworkRpcClient, GetTxns};
+use network_api::{reputation, NetworkService};
+use network_rpc::{gen_client::NetworkRpcClient, GetTxnHashes, GetTxns, TxnBloomFilter, MAX_TXNS};
 use starcoin_sync_api::StartSyncTxnEvent;
 use starcoin_txpool_api::TxPoolSyncService;
+use std::collections::HashSet;
+use tx_relay::PeerTxnAnnouncement;
 use txpool::TxPoolService;
 use types::peer_info::PeerId;
 
+/// Max txn count synced from peer in one round of txpool sync.
+const MAX_TXNS_PER_ROUND: usize = 4096;
+
 #[derive(Clone)]
 pub struct TxnSyncActor {
     bus: Addr<BusActor>,
     inner: Inner,
+    /// txns which are fetching from peers.
+    fetching_txns: HashSet<HashValue>,
 }
 
 impl TxnSyncActor {
@@ -30,6 +38,7 @@ impl TxnSyncActor {
                 network_service: network,
             },
             bus,
+            fetching_txns: HashSet::new(),
         };
         actor.start()
     }
@@ -38,7 +47,7 @@ impl TxnSyncActor {
 impl actix::Actor for TxnSyncActor {
     type Context = actix::Context<Self>;
 
-    /// when start, subscribe StartSyncTxnEvent.
+    /// when start, subscribe StartSyncTxnEvent and PeerTxnAnnouncement.
     fn started(&mut self, ctx: &mut Self::Context) {
         let myself = ctx.address().recipient::<StartSyncTxnEvent>();
         self.bus
@@ -53,6 +62,19 @@ impl actix::Actor for TxnSyncActor {
             })
             .wait(ctx);
 
+        let myself = ctx.address().recipient::<PeerTxnAnnouncement>();
+        self.bus
+            .clone()
+            .subscribe(myself)
+            .into_actor(self)
+            .map(|res, _act, ctx| {
+                if let Err(e) = res {
+                    error!("fail to subscribe peer txn announcement, err: {:?}", e);
+                    ctx.terminate();
+                }
+            })
+            .wait(ctx);
+
         info!("txn sync actor started");
     }
 }
@@ -78,6 +100,46 @@ impl actix::Handler<StartSyncTxnEvent> for TxnSyncActor {
     }
 }
 
+impl actix::Handler<PeerTxnAnnouncement> for TxnSyncActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: PeerTxnAnnouncement,
+        ctx: &mut <Self as Actor>::Context,
+    ) -> Self::Result {
+        let mut txn_ids = Vec::new();
+        for id in msg.txn_ids() {
+            if self.fetching_txns.contains(id) || self.inner.pool.find_txn(id).is_some() {
+                continue;
+            }
+            self.fetching_txns.insert(*id);
+            txn_ids.push(*id);
+        }
+        if txn_ids.is_empty() {
+            return;
+        }
+
+        let peer_id = msg.peer_id().clone();
+        for ids in txn_ids.chunks(MAX_TXNS) {
+            let ids = ids.to_vec();
+            self.inner
+                .clone()
+                .fetch_txns_from_peer(peer_id.clone(), ids.clone())
+                .into_actor(self)
+                .map(move |res, act, _ctx| {
+                    if let Err(e) = res {
+                        error!("fail to fetch announced txns from peer, err: {:?}", e);
+                    }
+                    for id in &ids {
+                        act.fetching_txns.remove(id);
+                    }
+                })
+                .spawn(ctx);
+        }
+    }
+}
+
 #[derive(Clone)]
 struct Inner {
     pool: TxPoolService,
@@ -102,13 +164,70 @@ impl Inner {
 
         bail!("fail to sync txn from all peers")
     }
+    /// Fetch the pending txn hashes of the peer, filtered by the txns in local pool, then fetch
+    /// the missing txns by hashes.
     async fn sync_txn_from_peer(&self, peer_id: PeerId) -> Result<()> {
-        let txn_data = helper::get_txns(&self.rpc_client, peer_id.clone(), GetTxns { ids: None })
-            .await?
-            .txns;
+        let local_txns = self.pool.get_pending_txns(None, None);
+        let filter = if local_txns.is_empty() {
+            None
+        } else {
+            let mut filter = TxnBloomFilter::new(local_txns.len());
+            filter.extend(local_txns.iter().map(|txn| txn.crypto_hash()));
+            Some(filter)
+        };
+        let txn_hashes = helper::get_txn_hashes(
+            &self.rpc_client,
+            peer_id.clone(),
+            GetTxnHashes {
+                filter,
+                max_size: MAX_TXNS_PER_ROUND,
+            },
+        )
+        .await?;
+        let missing_hashes: Vec<HashValue> = txn_hashes
+            .into_iter()
+            .filter(|hash| self.pool.find_txn(hash).is_none())
+            .take(MAX_TXNS_PER_ROUND)
+            .collect();
+        let mut succ_num = 0;
+        for ids in missing_hashes.chunks(MAX_TXNS) {
+            succ_num += self
+                .clone()
+                .fetch_txns_from_peer(peer_id.clone(), ids.to_vec())
+                .await?;
+        }
+        info!(
+            "succ to sync {} txn from peer {}, missing {}",
+            succ_num,
+            peer_id,
+            missing_hashes.len()
+        );
+        Ok(())
+    }
+
+    /// Fetch txns by hashes from the peer and import them to pool, return the count of imported
+    /// txns.
+    async fn fetch_txns_from_peer(self, peer_id: PeerId, ids: Vec<HashValue>) -> Result<usize> {
+        let txn_data = helper::get_txns(
+            &self.rpc_client,
+            peer_id.clone(),
+            GetTxns {
+                ids: Some(ids.clone()),
+            },
+        )
+        .await?
+        .txns;
+        let requested: HashSet<HashValue> = ids.into_iter().collect();
+        if txn_data.iter().any(|txn| {
+            !requested.contains(&txn.crypto_hash()) || txn.clone().check_signature().is_err()
+        }) {
+            self.network_service
+                .report_peer(peer_id.clone().into(), reputation::INVALID_TXN);
+            bail!("peer {} respond invalid txns", peer_id);
+        }
         let import_result = self.pool.add_txns(txn_data);
         let succ_num = import_result.iter().filter(|r| r.is_ok()).count();
-        info!("succ to sync {} txn from peer {}", succ_num, peer_id);
-        Ok(())
+        debug!("succ to fetch {} txn from peer {}", succ_num, peer_id);
+        Ok(succ_num)
     }
 }
@@ -18,6 +18,7 @@ use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::storage::StorageInstance;
 use starcoin_storage::Storage;
 use starcoin_sync::SyncActor;
+use starcoin_sync_api::SyncProgress;
 use starcoin_wallet_api::WalletAccount;
 use std::{sync::Arc, time::Duration};
 use traits::ChainAsyncService;
@@ -99,6 +100,7 @@ fn test_network_actor_rpc() {
             txpool_1.get_service(),
             network_1.clone(),
             storage_1.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
         Delay::new(Duration::from_secs(1)).await;
@@ -209,6 +211,7 @@ fn test_network_actor_rpc() {
             txpool_2.get_service(),
             network_2.clone(),
             storage_2.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
 
@@ -315,6 +318,7 @@ fn test_network_actor_rpc_2() {
             txpool_1.get_service(),
             network_1.clone(),
             storage_1.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
         Delay::new(Duration::from_secs(1)).await;
@@ -391,6 +395,7 @@ fn test_network_actor_rpc_2() {
             txpool_2.get_service(),
             network_2.clone(),
             storage_2.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
         Delay::new(Duration::from_secs(1)).await;
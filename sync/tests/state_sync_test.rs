@@ -18,6 +18,7 @@ use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::storage::StorageInstance;
 use starcoin_storage::Storage;
 use starcoin_sync::SyncActor;
+use starcoin_sync_api::SyncProgress;
 use starcoin_wallet_api::WalletAccount;
 use std::{sync::Arc, time::Duration};
 use traits::ChainAsyncService;
@@ -100,6 +101,7 @@ fn test_state_sync() {
             txpool_1.get_service(),
             network_1.clone(),
             storage_1.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
         BlockRelayer::new(bus_1.clone(), txpool_1.get_service(), network_1.clone()).unwrap();
@@ -217,6 +219,7 @@ fn test_state_sync() {
             txpool_2.get_service(),
             network_2.clone(),
             storage_2.clone(),
+            SyncProgress::default(),
         )
         .unwrap();
         Delay::new(Duration::from_secs(5)).await;
@@ -3,6 +3,8 @@
 use crate::MyWorld;
 use cucumber::{Steps, StepsBuilder};
 use starcoin_config::{ChainNetwork, NodeConfig, StarcoinOpt};
+use starcoin_consensus::dev::DevConsensus;
+use starcoin_traits::Consensus;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -56,6 +58,24 @@ pub fn steps() -> Steps<MyWorld> {
             let client = world.rpc_client.as_ref().take().unwrap();
             let peers = client.clone().node_peers();
             assert!(peers.is_ok());
+        })
+        .then("mine block by rpc", |world: &mut MyWorld, _step| {
+            let client = world.rpc_client.as_ref().take().unwrap();
+            let account = world.default_account.as_ref().take().unwrap();
+            let template = client
+                .miner_get_block_template(
+                    account.address,
+                    Some(account.get_auth_key().prefix().to_vec()),
+                )
+                .unwrap();
+            let nonce = DevConsensus::solve_consensus_nonce(
+                template.template_id.to_vec().as_slice(),
+                template.difficulty,
+            );
+            let block_id = client
+                .miner_submit_block(template.template_id, nonce)
+                .unwrap();
+            assert_ne!(block_id, template.template_id);
         });
     builder.build()
 }
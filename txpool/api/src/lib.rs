@@ -23,6 +23,13 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     /// there is a good chance that the transaction will actually be removed.
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction>;
 
+    /// Removes the txns discarded when packaging a block as invalid, and penalizes their senders,
+    /// so the remaining txns of the senders are ordered after others.
+    fn discard_txns(&self, txns: Vec<SignedUserTransaction>);
+
+    /// Find the transaction by hash in the pool, returns `None` if it's not in pool.
+    fn find_txn(&self, txn_hash: &HashValue) -> Option<SignedUserTransaction>;
+
     /// Get all pending txns which is ok to be packaged to mining.
     /// `now` is the current timestamp in secs, if it's None, it default to real world's current timestamp.
     /// It's an Option to make mock time easier.
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use crypto::hash::HashValue;
+use crypto::hash::{HashValue, PlainCryptoHash};
 use futures_channel::mpsc;
 use starcoin_txpool_api::TxPoolSyncService;
 use std::{
@@ -50,6 +50,19 @@ impl TxPoolSyncService for MockTxPoolService {
         unimplemented!()
     }
 
+    fn discard_txns(&self, txns: Vec<SignedUserTransaction>) {
+        self.pool.lock().unwrap().retain(|txn| !txns.contains(txn));
+    }
+
+    fn find_txn(&self, txn_hash: &HashValue) -> Option<SignedUserTransaction> {
+        self.pool
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|txn| &txn.crypto_hash() == txn_hash)
+            .cloned()
+    }
+
     /// Get all pending txns which is ok to be packaged to mining.
     fn get_pending_txns(
         &self,
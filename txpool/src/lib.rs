@@ -17,7 +17,7 @@ use starcoin_config::TxPoolConfig;
 use starcoin_txpool_api::TxnStatusFullEvent;
 use std::{fmt::Debug, sync::Arc};
 use storage::Store;
-use tx_relay::{PeerTransactions, PropagateNewTransactions};
+use tx_relay::PropagateNewTransactions;
 
 use counters::{TXPOOL_STATUS_GAUGE_VEC, TXPOOL_TXNS_GAUGE};
 pub use pool::TxStatus;
@@ -123,21 +123,6 @@ impl actix::Actor for TxPoolActor {
     type Context = actix::Context<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
-        // subscribe txn relayer peer txns
-        let myself = ctx.address().recipient::<PeerTransactions>();
-        self.bus
-            .clone()
-            .subscribe(myself)
-            .into_actor(self)
-            .then(|res, act, ctx| {
-                if let Err(e) = res {
-                    error!("fail to subscribe txn relayer message, err: {:?}", e);
-                    ctx.terminate();
-                }
-                async {}.into_actor(act)
-            })
-            .wait(ctx);
-
         ctx.add_stream(self.inner.subscribe_txns());
 
         info!("txn pool started");
@@ -199,20 +184,6 @@ impl StreamHandler<TxnStatusFullEvent> for TxPoolActor {
     }
 }
 
-impl actix::Handler<PeerTransactions> for TxPoolActor {
-    type Result = ();
-
-    fn handle(
-        &mut self,
-        msg: PeerTransactions,
-        _ctx: &mut <Self as Actor>::Context,
-    ) -> Self::Result {
-        // JUST need to keep at most once delivery.
-        let txns = msg.peer_transactions();
-        let _ = self.inner.import_txns(txns);
-    }
-}
-
 #[cfg(test)]
 mod test_sync_and_send {
     fn assert_send<T: Send>() {}
@@ -1,10 +1,11 @@
 use super::test_helper;
 use crate::pool::AccountSeqNumberClient;
+use crate::TxPoolService;
 use anyhow::Result;
 use common_crypto::{hash::PlainCryptoHash, keygen::KeyGen};
 use parking_lot::RwLock;
 use starcoin_executor::{
-    create_signed_txn_with_association_account, encode_transfer_script, DEFAULT_EXPIRATION_TIME,
+    build_transfer_txn, peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME,
     DEFAULT_MAX_GAS_AMOUNT,
 };
 use starcoin_open_block::OpenedBlock;
@@ -16,9 +17,7 @@ use storage::BlockStore;
 use types::{
     account_address::{self, AccountAddress},
     account_config,
-    transaction::{
-        authenticator::AuthenticationKey, SignedUserTransaction, Transaction, TransactionPayload,
-    },
+    transaction::{authenticator::AuthenticationKey, SignedUserTransaction, Transaction},
     U256,
 };
 
@@ -56,13 +55,7 @@ async fn test_txn_expire() -> Result<()> {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let auth_prefix = AuthenticationKey::ed25519(&public_key).prefix().to_vec();
-    let txn = create_signed_txn_with_association_account(
-        TransactionPayload::Script(encode_transfer_script(&account_address, auth_prefix, 10000)),
-        0,
-        DEFAULT_MAX_GAS_AMOUNT,
-        1,
-        2,
-    );
+    let txn = peer_to_peer_txn_sent_as_association(account_address, auth_prefix, 0, 10000, 2);
     txpool_service.add_txns(vec![txn]).pop().unwrap()?;
     let pendings = txpool_service.get_pending_txns(None, Some(0));
     assert_eq!(pendings.len(), 1);
@@ -201,3 +194,107 @@ async fn test_rollback() -> Result<()> {
     assert_eq!(txns.len(), 0);
     Ok(())
 }
+
+#[stest::test]
+async fn test_discard_txns_penalize_sender() -> Result<()> {
+    let (pool, storage) = test_helper::start_txpool();
+    let txpool_service = pool.get_service();
+    let start_timestamp = 0;
+    let expiration_timestamp = start_timestamp + DEFAULT_EXPIRATION_TIME;
+
+    // fund a second sender, so the order between two senders can be observed.
+    let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+    let account_address = account_address::from_public_key(&public_key);
+    let auth_prefix = AuthenticationKey::ed25519(&public_key).prefix().to_vec();
+    let funded_block = {
+        let master = storage.get_startup_info()?.unwrap().master;
+        let block_header = storage.get_block_header_by_hash(master)?.unwrap();
+        let txn = peer_to_peer_txn_sent_as_association(
+            account_address,
+            auth_prefix,
+            0,
+            1_000_000,
+            expiration_timestamp,
+        );
+        let mut open_block = OpenedBlock::new(
+            storage.clone(),
+            block_header.clone(),
+            u64::MAX,
+            account_config::association_address(),
+            None,
+            start_timestamp + 60 * 10,
+            vec![],
+        )?;
+        let excluded_txns = open_block.push_txns(vec![txn])?;
+        assert_eq!(excluded_txns.discarded_txns.len(), 0);
+        let block = open_block.finalize()?.into_block(0, U256::from(1024u64));
+
+        let chain_state = ChainStateDB::new(storage.clone(), Some(block_header.state_root()));
+        let mut txns: Vec<_> = block
+            .transactions()
+            .iter()
+            .map(|t| Transaction::UserTransaction(t.clone()))
+            .collect();
+        txns.insert(0, Transaction::BlockMetadata(block.clone().into_metadata()));
+        let root =
+            starcoin_executor::block_execute(&chain_state, txns, block.header().gas_limit())?
+                .state_root;
+        assert_eq!(root, block.header().state_root());
+        chain_state.flush()?;
+        block
+    };
+    txpool_service.chain_new_block(vec![funded_block], vec![])?;
+
+    let sender_txn = |seq_num: u64| {
+        build_transfer_txn(
+            account_address,
+            account_config::association_address(),
+            vec![],
+            seq_num,
+            1,
+            4,
+            DEFAULT_MAX_GAS_AMOUNT,
+            expiration_timestamp,
+        )
+        .sign(&private_key, public_key.clone())
+        .map(|txn| txn.into_inner())
+    };
+    let queued_txn = sender_txn(0)?;
+    let discarded_txn = sender_txn(1)?;
+    // gas price of association txn is 1, lower than the sender's, but higher than the penalized score.
+    let other_txn =
+        peer_to_peer_txn_sent_as_association(account_address, vec![], 1, 1, expiration_timestamp);
+    for result in
+        txpool_service.add_txns(vec![queued_txn.clone(), discarded_txn.clone(), other_txn])
+    {
+        result?;
+    }
+
+    let pending_senders = |txpool_service: &TxPoolService| {
+        txpool_service
+            .get_pending_txns(None, Some(start_timestamp + 60 * 10))
+            .iter()
+            .map(|txn| txn.sender())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        pending_senders(&txpool_service),
+        vec![
+            account_address,
+            account_address,
+            account_config::association_address()
+        ]
+    );
+
+    txpool_service.discard_txns(vec![discarded_txn.clone()]);
+    assert!(txpool_service
+        .find_txn(&discarded_txn.crypto_hash())
+        .is_none());
+    assert!(txpool_service.find_txn(&queued_txn.crypto_hash()).is_some());
+    // the penalized sender now sorts after the other sender, despite its higher gas price.
+    assert_eq!(
+        pending_senders(&txpool_service),
+        vec![account_config::association_address(), account_address]
+    );
+    Ok(())
+}
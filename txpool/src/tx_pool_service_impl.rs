@@ -12,12 +12,12 @@ use crate::{
 };
 
 use anyhow::Result;
-use common_crypto::hash::HashValue;
+use common_crypto::hash::{HashValue, PlainCryptoHash};
 use futures_channel::mpsc;
 use parking_lot::RwLock;
 use starcoin_config::TxPoolConfig;
 use starcoin_txpool_api::TxPoolSyncService;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use storage::Store;
 use types::{
     account_address::AccountAddress,
@@ -98,6 +98,20 @@ impl TxPoolSyncService for TxPoolService {
             .map(|t| t.signed().clone())
     }
 
+    fn discard_txns(&self, txns: Vec<SignedUserTransaction>) {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["discard_txns"])
+            .start_timer();
+        self.inner.discard_txns(txns)
+    }
+
+    fn find_txn(&self, txn_hash: &HashValue) -> Option<SignedUserTransaction> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["find_txn"])
+            .start_timer();
+        self.inner.queue.find(txn_hash).map(|t| t.signed().clone())
+    }
+
     /// Get all pending txns which is ok to be packaged to mining.
     fn get_pending_txns(
         &self,
@@ -206,6 +220,12 @@ impl Inner {
             .pop()
             .expect("remove should return one result per hash")
     }
+    pub(crate) fn discard_txns(&self, txns: Vec<SignedUserTransaction>) {
+        let txn_hashes: Vec<_> = txns.iter().map(|txn| txn.crypto_hash()).collect();
+        let senders: HashSet<_> = txns.iter().map(|txn| txn.sender()).collect();
+        self.queue.remove(txn_hashes.iter(), true);
+        self.queue.penalize(senders.iter());
+    }
     pub(crate) fn get_pending(
         &self,
         max_len: u64,
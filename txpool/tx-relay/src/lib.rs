@@ -1,10 +1,11 @@
-use starcoin_types::transaction::SignedUserTransaction;
+use starcoin_crypto::HashValue;
+use starcoin_types::{peer_info::PeerId, transaction::SignedUserTransaction};
 
 pub enum TxnRelayMessage {
     /// propagate local txns to remote peers,
     PropagateNewTransactions(PropagateNewTransactions),
-    /// txns received from remote peers.
-    PeerTransactions(PeerTransactions),
+    /// txn hashes announced by remote peers.
+    PeerTxnAnnouncement(PeerTxnAnnouncement),
 }
 
 #[derive(Clone, Debug)]
@@ -22,21 +23,28 @@ impl actix::Message for PropagateNewTransactions {
     type Result = ();
 }
 
+/// Txn hashes announced by a remote peer, the txns which are unknown to local
+/// should be fetched from the peer by rpc.
 #[derive(Clone, Debug)]
-pub struct PeerTransactions {
-    txns: Vec<SignedUserTransaction>,
+pub struct PeerTxnAnnouncement {
+    peer_id: PeerId,
+    txn_ids: Vec<HashValue>,
 }
-impl actix::Message for PeerTransactions {
+impl actix::Message for PeerTxnAnnouncement {
     type Result = ();
 }
 
-impl PeerTransactions {
-    pub fn new(txns: Vec<SignedUserTransaction>) -> Self {
-        Self { txns }
+impl PeerTxnAnnouncement {
+    pub fn new(peer_id: PeerId, txn_ids: Vec<HashValue>) -> Self {
+        Self { peer_id, txn_ids }
     }
 
-    pub fn peer_transactions(self) -> Vec<SignedUserTransaction> {
-        self.txns
+    pub fn peer_id(&self) -> &PeerId {
+        &self.peer_id
+    }
+
+    pub fn txn_ids(&self) -> &[HashValue] {
+        self.txn_ids.as_slice()
     }
 }
 
@@ -20,8 +20,12 @@ use std::cmp::PartialOrd;
 /// Type for block number.
 pub type BlockNumber = u64;
 
-/// block timestamp allowed future times
-pub const ALLOWED_FUTURE_BLOCKTIME: u64 = 15 * 1000; // 15 Second;
+/// block timestamp allowed future times, block timestamp is in seconds.
+pub const ALLOWED_FUTURE_BLOCKTIME: u64 = 15; // 15 Second;
+
+/// Count of latest blocks to calculate the median time past,
+/// a new block's timestamp must be newer than the median time past.
+pub const MEDIAN_TIME_PAST_WINDOW: u64 = 11;
 
 #[derive(
     Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Serialize, Deserialize, CryptoHasher, CryptoHash,
@@ -287,6 +291,10 @@ impl BlockBody {
             uncles,
         }
     }
+
+    pub fn transactions(&self) -> &[SignedUserTransaction] {
+        self.transactions.as_slice()
+    }
 }
 
 impl Into<BlockBody> for Vec<SignedUserTransaction> {
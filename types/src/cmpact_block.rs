@@ -2,38 +2,131 @@ use crate::block::{Block, BlockHeader};
 use crate::transaction::{SignedUserTransaction, Transaction};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
+use std::collections::HashSet;
 
+/// Length in bytes of the short transaction id.
+pub const SHORT_ID_LENGTH: usize = 6;
+
+/// Compact block is relayed to peers instead of full block, peers rebuild the block by the txns
+/// in their txpool.
+/// `short_ids` are the short ids of the txns which are not prefilled, in block order.
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct CompactBlock {
     pub header: BlockHeader,
+    /// Salt of the short ids, chosen randomly by the sender for every block.
+    pub nonce: u64,
     pub short_ids: Vec<ShortId>,
     pub prefilled_txn: Vec<PrefiledTxn>,
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PrefiledTxn {
+    /// index of the txn in block.
     pub index: u64,
-    //todo: change to a small one
     pub tx: SignedUserTransaction,
 }
 
-// TODO: change to siphash24 of 6bites
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
-pub struct ShortId(pub HashValue);
+/// Keyed hash of the txn id truncated to 6 bytes, the key is derived from the block id and nonce,
+/// so an attacker can not make collisions for all blocks.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ShortId(pub [u8; SHORT_ID_LENGTH]);
+
+impl ShortId {
+    pub fn new(key: &HashValue, txn_id: &HashValue) -> Self {
+        let mut data = key.to_vec();
+        data.extend_from_slice(txn_id.to_vec().as_slice());
+        let hash = HashValue::sha3_256_of(data.as_slice());
+        let mut short_id = [0u8; SHORT_ID_LENGTH];
+        short_id.copy_from_slice(&hash.to_vec()[..SHORT_ID_LENGTH]);
+        ShortId(short_id)
+    }
+}
 
 impl CompactBlock {
-    pub fn new(block: &Block, prefilled_txn: Vec<PrefiledTxn>) -> Self {
+    /// Build compact block of the `block`, the txns at `prefilled_indexes` are sent in full.
+    pub fn new(block: &Block, nonce: u64, prefilled_indexes: &[usize]) -> Self {
         let header = block.header.clone();
-        let short_ids: Vec<ShortId> = block
-            .transactions()
-            .iter()
-            .map(|tx| Transaction::UserTransaction(tx.clone()).id())
-            .map(ShortId)
-            .collect();
+        let key = Self::short_id_key(&header, nonce);
+        let mut short_ids = Vec::new();
+        let mut prefilled_txn = Vec::new();
+        let prefilled_indexes: HashSet<&usize> = prefilled_indexes.iter().collect();
+        for (index, txn) in block.transactions().iter().enumerate() {
+            if prefilled_indexes.contains(&index) {
+                prefilled_txn.push(PrefiledTxn {
+                    index: index as u64,
+                    tx: txn.clone(),
+                });
+            } else {
+                let txn_id = Transaction::UserTransaction(txn.clone()).id();
+                short_ids.push(ShortId::new(&key, &txn_id));
+            }
+        }
         CompactBlock {
             header,
+            nonce,
             short_ids,
             prefilled_txn,
         }
     }
+
+    fn short_id_key(header: &BlockHeader, nonce: u64) -> HashValue {
+        let mut data = header.id().to_vec();
+        data.extend_from_slice(&nonce.to_le_bytes());
+        HashValue::sha3_256_of(data.as_slice())
+    }
+
+    /// Short id of the txn in this compact block.
+    pub fn short_id(&self, txn_id: &HashValue) -> ShortId {
+        ShortId::new(&Self::short_id_key(&self.header, self.nonce), txn_id)
+    }
+
+    /// Count of the txns in the block.
+    pub fn txn_len(&self) -> usize {
+        self.short_ids.len() + self.prefilled_txn.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockBody;
+
+    fn mock_block(txn_len: usize) -> Block {
+        let txns = (0..txn_len)
+            .map(|_| SignedUserTransaction::mock())
+            .collect::<Vec<_>>();
+        Block::new(BlockHeader::random(), BlockBody::new(txns, None))
+    }
+
+    #[test]
+    fn test_compact_block() {
+        let block = mock_block(3);
+        let compact_block = CompactBlock::new(&block, 1, &[1]);
+        assert_eq!(3, compact_block.txn_len());
+        assert_eq!(2, compact_block.short_ids.len());
+        assert_eq!(1, compact_block.prefilled_txn[0].index);
+        let txn_id = Transaction::UserTransaction(block.transactions()[2].clone()).id();
+        assert_eq!(compact_block.short_ids[1], compact_block.short_id(&txn_id));
+    }
+
+    #[test]
+    fn test_compact_block_duplicate_prefilled_indexes() {
+        let block = mock_block(2);
+        let compact_block = CompactBlock::new(&block, 1, &[0, 0, 5]);
+        assert_eq!(2, compact_block.txn_len());
+        assert_eq!(1, compact_block.prefilled_txn.len());
+        assert_eq!(0, compact_block.prefilled_txn[0].index);
+    }
+
+    #[test]
+    fn test_short_id_salted_by_nonce() {
+        let block = mock_block(1);
+        let txn_id = Transaction::UserTransaction(block.transactions()[0].clone()).id();
+        let compact_block1 = CompactBlock::new(&block, 1, &[]);
+        let compact_block2 = CompactBlock::new(&block, 2, &[]);
+        assert_ne!(
+            compact_block1.short_id(&txn_id),
+            compact_block2.short_id(&txn_id)
+        );
+    }
 }
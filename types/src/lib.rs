@@ -60,5 +60,5 @@ pub mod write_set {
 pub use ethereum_types::{H256, U256};
 
 pub const CHAIN_PROTOCOL_NAME: &[u8] = b"/starcoin/chain/1";
-pub const TXN_PROTOCOL_NAME: &[u8] = b"/starcoin/txn/1";
+pub const TXN_PROTOCOL_NAME: &[u8] = b"/starcoin/txn/2";
 pub const BLOCK_PROTOCOL_NAME: &[u8] = b"/starcoin/block/1";
@@ -243,6 +243,16 @@ impl PeerInfo {
     }
 }
 
+/// Reputation of a known peer.
+#[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug)]
+pub struct PeerScore {
+    pub peer_id: PeerId,
+    pub reputation: i32,
+    pub connected: bool,
+    /// The peer is banned manually, it keeps banned until unban.
+    pub banned: bool,
+}
+
 #[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug)]
 pub struct RpcInfo {
     paths: Vec<String>,
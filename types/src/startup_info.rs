@@ -94,3 +94,32 @@ impl Into<Vec<ChainInfo>> for StartupInfo {
         branches
     }
 }
+
+/// Progress of the fast state sync, persisted to resume the state sync after restart.
+#[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug)]
+pub struct StateSyncProgress {
+    pub pivot_id: HashValue,
+    pub state_root: HashValue,
+    pub block_accumulator_root: HashValue,
+    /// State nodes waiting for sync, and whether the node is in the global state tree.
+    pub state_nodes: Vec<(HashValue, bool)>,
+    pub accumulator_nodes: Vec<HashValue>,
+    /// Blocks whose txn infos are waiting for sync.
+    pub txn_info_blocks: Vec<HashValue>,
+}
+
+impl TryFrom<Vec<u8>> for StateSyncProgress {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        StateSyncProgress::decode(value.as_slice())
+    }
+}
+
+impl TryInto<Vec<u8>> for StateSyncProgress {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+}
@@ -4,7 +4,7 @@
 use crate::contract::{Contract, ModuleContract};
 /// A wrap to move-lang compiler
 use crate::shared::Address;
-use anyhow::{bail, ensure, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use starcoin_vm_types::account_address::AccountAddress;
@@ -156,6 +156,70 @@ pub fn check_compat_and_verify_module(pre_code: &[u8], new_code: &[u8]) -> Resul
     Ok(new_module)
 }
 
+/// Split a comma separated list of `name: type` (or `name: kind`), and return the names.
+fn split_names(list: &str) -> Result<Vec<String>> {
+    let mut names = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in list
+        .char_indices()
+        .chain(std::iter::once((list.len(), ',')))
+    {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                let item = list[start..i].trim();
+                if !item.is_empty() {
+                    let name = item
+                        .split(':')
+                        .next()
+                        .map(|name| name.trim())
+                        .filter(|name| !name.is_empty())
+                        .ok_or_else(|| format_err!("Invalid script parameter {}", item))?;
+                    names.push(name.to_string());
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    Ok(names)
+}
+
+/// Parse the type parameter list and the parameter list of the script function from the script
+/// source.
+fn script_function_signature(source: &str) -> Result<(String, String)> {
+    static COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)//[^\n]*|/\*.*?\*/").unwrap());
+    static FUN: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"(^|[^A-Za-z0-9_])fun[ \t\r\n]+[A-Za-z_][A-Za-z0-9_]*[ \t\r\n]*(<([^>]*)>)?[ \t\r\n]*\(",
+        )
+        .unwrap()
+    });
+    let source = COMMENT.replace_all(source, " ");
+    let fun = FUN
+        .captures(&source)
+        .ok_or_else(|| format_err!("Can not find script function in source."))?;
+    let type_params = fun.get(3).map(|m| m.as_str()).unwrap_or("");
+    let params = &source[fun.get(0).expect("match must exist").end()..];
+    let params = &params[..params
+        .find(')')
+        .ok_or_else(|| format_err!("Script function parameters are not closed."))?];
+    Ok((type_params.to_string(), params.to_string()))
+}
+
+/// Parse the parameter names of the script function from the script source, the compiled
+/// script does not keep the names.
+pub fn script_parameter_names(source: &str) -> Result<Vec<String>> {
+    split_names(script_function_signature(source)?.1.as_str())
+}
+
+/// Parse the type parameter names of the script function from the script source.
+pub fn script_type_parameter_names(source: &str) -> Result<Vec<String>> {
+    split_names(script_function_signature(source)?.0.as_str())
+}
+
 /// Load bytecode file, return the bytecode bytes, and whether it's script.
 pub fn load_bytecode_file<P: AsRef<Path>>(file_path: P) -> Result<(Vec<u8>, bool)> {
     let mut file = OpenOptions::new().read(true).write(false).open(file_path)?;
@@ -186,8 +250,165 @@ mod tests {
     use super::*;
     use crate::command_line::parse_address;
     use starcoin_logger::prelude::*;
+    use starcoin_vm_types::abi::{
+        ArgumentABI, FieldABI, FunctionABI, KindABI, ModuleABI, StructABI, StructTypeABI, TypeABI,
+        TypeParameterABI,
+    };
     use starcoin_vm_types::language_storage::CORE_CODE_ADDRESS;
 
+    #[test]
+    fn test_script_parameter_names() {
+        let source = r#"
+        script {
+        use 0x1::Account;
+        // fun comment(a: u8)
+        /* fun block_comment(b: u8)
+        */
+        fun main<Token: copyable>(account: &signer, /* to */ payee: address, meta: vector<vector<u8>>, amount: u128) {
+            Account::pay_from<Token>(account, payee, amount)
+        }
+        }
+        "#;
+        assert_eq!(
+            script_parameter_names(source).unwrap(),
+            vec!["account", "payee", "meta", "amount"]
+        );
+        assert_eq!(script_type_parameter_names(source).unwrap(), vec!["Token"]);
+        assert!(script_parameter_names("script { fun main() {} }")
+            .unwrap()
+            .is_empty());
+        assert!(script_type_parameter_names("script { fun main() {} }")
+            .unwrap()
+            .is_empty());
+        assert!(script_parameter_names("script {}").is_err());
+    }
+
+    #[stest::test]
+    fn test_module_abi() {
+        let source = r#"
+            module M {
+                resource struct Box<T> {
+                    value: T,
+                }
+
+                struct Pair<K: copyable, V> {
+                    key: K,
+                    values: vector<V>,
+                }
+
+                public fun new_box<T>(value: T): Box<T> {
+                    Box<T> { value }
+                }
+
+                public fun value<T: copyable>(box: &Box<T>, count: u64): (T, u64) {
+                    (*&box.value, count)
+                }
+
+                fun private_fun() {
+                }
+            }
+        "#;
+        let code = compile_source_string_no_report(source, &[], CORE_CODE_ADDRESS)
+            .unwrap()
+            .1
+            .unwrap()
+            .serialize();
+        let module = CompiledModule::deserialize(code.as_slice()).unwrap();
+        let abi = ModuleABI::new(&module);
+        assert_eq!(abi.address, CORE_CODE_ADDRESS);
+        assert_eq!(abi.name, "M");
+        let box_type = |type_argument: TypeABI| {
+            TypeABI::Struct(StructTypeABI {
+                address: CORE_CODE_ADDRESS,
+                module: "M".to_string(),
+                name: "Box".to_string(),
+                type_arguments: vec![type_argument],
+            })
+        };
+
+        assert_eq!(
+            abi.find_struct("Box").unwrap(),
+            &StructABI {
+                name: "Box".to_string(),
+                is_resource: true,
+                type_parameters: vec![TypeParameterABI {
+                    name: "T0".to_string(),
+                    kind: KindABI::All,
+                }],
+                fields: Some(vec![FieldABI {
+                    name: "value".to_string(),
+                    type_abi: TypeABI::TypeParameter(0),
+                }]),
+            }
+        );
+        assert_eq!(
+            abi.find_struct("Pair").unwrap(),
+            &StructABI {
+                name: "Pair".to_string(),
+                is_resource: false,
+                type_parameters: vec![
+                    TypeParameterABI {
+                        name: "T0".to_string(),
+                        kind: KindABI::Copyable,
+                    },
+                    TypeParameterABI {
+                        name: "T1".to_string(),
+                        kind: KindABI::All,
+                    },
+                ],
+                fields: Some(vec![
+                    FieldABI {
+                        name: "key".to_string(),
+                        type_abi: TypeABI::TypeParameter(0),
+                    },
+                    FieldABI {
+                        name: "values".to_string(),
+                        type_abi: TypeABI::Vector(Box::new(TypeABI::TypeParameter(1))),
+                    },
+                ]),
+            }
+        );
+
+        assert_eq!(abi.functions.len(), 2);
+        assert!(abi.find_function("private_fun").is_none());
+        assert_eq!(
+            abi.find_function("new_box").unwrap(),
+            &FunctionABI {
+                name: "new_box".to_string(),
+                type_parameters: vec![TypeParameterABI {
+                    name: "T0".to_string(),
+                    kind: KindABI::All,
+                }],
+                parameters: vec![ArgumentABI {
+                    name: "arg0".to_string(),
+                    type_abi: TypeABI::TypeParameter(0),
+                }],
+                returns: vec![box_type(TypeABI::TypeParameter(0))],
+            }
+        );
+        assert_eq!(
+            abi.find_function("value").unwrap(),
+            &FunctionABI {
+                name: "value".to_string(),
+                type_parameters: vec![TypeParameterABI {
+                    name: "T0".to_string(),
+                    kind: KindABI::Copyable,
+                }],
+                parameters: vec![
+                    ArgumentABI {
+                        name: "arg0".to_string(),
+                        type_abi: TypeABI::Reference(Box::new(box_type(TypeABI::TypeParameter(0)))),
+                    },
+                    ArgumentABI {
+                        name: "arg1".to_string(),
+                        type_abi: TypeABI::U64,
+                    },
+                ],
+                returns: vec![TypeABI::TypeParameter(0), TypeABI::U64],
+            }
+        );
+    }
+
     #[test]
     fn test_unknown_place_holder() {
         let source_tpl = r#"
@@ -66,6 +66,8 @@ pub struct AccountDefinition {
 pub enum Entry {
     /// Defines an account that can be used in tests.
     AccountDefinition(AccountDefinition),
+    /// Executes the transactions between block prologues in one block.
+    ExecuteInBlock,
 }
 
 impl FromStr for Entry {
@@ -98,6 +100,9 @@ impl FromStr for Entry {
                 sequence_number,
             }));
         }
+        if s == "execute-in-block" {
+            return Ok(Entry::ExecuteInBlock);
+        }
         Err(ErrorKind::Other(format!("failed to parse '{}' as global config entry", s)).into())
     }
 }
@@ -110,11 +115,14 @@ pub struct Config {
     pub genesis_accounts: BTreeMap<String, Account>,
     /// The validator set after genesis
     pub validator_accounts: usize,
+    /// Whether to execute the transactions between block prologues in one block
+    pub execute_in_block: bool,
 }
 
 impl Config {
     pub fn build(entries: &[Entry]) -> Result<Self> {
         let mut accounts = BTreeMap::new();
+        let mut execute_in_block = false;
 
         // key generator with a fixed seed
         // this is important as it ensures the tests are deterministic
@@ -149,6 +157,7 @@ impl Config {
                         }
                     }
                 }
+                Entry::ExecuteInBlock => execute_in_block = true,
             }
         }
 
@@ -167,6 +176,7 @@ impl Config {
             accounts,
             genesis_accounts: make_genesis_accounts(),
             validator_accounts: 0,
+            execute_in_block,
         })
     }
 
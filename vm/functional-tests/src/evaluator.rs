@@ -349,25 +349,22 @@ fn run_transaction(
     exec: &mut FakeExecutor,
     transaction: SignedUserTransaction,
 ) -> Result<TransactionOutput> {
-    let mut outputs = exec.execute_block(vec![transaction]).unwrap();
-    if outputs.len() == 1 {
-        let (vm_status, output) = outputs.pop().unwrap();
-        match output.status() {
-            TransactionStatus::Keep(status) => {
-                exec.apply_write_set(output.write_set());
-                if status == &KeptVMStatus::Executed {
-                    Ok(output)
-                } else {
-                    Err(ErrorKind::VMExecutionFailure(vm_status, output).into())
-                }
-            }
-            TransactionStatus::Discard(_status) => {
-                checked_verify!(output.write_set().is_empty());
-                Err(ErrorKind::DiscardedTransaction(output).into())
+    let (vm_status, output) = exec
+        .execute_transaction_in_block(StarcoinTransaction::UserTransaction(transaction))
+        .unwrap();
+    match output.status() {
+        TransactionStatus::Keep(status) => {
+            exec.apply_write_set(output.write_set());
+            if status == &KeptVMStatus::Executed {
+                Ok(output)
+            } else {
+                Err(ErrorKind::VMExecutionFailure(vm_status, output).into())
             }
         }
-    } else {
-        unreachable!("transaction outputs size mismatch")
+        TransactionStatus::Discard(_status) => {
+            checked_verify!(output.write_set().is_empty());
+            Err(ErrorKind::DiscardedTransaction(output).into())
+        }
     }
 }
 
@@ -526,29 +523,25 @@ pub fn eval_block_metadata(
     block_metadata: BlockMetadata,
     log: &mut EvaluationLog,
 ) -> Result<Status> {
-    let outputs = executor
-        .execute_transaction_block(vec![StarcoinTransaction::BlockMetadata(block_metadata)]);
-
-    match outputs {
-        Ok(mut outputs) => {
-            let (_vm_status, output) = outputs
-                .pop()
-                .expect("There should be one output in the result");
-            match output.status() {
-                TransactionStatus::Keep(_status) => {
-                    executor.apply_write_set(output.write_set());
-                    log.append(EvaluationOutput::Output(OutputType::TransactionOutput(
-                        Box::new(output),
-                    )));
-                    Ok(Status::Success)
-                }
-                TransactionStatus::Discard(status) => {
-                    let err: Error = ErrorKind::VerificationError(VMStatus::Error(*status)).into();
-                    log.append(EvaluationOutput::Error(Box::new(err)));
-                    Ok(Status::Failure)
-                }
+    executor.end_block();
+    let output =
+        executor.execute_transaction_in_block(StarcoinTransaction::BlockMetadata(block_metadata));
+
+    match output {
+        Ok((_vm_status, output)) => match output.status() {
+            TransactionStatus::Keep(_status) => {
+                executor.apply_write_set(output.write_set());
+                log.append(EvaluationOutput::Output(OutputType::TransactionOutput(
+                    Box::new(output),
+                )));
+                Ok(Status::Success)
             }
-        }
+            TransactionStatus::Discard(status) => {
+                let err: Error = ErrorKind::VerificationError(VMStatus::Error(*status)).into();
+                log.append(EvaluationOutput::Error(Box::new(err)));
+                Ok(Status::Failure)
+            }
+        },
         Err(err) => {
             let err: Error = ErrorKind::Other(err.to_string()).into();
             log.append(EvaluationOutput::Error(Box::new(err)));
@@ -570,6 +563,7 @@ pub fn eval<TComp: Compiler>(
     for data in config.accounts.values() {
         exec.add_account_data(&data);
     }
+    exec.set_execute_in_block(config.execute_in_block);
 
     for (idx, command) in commands.iter().enumerate() {
         match command {
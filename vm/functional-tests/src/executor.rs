@@ -3,7 +3,7 @@
 
 //! Support for running the VM to execute and verify transactions.
 use crate::account::{Account, AccountData};
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use starcoin_config::ChainNetwork;
 use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis;
@@ -12,7 +12,7 @@ use starcoin_types::write_set::{WriteOp, WriteSetMut};
 use starcoin_types::{
     access_path::AccessPath,
     block_metadata::BlockMetadata,
-    transaction::{SignedUserTransaction, Transaction, TransactionOutput},
+    transaction::{SignedUserTransaction, Transaction, TransactionOutput, TransactionStatus},
     write_set::WriteSet,
 };
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
@@ -30,6 +30,9 @@ use starcoin_vm_types::{
 pub struct FakeExecutor {
     data_store: ChainStateDB,
     block_time: u64,
+    execute_in_block: bool,
+    /// The state root at the start of current block, and the kept transactions of the block.
+    current_block: Option<(HashValue, Vec<Transaction>)>,
 }
 
 impl Default for FakeExecutor {
@@ -46,6 +49,8 @@ impl FakeExecutor {
         Self {
             data_store,
             block_time: 0,
+            execute_in_block: false,
+            current_block: None,
         }
     }
 
@@ -54,6 +59,8 @@ impl FakeExecutor {
         let mut executor = FakeExecutor {
             data_store: ChainStateDB::mock(),
             block_time: 0,
+            execute_in_block: false,
+            current_block: None,
         };
         executor.apply_write_set(write_set);
         executor
@@ -64,6 +71,8 @@ impl FakeExecutor {
         FakeExecutor {
             data_store: ChainStateDB::mock(),
             block_time: 0,
+            execute_in_block: false,
+            current_block: None,
         }
     }
 
@@ -177,6 +186,57 @@ impl FakeExecutor {
         vm.execute_transactions(&self.data_store, txn_block)
     }
 
+    /// Executes each transaction of the following blocks in the block it belongs to, so the
+    /// transaction observes the effects of the transactions before it in the same block, e.g.
+    /// the on-chain configs are loaded at the start of the block rather than the transaction.
+    pub fn set_execute_in_block(&mut self, execute_in_block: bool) {
+        self.execute_in_block = execute_in_block;
+        self.current_block = None;
+    }
+
+    /// Ends the current block, the next transaction will start a new block.
+    pub fn end_block(&mut self) {
+        self.current_block = None;
+    }
+
+    /// Executes the transaction as the last transaction of the current block.
+    ///
+    /// If not executing in block, the transaction is executed in a block by itself. Otherwise
+    /// the kept transactions of current block are re-executed before it on the state at the
+    /// start of the block. Like `execute_block`, this doesn't apply the output to the data store.
+    pub fn execute_transaction_in_block(
+        &mut self,
+        txn: Transaction,
+    ) -> Result<(VMStatus, TransactionOutput)> {
+        if !self.execute_in_block {
+            return self
+                .execute_transaction_block(vec![txn])?
+                .pop()
+                .ok_or_else(|| format_err!("A block with one transaction should have one output"));
+        }
+        if self.current_block.is_none() {
+            let state_root = self.data_store.commit()?;
+            self.data_store.flush()?;
+            self.current_block = Some((state_root, vec![]));
+        }
+        let (block_state_root, block_txns) = self
+            .current_block
+            .as_mut()
+            .expect("current block must exist");
+        let block_state = self.data_store.change_root(*block_state_root);
+        let mut txns = block_txns.clone();
+        txns.push(txn.clone());
+        let mut vm = StarcoinVM::new();
+        let (vm_status, output) = vm
+            .execute_transactions(&block_state, txns)?
+            .pop()
+            .ok_or_else(|| format_err!("Missing the output of the last transaction in block"))?;
+        if let TransactionStatus::Keep(_) = output.status() {
+            block_txns.push(txn);
+        }
+        Ok((vm_status, output))
+    }
+
     pub fn execute_transaction(&self, txn: SignedUserTransaction) -> (VMStatus, TransactionOutput) {
         let txn_block = vec![txn];
         let mut outputs = self
@@ -8,10 +8,30 @@ use crate::{
     evaluator::{eval, EvaluationOutput},
     preprocessor::{build_transactions, split_input},
 };
-use std::{env, fs::read_to_string, io::Write, iter, path::Path};
+use anyhow::{format_err, Result};
+use once_cell::sync::Lazy;
+use std::{
+    env,
+    fs::{self, read_to_string, File},
+    io::{ErrorKind, Read, Seek, SeekFrom, Write},
+    iter,
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+};
 use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
 
 pub const PRETTY: &str = "PRETTY";
+/// The file the Move VM appends its execution trace to.
+pub const MOVE_VM_TRACE: &str = "MOVE_VM_TRACE";
+/// The directory to save the VM execution trace of each test into, `MOVE_VM_TRACE` must be set
+/// too. The tests are evaluated one at a time when it's set.
+pub const MOVE_TEST_TRACE_DIR: &str = "MOVE_TEST_TRACE_DIR";
+/// The extension of the trace file of a test.
+pub const TRACE_EXTENSION: &str = "trace";
+
+// The VM trace file is shared by the whole process, so the evaluations are serialized while
+// tracing, and the trace of a test is the part appended to the file during its evaluation.
+static TRACE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 fn at_most_n_chars(s: impl IntoIterator<Item = char>, n: usize) -> String {
     let mut it = s.into_iter();
@@ -55,6 +75,59 @@ fn pretty_mode() -> bool {
     pretty == "1" || pretty == "true"
 }
 
+fn trace_file_len(trace_path: &Path) -> Result<u64> {
+    match fs::metadata(trace_path) {
+        Ok(metadata) => Ok(metadata.len()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The trace file name of the test at `test_path`, e.g. `tests__testsuite__foo.move.trace`.
+fn trace_file_name(test_path: &Path) -> String {
+    let components: Vec<_> = test_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect();
+    format!("{}.{}", components.join("__"), TRACE_EXTENSION)
+}
+
+/// Run `f`, and save the VM execution trace produced by it as the trace of the test at
+/// `test_path` if `MOVE_TEST_TRACE_DIR` is set.
+///
+/// The Move VM appends every trace line to `MOVE_VM_TRACE` while it executes, and `f` runs the
+/// transactions synchronously, so the trace of the test is complete once `f` returns. The VM
+/// exposes no way to flush its tracer, `tests/trace.rs` fails if it ever buffers the trace.
+fn with_trace<T>(test_path: &Path, f: impl FnOnce() -> T) -> Result<T> {
+    let trace_dir = match env::var_os(MOVE_TEST_TRACE_DIR) {
+        Some(trace_dir) => PathBuf::from(trace_dir),
+        None => return Ok(f()),
+    };
+    let trace_path = PathBuf::from(env::var_os(MOVE_VM_TRACE).ok_or_else(|| {
+        format_err!(
+            "{} must be set to record the trace of tests.",
+            MOVE_VM_TRACE
+        )
+    })?);
+
+    // Keep tracing the other tests if a test panicked during evaluation.
+    let _guard = TRACE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let start = trace_file_len(&trace_path)?;
+    let result = f();
+    let mut trace = vec![];
+    if start < trace_file_len(&trace_path)? {
+        let mut file = File::open(&trace_path)?;
+        file.seek(SeekFrom::Start(start))?;
+        file.read_to_end(&mut trace)?;
+    }
+    fs::create_dir_all(&trace_dir)?;
+    fs::write(trace_dir.join(trace_file_name(test_path)), trace)?;
+    Ok(result)
+}
+
 // Runs all tests under the test/testsuite directory.
 pub fn functional_tests<TComp: Compiler>(
     compiler: TComp,
@@ -68,7 +141,7 @@ pub fn functional_tests<TComp: Compiler>(
     let config = GlobalConfig::build(&config)?;
     let commands = build_transactions(&config, &transactions)?;
 
-    let log = eval(&config, compiler, &commands)?;
+    let log = with_trace(path, || eval(&config, compiler, &commands))??;
 
     let res = match_output(&log, &directives);
 
@@ -227,3 +300,24 @@ pub fn functional_tests<TComp: Compiler>(
 
     panic!("test failed")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_file_name() {
+        assert_eq!(
+            trace_file_name(Path::new("tests/testsuite/foo.move")),
+            "tests__testsuite__foo.move.trace"
+        );
+        assert_eq!(
+            trace_file_name(Path::new("./tests/testsuite/foo.move")),
+            "tests__testsuite__foo.move.trace"
+        );
+        assert_eq!(
+            trace_file_name(Path::new("/tmp/tests/../foo.move")),
+            "tmp__tests__foo.move.trace"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+// Test only the genesis account can set the gas schedule, and the new gas schedule takes effect.
+//! account: alice
+
+//! new-transaction
+//! sender: alice
+script{
+use 0x1::VMConfig;
+fun main(account: &signer) {
+    VMConfig::set_gas_schedule(account, x"", x"");
+}
+}
+
+// check: ABORTED
+// check: 24
+
+//! new-transaction
+//! sender: alice
+//! max-gas: 1000
+script{
+fun main(_account: &signer) {
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: genesis
+script{
+use 0x1::VMConfig;
+use 0x1::Vector;
+fun main(account: &signer) {
+    // The 63 instructions cost 10_000_000 instruction gas and 1 memory gas each, the table is
+    // encoded as a vector of GasCost: the ULEB128 length and the u64 pairs in little endian.
+    let instruction_schedule = Vector::singleton<u8>(63);
+    let i = 0;
+    while (i < 63) {
+        Vector::append(&mut instruction_schedule, x"80969800000000000100000000000000");
+        i = i + 1;
+    };
+    // The natives cost nothing as the genesis schedule, the table is longer than the native
+    // functions, the extra entries are not used.
+    let native_schedule = Vector::singleton<u8>(127);
+    let i = 0;
+    while (i < 127) {
+        Vector::append(&mut native_schedule, x"00000000000000000000000000000000");
+        i = i + 1;
+    };
+    VMConfig::set_gas_schedule(account, instruction_schedule, native_schedule);
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! max-gas: 1000
+script{
+fun main(_account: &signer) {
+}
+}
+
+// check: OUT_OF_GAS
+// check: gas_used
+// check: 1000
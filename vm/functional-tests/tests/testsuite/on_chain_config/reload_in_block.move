@@ -0,0 +1,117 @@
+// Test the on-chain configs changed by a transaction take effect from the next transaction
+// in the same block.
+//! execute-in-block
+//! account: alice
+
+//! new-transaction
+//! sender: alice
+script{
+use 0x1::Version;
+fun main(_account: &signer) {
+    assert(Version::get() == 1, 100);
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: genesis
+script{
+use 0x1::Version;
+fun main(account: &signer) {
+    Version::set(account, 2);
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+script{
+use 0x1::Version;
+fun main(_account: &signer) {
+    assert(Version::get() == 2, 101);
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! max-gas: 1000
+script{
+fun main(_account: &signer) {
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: genesis
+script{
+use 0x1::VMConfig;
+use 0x1::Vector;
+fun main(account: &signer) {
+    // The 63 instructions cost 2_000_000 instruction gas and 1 memory gas each, see
+    // gas_schedule.move for the encoding.
+    let instruction_schedule = Vector::singleton<u8>(63);
+    let i = 0;
+    while (i < 63) {
+        Vector::append(&mut instruction_schedule, x"80841e00000000000100000000000000");
+        i = i + 1;
+    };
+    let native_schedule = Vector::singleton<u8>(127);
+    let i = 0;
+    while (i < 127) {
+        Vector::append(&mut native_schedule, x"00000000000000000000000000000000");
+        i = i + 1;
+    };
+    VMConfig::set_gas_schedule(account, instruction_schedule, native_schedule);
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! max-gas: 1000
+script{
+fun main(_account: &signer) {
+}
+}
+
+// check: OUT_OF_GAS
+
+//! new-transaction
+//! sender: genesis
+script{
+use 0x1::VMConfig;
+fun main(account: &signer) {
+    // VMPublishingOption::Locked with an empty script whitelist.
+    VMConfig::set_publishing_option(account, x"0000");
+}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+script{
+fun main(_account: &signer) {
+}
+}
+
+// check: UNKNOWN_SCRIPT
+
+//! block-prologue
+//! proposer: alice
+//! block-time: 1000
+
+//! new-transaction
+//! sender: alice
+script{
+fun main(_account: &signer) {
+}
+}
+
+// check: UNKNOWN_SCRIPT
@@ -0,0 +1,51 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use common::{MoveSourceCompiler, FUNCTIONAL_TEST_DIR, STD_LIB_DIR};
+use starcoin_functional_tests::testsuite::{
+    functional_tests, MOVE_TEST_TRACE_DIR, MOVE_VM_TRACE, TRACE_EXTENSION,
+};
+use starcoin_move_compiler::test_utils::stdlib_files;
+use std::{env, fs, path::Path};
+
+mod common;
+
+// The Move VM reads `MOVE_VM_TRACE` once per process, so this is the only test of the binary.
+#[test]
+fn test_functional_test_trace() -> Result<()> {
+    let _log = starcoin_logger::init_for_test();
+    let temp_dir = tempfile::tempdir()?;
+    let trace_dir = temp_dir.path().join("traces");
+    env::set_var(MOVE_VM_TRACE, temp_dir.path().join("move_vm.trace"));
+    env::set_var(MOVE_TEST_TRACE_DIR, &trace_dir);
+
+    let test_path = Path::new(FUNCTIONAL_TEST_DIR).join("testsuite/dummy.move");
+    functional_tests(
+        MoveSourceCompiler::new(stdlib_files(STD_LIB_DIR)),
+        &test_path,
+    )
+    .map_err(|e| format_err!("{}", e))?;
+
+    let trace = fs::read_to_string(
+        trace_dir.join(format!("tests__testsuite__dummy.move.{}", TRACE_EXTENSION)),
+    )?;
+    // every line is `<function>,<pc>,<instruction>`, the script runs between the prologue and
+    // epilogue of the txn.
+    let functions: Vec<_> = trace
+        .lines()
+        .filter_map(|line| line.split(',').next())
+        .collect();
+    for function in &[
+        "0x1::TransactionManager::prologue",
+        "0x1::TransactionManager::epilogue",
+    ] {
+        assert!(
+            functions.contains(function),
+            "{} is not traced in:\n{}",
+            function,
+            trace
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+#! /bin/bash
+
+TRACE_PATH=$HOME/trace
+TRACE_DIR=$HOME/trace.d
+REPORT_DIR=${1:-coverage_report}
+
+[ ! -e  "$TRACE_PATH" ] || rm -f "$TRACE_PATH"
+[ ! -e  "$TRACE_DIR" ] || rm -rf "$TRACE_DIR"
+
+export MOVE_VM_TRACE=$TRACE_PATH
+export MOVE_TEST_TRACE_DIR=$TRACE_DIR
+
+echo "Rebuilding stdlib..."
+pushd ../stdlib || exit 1
+cargo run
+popd || exit 1
+
+echo "Running functional testsuite..."
+pushd ../functional-tests || exit 1
+cargo test --test testsuite
+popd || exit 1
+
+unset MOVE_VM_TRACE
+unset MOVE_TEST_TRACE_DIR
+
+echo "Producing coverage reports..."
+cargo run --bin coverage-report -- -t "$TRACE_DIR" -m trace.mvcov -o "$REPORT_DIR"
+
+echo "DONE"
@@ -0,0 +1,150 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use move_coverage::{
+    coverage_map::{output_map_to_file, CoverageMap},
+    report::{write_html_report, write_lcov, ModuleSource},
+    summary::{self, ModuleSummary, ModuleSummaryOptions},
+};
+use starcoin_move_compiler::{
+    compiled_unit::CompiledUnit, move_compile, shared::Address, test_utils::stdlib_files,
+};
+use starcoin_vm_types::file_format::FunctionDefinitionIndex;
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "Move Coverage Report",
+    about = "Creates the summary, LCOV and HTML coverage reports of the stdlib modules from the \
+             trace data collected from the Move VM"
+)]
+struct Args {
+    /// The path to the raw trace file, or the directory of raw trace files, e.g. the traces of
+    /// the functional tests
+    #[structopt(long = "input-trace-path", short = "t")]
+    pub input_trace_path: String,
+    /// The path to the source directory of the stdlib modules
+    #[structopt(
+        long = "stdlib-source-path",
+        short = "s",
+        default_value = "../stdlib/modules"
+    )]
+    pub stdlib_source_path: String,
+    /// The directory to write the reports to
+    #[structopt(long = "output-dir", short = "o", default_value = "coverage_report")]
+    pub output_dir: String,
+    /// Optional path to save the aggregated coverage map
+    #[structopt(long = "coverage-map-path", short = "m")]
+    pub coverage_map_path: Option<String>,
+}
+
+/// Compile the stdlib modules with their source maps. The traces are recorded with the staged
+/// stdlib, so the stdlib must be rebuilt before the traces are collected.
+fn compile_stdlib(stdlib_source_path: &str) -> Vec<ModuleSource> {
+    let (_, compiled_units) = move_compile(
+        &stdlib_files(stdlib_source_path),
+        &[],
+        Some(Address::LIBRA_CORE),
+    )
+    .expect("Unable to compile the stdlib");
+    compiled_units
+        .into_iter()
+        .filter_map(|compiled_unit| match compiled_unit {
+            CompiledUnit::Module {
+                module, source_map, ..
+            } => {
+                // Modules without functions have nothing to cover.
+                let source_path = source_map
+                    .get_function_source_map(FunctionDefinitionIndex(0))
+                    .ok()
+                    .map(|function_map| PathBuf::from(function_map.decl_location.file()))?;
+                Some(ModuleSource {
+                    module,
+                    source_map,
+                    source_path,
+                })
+            }
+            CompiledUnit::Script { .. } => None,
+        })
+        .collect()
+}
+
+fn write_summary<W: Write>(
+    modules: &[ModuleSource],
+    coverage_map: &CoverageMap,
+    summary_writer: &mut W,
+) -> io::Result<()> {
+    writeln!(summary_writer, "+-------------------------+")?;
+    writeln!(summary_writer, "| Move Coverage Summary   |")?;
+    writeln!(summary_writer, "+-------------------------+")?;
+
+    let mut total_covered = 0;
+    let mut total_instructions = 0;
+    for module_source in modules {
+        let (total, covered) = ModuleSummary::new(
+            ModuleSummaryOptions {
+                summarize_function_coverage: true,
+            },
+            &module_source.module,
+            coverage_map,
+        )
+        .summarize_human(summary_writer)?;
+        total_covered += covered;
+        total_instructions += total;
+    }
+
+    writeln!(summary_writer, "+-------------------------+")?;
+    writeln!(
+        summary_writer,
+        "| % Move Coverage: {:.2}  |",
+        summary::percent_coverage_for_counts(total_instructions, total_covered)
+    )?;
+    writeln!(summary_writer, "+-------------------------+")
+}
+
+fn main() {
+    let args = Args::from_args();
+    let coverage_map = CoverageMap::from_trace_path(&args.input_trace_path);
+    if let Some(coverage_map_path) = &args.coverage_map_path {
+        output_map_to_file(coverage_map_path, &coverage_map)
+            .expect("Unable to serialize coverage map to output file");
+    }
+
+    let modules = compile_stdlib(&args.stdlib_source_path);
+    let output_dir = Path::new(&args.output_dir);
+    fs::create_dir_all(output_dir).unwrap();
+
+    write_summary(&modules, &coverage_map, &mut io::stdout()).unwrap();
+    let mut csv_writer = File::create(output_dir.join("summary.csv")).unwrap();
+    writeln!(csv_writer, "ModuleName,FunctionName,Covered,Uncovered").unwrap();
+    for module_source in modules.iter() {
+        ModuleSummary::new(
+            ModuleSummaryOptions {
+                summarize_function_coverage: true,
+            },
+            &module_source.module,
+            &coverage_map,
+        )
+        .summarize_csv(&mut csv_writer)
+        .unwrap();
+    }
+
+    let lcov_path = output_dir.join("lcov.info");
+    write_lcov(
+        &mut File::create(&lcov_path).unwrap(),
+        &modules,
+        &coverage_map,
+    )
+    .unwrap();
+    write_html_report(output_dir, &modules, &coverage_map).unwrap();
+
+    println!("LCOV report: {}", lcov_path.display());
+    println!("HTML report: {}", output_dir.join("index.html").display());
+}
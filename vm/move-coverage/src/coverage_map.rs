@@ -9,7 +9,7 @@ use starcoin_types::account_address::AccountAddress;
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use std::{
     collections::BTreeMap,
-    fs::File,
+    fs::{self, File},
     io::{BufRead, BufReader, Read, Write},
     path::Path,
 };
@@ -63,6 +63,35 @@ impl CoverageMap {
         empty_module_map.update_coverage_from_trace_file(filename)
     }
 
+    /// Takes in a directory of raw VM trace files, e.g. the traces of the functional tests, and
+    /// returns an updated coverage map.
+    pub fn update_coverage_from_trace_dir<P: AsRef<Path>>(self, dirname: P) -> Self {
+        let mut trace_files: Vec<_> = fs::read_dir(dirname)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.is_file())
+            .collect();
+        trace_files.sort();
+        trace_files
+            .into_iter()
+            .fold(self, |coverage_map, trace_file| {
+                coverage_map.update_coverage_from_trace_file(trace_file)
+            })
+    }
+
+    /// Takes in a raw VM trace file, or a directory of raw VM trace files, and returns a coverage
+    /// map.
+    pub fn from_trace_path<P: AsRef<Path>>(path: P) -> Self {
+        let empty_module_map = CoverageMap {
+            module_maps: BTreeMap::new(),
+        };
+        if path.as_ref().is_dir() {
+            empty_module_map.update_coverage_from_trace_dir(path)
+        } else {
+            empty_module_map.update_coverage_from_trace_file(path)
+        }
+    }
+
     /// Takes in a file containing a serialized coverage map and returns a coverage map.
     pub fn from_binary_file<P: AsRef<Path>>(filename: P) -> Self {
         let mut bytes = Vec::new();
@@ -106,3 +135,42 @@ pub fn output_map_to_file<M: Serialize, P: AsRef<Path>>(file_name: P, data: &M)
     file.write_all(&bytes)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_trace_dir() {
+        let trace_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            trace_dir.path().join("a.trace"),
+            "0x1::M::f,0\n0x1::M::f,1\nmain::main,0\n",
+        )
+        .unwrap();
+        fs::write(
+            trace_dir.path().join("b.trace"),
+            "0x1::M::f,0\n0x1::N::g,2\n",
+        )
+        .unwrap();
+        // Sub directories are not traces.
+        fs::create_dir(trace_dir.path().join("c")).unwrap();
+
+        let coverage_map = CoverageMap::from_trace_path(trace_dir.path());
+        // Scripts are not counted.
+        assert_eq!(coverage_map.module_maps.len(), 2);
+
+        let addr = AccountAddress::from_hex_literal("0x1").unwrap();
+        let m = &coverage_map.module_maps[&(addr, Identifier::new("M").unwrap())];
+        let f_coverage = m
+            .get_function_coverage(&Identifier::new("f").unwrap())
+            .unwrap();
+        assert_eq!(f_coverage.get(&0), Some(&2));
+        assert_eq!(f_coverage.get(&1), Some(&1));
+        let n = &coverage_map.module_maps[&(addr, Identifier::new("N").unwrap())];
+        let g_coverage = n
+            .get_function_coverage(&Identifier::new("g").unwrap())
+            .unwrap();
+        assert_eq!(g_coverage.get(&2), Some(&1));
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::coverage_map::CoverageMap;
+use crate::source_coverage::{SourceCoverageBuilder, StringSegment};
+use crate::summary::{percent_coverage_for_counts, ModuleSummary, ModuleSummaryOptions};
+use anyhow::{format_err, Result};
+use bytecode_source_map::source_map::SourceMap;
+use codespan::Files;
+use starcoin_vm_types::location::Loc;
+use starcoin_vm_types::{
+    access::ModuleAccess,
+    file_format::{CodeOffset, CompiledModule, FunctionDefinitionIndex},
+};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// A compiled module with its source map and the path of its source file.
+pub struct ModuleSource {
+    pub module: CompiledModule,
+    pub source_map: SourceMap<Loc>,
+    pub source_path: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct FunctionLineCoverage {
+    /// The name of the function, qualified by the module name.
+    pub name: String,
+    pub decl_line: u32,
+    /// The execution count of the first instruction, i.e. the number of calls.
+    pub hits: u64,
+}
+
+/// The coverage of the source lines of a module, the lines are 1-based as in LCOV.
+#[derive(Debug)]
+pub struct LineCoverage {
+    pub functions: Vec<FunctionLineCoverage>,
+    /// The execution count of each line with instructions, which is the max execution count of
+    /// the instructions of the line.
+    pub lines: BTreeMap<u32, u64>,
+}
+
+impl LineCoverage {
+    pub fn new(module_source: &ModuleSource, coverage_map: &CoverageMap) -> Result<Self> {
+        let module = &module_source.module;
+        let source_map = &module_source.source_map;
+        let mut files = Files::new();
+        let file_id = files.add(
+            module_source.source_path.as_os_str().to_os_string(),
+            fs::read_to_string(&module_source.source_path)?,
+        );
+        let line_of = |loc: Loc| -> Result<u32> {
+            files
+                .location(file_id, loc.span().start())
+                .map(|location| location.line.0 + 1)
+                .map_err(|e| format_err!("Invalid source location {:?}: {:?}", loc, e))
+        };
+
+        let module_name = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_name.address(), module_name.name().to_owned()));
+
+        let mut functions = vec![];
+        let mut lines = BTreeMap::new();
+        for (function_def_idx, function_def) in module.function_defs().iter().enumerate() {
+            let code_unit = match &function_def.code {
+                Some(code_unit) => code_unit,
+                None => continue,
+            };
+            let fn_handle = module.function_handle_at(function_def.function);
+            let fn_name = module.identifier_at(fn_handle.name);
+            let function_def_idx = FunctionDefinitionIndex(function_def_idx as u16);
+            let function_coverage =
+                module_map.and_then(|fn_map| fn_map.get_function_coverage(fn_name));
+            let hits = |code_offset: u64| {
+                function_coverage
+                    .and_then(|function_coverage| function_coverage.get(&code_offset))
+                    .cloned()
+                    .unwrap_or(0)
+            };
+
+            let function_map = source_map.get_function_source_map(function_def_idx)?;
+            functions.push(FunctionLineCoverage {
+                name: format!("{}::{}", module_name.name(), fn_name),
+                decl_line: line_of(function_map.decl_location)?,
+                hits: hits(0),
+            });
+            for code_offset in 0..code_unit.code.len() {
+                let loc =
+                    source_map.get_code_location(function_def_idx, code_offset as CodeOffset)?;
+                let line_hits = lines.entry(line_of(loc)?).or_insert(0);
+                *line_hits = (*line_hits).max(hits(code_offset as u64));
+            }
+        }
+
+        Ok(Self { functions, lines })
+    }
+}
+
+/// Write the LCOV tracefile of `modules`, one record per module.
+pub fn write_lcov<W: Write>(
+    writer: &mut W,
+    modules: &[ModuleSource],
+    coverage_map: &CoverageMap,
+) -> Result<()> {
+    for module_source in modules {
+        let coverage = LineCoverage::new(module_source, coverage_map)?;
+        writeln!(writer, "SF:{}", module_source.source_path.display())?;
+        for function in &coverage.functions {
+            writeln!(writer, "FN:{},{}", function.decl_line, function.name)?;
+        }
+        for function in &coverage.functions {
+            writeln!(writer, "FNDA:{},{}", function.hits, function.name)?;
+        }
+        writeln!(writer, "FNF:{}", coverage.functions.len())?;
+        writeln!(
+            writer,
+            "FNH:{}",
+            coverage.functions.iter().filter(|f| f.hits > 0).count()
+        )?;
+        for (line, hits) in &coverage.lines {
+            writeln!(writer, "DA:{},{}", line, hits)?;
+        }
+        writeln!(writer, "LF:{}", coverage.lines.len())?;
+        writeln!(
+            writer,
+            "LH:{}",
+            coverage.lines.values().filter(|hits| **hits > 0).count()
+        )?;
+        writeln!(writer, "end_of_record")?;
+    }
+    Ok(())
+}
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 2px 8px; text-align: left; }
+pre { font-family: monospace; }
+.line-number { color: #999; }
+.uncovered { background-color: #fdd; }";
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_html_header<W: Write>(writer: &mut W, title: &str) -> Result<()> {
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html>")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>{}</title>", escape_html(title))?;
+    writeln!(writer, "<style>\n{}\n</style>", HTML_STYLE)?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>{}</h1>", escape_html(title))?;
+    Ok(())
+}
+
+fn write_html_footer<W: Write>(writer: &mut W) -> Result<()> {
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+}
+
+/// Write the HTML report of `modules` to `output_dir`, an `index.html` with the coverage summary
+/// of each module, and a page with the function summaries and the annotated source of each module.
+pub fn write_html_report(
+    output_dir: &Path,
+    modules: &[ModuleSource],
+    coverage_map: &CoverageMap,
+) -> Result<()> {
+    fs::create_dir_all(output_dir)?;
+    let mut index = File::create(output_dir.join("index.html"))?;
+    write_html_header(&mut index, "Move Coverage Summary")?;
+    writeln!(index, "<table>")?;
+    writeln!(
+        index,
+        "<tr><th>Module</th><th>Covered</th><th>Total</th><th>% Coverage</th></tr>"
+    )?;
+
+    let mut total_covered = 0;
+    let mut total_instructions = 0;
+    for module_source in modules {
+        let module_name = module_source.module.self_id();
+        let summary = ModuleSummary::new(
+            ModuleSummaryOptions {
+                summarize_function_coverage: true,
+            },
+            &module_source.module,
+            coverage_map,
+        );
+        let (total, covered) = summary.function_summaries.values().fold(
+            (0, 0),
+            |(total, covered), function_summary| {
+                (
+                    total + function_summary.total_number_of_instructions,
+                    covered + function_summary.covered_instructions,
+                )
+            },
+        );
+        total_covered += covered;
+        total_instructions += total;
+
+        let page = format!("{}_{}.html", module_name.address(), module_name.name());
+        writeln!(
+            index,
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td>{:.2}</td></tr>",
+            page,
+            escape_html(module_name.name().as_str()),
+            covered,
+            total,
+            percent_coverage_for_counts(total, covered)
+        )?;
+        write_module_page(
+            &output_dir.join(page),
+            module_source,
+            &summary,
+            coverage_map,
+        )?;
+    }
+
+    writeln!(
+        index,
+        "<tr><th>Total</th><th>{}</th><th>{}</th><th>{:.2}</th></tr>",
+        total_covered,
+        total_instructions,
+        percent_coverage_for_counts(total_instructions, total_covered)
+    )?;
+    writeln!(index, "</table>")?;
+    write_html_footer(&mut index)
+}
+
+fn write_module_page(
+    path: &Path,
+    module_source: &ModuleSource,
+    summary: &ModuleSummary,
+    coverage_map: &CoverageMap,
+) -> Result<()> {
+    let mut page = File::create(path)?;
+    let module_name = &summary.module_name;
+    write_html_header(
+        &mut page,
+        format!("{}::{}", module_name.address(), module_name.name()).as_str(),
+    )?;
+    writeln!(page, "<p><a href=\"index.html\">Summary</a></p>")?;
+
+    writeln!(page, "<table>")?;
+    writeln!(
+        page,
+        "<tr><th>Function</th><th>Covered</th><th>Total</th><th>% Coverage</th></tr>"
+    )?;
+    for (fn_name, function_summary) in &summary.function_summaries {
+        if function_summary.fn_is_native {
+            writeln!(
+                page,
+                "<tr><td>{}</td><td colspan=\"3\">native</td></tr>",
+                escape_html(fn_name.as_str())
+            )?;
+        } else {
+            writeln!(
+                page,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td></tr>",
+                escape_html(fn_name.as_str()),
+                function_summary.covered_instructions,
+                function_summary.total_number_of_instructions,
+                function_summary.percent_coverage()
+            )?;
+        }
+    }
+    writeln!(page, "</table>")?;
+
+    writeln!(page, "<h2>{}</h2>", module_source.source_path.display())?;
+    writeln!(page, "<pre>")?;
+    let source_coverage = SourceCoverageBuilder::new(
+        &module_source.module,
+        coverage_map,
+        &module_source.source_map,
+    )
+    .compute_source_coverage(&module_source.source_path);
+    for (line_number, line) in source_coverage.annotated_lines.iter().enumerate() {
+        write!(
+            page,
+            "<span class=\"line-number\">{:>5}</span> ",
+            line_number + 1
+        )?;
+        for string_segment in line {
+            match string_segment {
+                StringSegment::Covered(s) => write!(page, "{}", escape_html(s))?,
+                StringSegment::Uncovered(s) => {
+                    write!(page, "<span class=\"uncovered\">{}</span>", escape_html(s))?
+                }
+            }
+        }
+        writeln!(page)?;
+    }
+    writeln!(page, "</pre>")?;
+    write_html_footer(&mut page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage_map::ModuleCoverageMap;
+    use starcoin_move_compiler::{compiled_unit::CompiledUnit, move_compile};
+    use starcoin_vm_types::identifier::Identifier;
+
+    const MODULE_SOURCE: &str = "address 0x1 {
+module M {
+    public fun f(x: u64): u64 {
+        if (x > 0) {
+            x
+        } else {
+            0
+        }
+    }
+
+    public fun g() {}
+}
+}
+";
+
+    fn compile_module(source_path: &Path) -> ModuleSource {
+        let (_, mut compiled_units) =
+            move_compile(&[source_path.to_str().unwrap().to_string()], &[], None).unwrap();
+        match compiled_units.pop().unwrap() {
+            CompiledUnit::Module {
+                module, source_map, ..
+            } => ModuleSource {
+                module,
+                source_map,
+                source_path: source_path.to_path_buf(),
+            },
+            CompiledUnit::Script { .. } => panic!("Expected a module"),
+        }
+    }
+
+    #[test]
+    fn test_write_lcov() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_path = temp_dir.path().join("M.move");
+        fs::write(&source_path, MODULE_SOURCE).unwrap();
+        let module_source = compile_module(&source_path);
+
+        // Execute every instruction of `f` once, and never call `g`.
+        let module_id = module_source.module.self_id();
+        let mut module_map =
+            ModuleCoverageMap::new(*module_id.address(), module_id.name().to_owned());
+        let f = Identifier::new("f").unwrap();
+        let f_def = module_source
+            .module
+            .function_defs()
+            .iter()
+            .find(|function_def| {
+                let fn_handle = module_source
+                    .module
+                    .function_handle_at(function_def.function);
+                module_source.module.identifier_at(fn_handle.name) == f.as_ident_str()
+            })
+            .unwrap();
+        for pc in 0..f_def.code.as_ref().unwrap().code.len() {
+            module_map.insert(f.clone(), pc as u64);
+        }
+        let mut module_maps = BTreeMap::new();
+        module_maps.insert(
+            (*module_id.address(), module_id.name().to_owned()),
+            module_map,
+        );
+        let coverage_map = CoverageMap { module_maps };
+
+        let mut output = vec![];
+        write_lcov(&mut output, &[module_source], &coverage_map).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let records: Vec<_> = output.lines().collect();
+
+        assert_eq!(records[0], format!("SF:{}", source_path.display()));
+        assert_eq!(
+            &records[1..7],
+            &[
+                "FN:3,M::f",
+                "FN:11,M::g",
+                "FNDA:1,M::f",
+                "FNDA:0,M::g",
+                "FNF:2",
+                "FNH:1",
+            ]
+        );
+        assert_eq!(records.last(), Some(&"end_of_record"));
+
+        // The lines of `f` (3 to 9) are all covered, the line of `g` is not.
+        let lines: Vec<(u32, u64)> = records
+            .iter()
+            .filter(|record| record.starts_with("DA:"))
+            .map(|record| {
+                let mut splits = record["DA:".len()..].split(',');
+                (
+                    splits.next().unwrap().parse().unwrap(),
+                    splits.next().unwrap().parse().unwrap(),
+                )
+            })
+            .collect();
+        assert!(lines.iter().any(|(line, _)| (3..=9).contains(line)));
+        assert!(lines.contains(&(11, 0)));
+        for (line, hits) in &lines {
+            assert_eq!(*hits, if *line <= 9 { 1 } else { 0 }, "line {}", line);
+        }
+        let covered = lines.iter().filter(|(_, hits)| *hits > 0).count();
+        assert!(records.contains(&format!("LF:{}", lines.len()).as_str()));
+        assert!(records.contains(&format!("LH:{}", covered).as_str()));
+    }
+}
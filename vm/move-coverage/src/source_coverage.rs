@@ -74,12 +74,12 @@ impl SourceCoverageBuilder {
 
                 // If the function summary doesn't exist then that function hasn't been called yet.
                 let coverage = match &function_def.code {
-                    None => Some(FunctionSourceCoverage {
+                    None => FunctionSourceCoverage {
                         fn_is_native: true,
                         uncovered_locations: Vec::new(),
-                    }),
+                    },
                     Some(code_unit) => {
-                        module_map.map(|fn_map| match fn_map.function_maps.get(&fn_name) {
+                        match module_map.and_then(|fn_map| fn_map.function_maps.get(&fn_name)) {
                             None => {
                                 let function_map = source_map
                                     .get_function_source_map(function_def_idx)
@@ -114,12 +114,11 @@ impl SourceCoverageBuilder {
                                     uncovered_locations,
                                 }
                             }
-                        })
+                        }
                     }
                 };
-                coverage.map(|x| (fn_name, x))
+                (fn_name, coverage)
             })
-            .flatten()
             .collect();
 
         Self {
@@ -4,6 +4,7 @@ module VMConfig {
     use 0x1::Config;
     use 0x1::Signer;
     use 0x1::CoreAddresses;
+    use 0x1::Vector;
 
     // The struct to hold all config data needed to operate the VM.
     // * publishing_option: Defines Scripts/Modules that are allowed to execute in the current configruation.
@@ -104,6 +105,98 @@ module VMConfig {
         current_config.publishing_option = publishing_option;
         Config::set<VMConfig>(account, current_config);
     }
+
+    // Set the instruction and native schedules of the gas schedule, both are the SCS bytes of the
+    // cost vector sorted by instruction/native order.
+    public fun set_gas_schedule(account: &signer, instruction_schedule: vector<u8>, native_schedule: vector<u8>) {
+        let current_config = Config::get<VMConfig>(account);
+        current_config.gas_schedule.instruction_schedule = instruction_schedule;
+        current_config.gas_schedule.native_schedule = native_schedule;
+        Config::set<VMConfig>(account, current_config);
+    }
+
+    // Add `script_hash`, the sha3-256 hash of a script, to the whitelist of the locked publishing
+    // option. Aborts if the publishing option is not locked, or the whitelist already contains
+    // `script_hash`.
+    public fun add_to_script_whitelist(account: &signer, script_hash: vector<u8>) {
+        assert(Vector::length(&script_hash) == 32, 3);
+        let current_config = Config::get<VMConfig>(account);
+        let script_hashes = locked_script_hashes(*&current_config.publishing_option);
+        let (found, _) = index_of_script_hash(&script_hashes, &script_hash);
+        assert(!found, 4);
+        Vector::append(&mut script_hashes, script_hash);
+        current_config.publishing_option = locked_publishing_option(script_hashes);
+        Config::set<VMConfig>(account, current_config);
+    }
+
+    // Remove `script_hash` from the whitelist of the locked publishing option. Aborts if the
+    // publishing option is not locked, or the whitelist does not contain `script_hash`.
+    public fun remove_from_script_whitelist(account: &signer, script_hash: vector<u8>) {
+        assert(Vector::length(&script_hash) == 32, 3);
+        let current_config = Config::get<VMConfig>(account);
+        let script_hashes = locked_script_hashes(*&current_config.publishing_option);
+        let (found, index) = index_of_script_hash(&script_hashes, &script_hash);
+        assert(found, 5);
+        let len = Vector::length(&script_hashes);
+        let remaining = Vector::empty<u8>();
+        let i = 0;
+        while (i < len) {
+            if (i < index * 32 || i >= index * 32 + 32) {
+                Vector::push_back(&mut remaining, *Vector::borrow(&script_hashes, i));
+            };
+            i = i + 1;
+        };
+        current_config.publishing_option = locked_publishing_option(remaining);
+        Config::set<VMConfig>(account, current_config);
+    }
+
+    // The SCS bytes of a `Locked` publishing option are the variant index 0, the ULEB128 length
+    // of the whitelist, and the 32 bytes script hashes. Strip the variant index and the length,
+    // and return the concatenated script hashes.
+    fun locked_script_hashes(publishing_option: vector<u8>): vector<u8> {
+        assert(*Vector::borrow(&publishing_option, 0) == 0, 2);
+        let i = 1;
+        while (*Vector::borrow(&publishing_option, i) >= 128) {
+            i = i + 1;
+        };
+        i = i + 1;
+        let len = Vector::length(&publishing_option);
+        let script_hashes = Vector::empty<u8>();
+        while (i < len) {
+            Vector::push_back(&mut script_hashes, *Vector::borrow(&publishing_option, i));
+            i = i + 1;
+        };
+        script_hashes
+    }
+
+    // Encode the concatenated `script_hashes` as the SCS bytes of a `Locked` publishing option.
+    fun locked_publishing_option(script_hashes: vector<u8>): vector<u8> {
+        let publishing_option = Vector::singleton<u8>(0);
+        let len = Vector::length(&script_hashes) / 32;
+        while (len >= 128) {
+            Vector::push_back(&mut publishing_option, ((len % 128) as u8) + 128);
+            len = len / 128;
+        };
+        Vector::push_back(&mut publishing_option, (len as u8));
+        Vector::append(&mut publishing_option, script_hashes);
+        publishing_option
+    }
+
+    // Return (true, i) if `script_hash` is the `i`th script hash of the concatenated
+    // `script_hashes`, otherwise returns (false, 0).
+    fun index_of_script_hash(script_hashes: &vector<u8>, script_hash: &vector<u8>): (bool, u64) {
+        let len = Vector::length(script_hashes) / 32;
+        let i = 0;
+        while (i < len) {
+            let j = 0;
+            while (j < 32 && *Vector::borrow(script_hashes, i * 32 + j) == *Vector::borrow(script_hash, j)) {
+                j = j + 1;
+            };
+            if (j == 32) return (true, i);
+            i = i + 1;
+        };
+        (false, 0)
+    }
 }
 
 }
@@ -0,0 +1,213 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate the typed Rust builders of the stdlib transaction scripts, from the compiled scripts
+//! and the parameter names in the script sources.
+
+use crate::transaction_scripts::StdlibScript;
+use crate::{MOVE_EXTENSION, STAGED_EXTENSION};
+use anyhow::{bail, format_err, Result};
+use starcoin_move_compiler::{script_parameter_names, script_type_parameter_names};
+use starcoin_vm_types::abi::{ScriptABI, TypeABI};
+use starcoin_vm_types::file_format::CompiledScript;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// The output path of the generated builders, relative to the stdlib crate.
+pub const GENERATED_BUILDERS_PATH: &str = "../transaction-builder/src/generated.rs";
+
+const HEADER: &str = "// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is generated from the stdlib transaction scripts by running `cargo run` in vm/stdlib,
+// do not edit it by hand.
+
+use crate::StdlibScript;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::language_storage::TypeTag;
+use starcoin_vm_types::transaction::{Script, TransactionArgument};
+";
+
+/// Generate the source of the Rust builders of all the `StdlibScript`s, the compiled scripts are
+/// read from `staged_scripts_dir` and the sources from `scripts_dir`.
+pub fn generate_rust_builders(scripts_dir: &Path, staged_scripts_dir: &Path) -> Result<String> {
+    let mut out = HEADER.to_string();
+    for script in StdlibScript::all() {
+        let source = std::fs::read_to_string(script_path(scripts_dir, script, MOVE_EXTENSION))?;
+        let bytecode = std::fs::read(script_path(staged_scripts_dir, script, STAGED_EXTENSION))?;
+        let compiled_script = CompiledScript::deserialize(bytecode.as_slice())
+            .map_err(|e| format_err!("Invalid script {}: {:?}", script, e))?;
+        let abi = ScriptABI::new(&compiled_script)
+            .with_type_parameter_names(script_type_parameter_names(source.as_str())?)?
+            .with_parameter_names(script_parameter_names(source.as_str())?)?;
+        out.push('\n');
+        write_rust_builder(&mut out, script, &abi, doc_lines(source.as_str()))?;
+    }
+    Ok(out)
+}
+
+fn script_path(dir: &Path, script: StdlibScript, extension: &str) -> PathBuf {
+    let mut path = dir.join(script.name());
+    path.set_extension(extension);
+    path
+}
+
+/// The `//` comment lines directly before the script function.
+fn doc_lines(source: &str) -> Vec<String> {
+    let lines: Vec<&str> = source.lines().map(|line| line.trim()).collect();
+    let fun = match lines.iter().position(|line| line.starts_with("fun ")) {
+        Some(fun) => fun,
+        None => return vec![],
+    };
+    let start = lines[..fun]
+        .iter()
+        .rposition(|line| !line.starts_with("//"))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    lines[start..fun]
+        .iter()
+        .map(|line| {
+            let line = line.trim_start_matches('/');
+            line.strip_prefix(' ')
+                .unwrap_or(line)
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Convert a `CamelCase` type parameter name to a `snake_case` parameter name.
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.char_indices() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// The Rust type and the `TransactionArgument` variant of a script parameter type.
+fn rust_type(type_abi: &TypeABI) -> Result<(&'static str, &'static str)> {
+    Ok(match type_abi {
+        TypeABI::Bool => ("bool", "Bool"),
+        TypeABI::U8 => ("u8", "U8"),
+        TypeABI::U64 => ("u64", "U64"),
+        TypeABI::U128 => ("u128", "U128"),
+        TypeABI::Address => ("AccountAddress", "Address"),
+        TypeABI::Vector(element) if element.as_ref() == &TypeABI::U8 => ("Vec<u8>", "U8Vector"),
+        _ => bail!("Unsupported script parameter type {:?}", type_abi),
+    })
+}
+
+fn write_rust_builder(
+    out: &mut String,
+    script: StdlibScript,
+    abi: &ScriptABI,
+    doc: Vec<String>,
+) -> Result<()> {
+    let name = script.name();
+    let function_name = if name.ends_with("_script") {
+        format!("encode_{}", name)
+    } else {
+        format!("encode_{}_script", name)
+    };
+    let type_args: Vec<String> = abi
+        .type_parameters
+        .iter()
+        .map(|type_parameter| snake_case(type_parameter.name.as_str()))
+        .collect();
+    let mut params: Vec<String> = type_args
+        .iter()
+        .map(|type_arg| format!("{}: TypeTag", type_arg))
+        .collect();
+    let mut args = vec![];
+    for parameter in abi.transaction_parameters() {
+        let (ty, variant) = rust_type(&parameter.type_abi)?;
+        params.push(format!("{}: {}", parameter.name, ty));
+        args.push(format!(
+            "TransactionArgument::{}({})",
+            variant, parameter.name
+        ));
+    }
+
+    writeln!(out, "/// Encode the `{}` script.", name)?;
+    if !doc.is_empty() {
+        writeln!(out, "///")?;
+        for line in doc {
+            if line.is_empty() {
+                writeln!(out, "///")?;
+            } else {
+                writeln!(out, "/// {}", line)?;
+            }
+        }
+    }
+    if params.is_empty() {
+        writeln!(out, "pub fn {}() -> Script {{", function_name)?;
+    } else {
+        writeln!(out, "pub fn {}(", function_name)?;
+        for param in params {
+            writeln!(out, "    {},", param)?;
+        }
+        writeln!(out, ") -> Script {{")?;
+    }
+    writeln!(out, "    Script::new(")?;
+    writeln!(
+        out,
+        "        StdlibScript::{:?}.compiled_bytes().into_vec(),",
+        script
+    )?;
+    write_vec(out, type_args)?;
+    write_vec(out, args)?;
+    writeln!(out, "    )")?;
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+fn write_vec(out: &mut String, items: Vec<String>) -> Result<()> {
+    if items.is_empty() {
+        writeln!(out, "        vec![],")?;
+    } else {
+        writeln!(out, "        vec![")?;
+        for item in items {
+            writeln!(out, "            {},", item)?;
+        }
+        writeln!(out, "        ],")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{STAGED_OUTPUT_PATH, TRANSACTION_SCRIPTS};
+
+    #[test]
+    fn test_generated_builders_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let generated = generate_rust_builders(
+            root.join(TRANSACTION_SCRIPTS).as_path(),
+            root.join(STAGED_OUTPUT_PATH)
+                .join(TRANSACTION_SCRIPTS)
+                .as_path(),
+        )
+        .unwrap();
+        let expected = std::fs::read_to_string(root.join(GENERATED_BUILDERS_PATH)).unwrap();
+        assert!(
+            generated == expected,
+            "The generated transaction builders are out of date, run `cargo run` in vm/stdlib \
+             to regenerate them."
+        );
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("TokenType"), "token_type");
+        assert_eq!(snake_case("T"), "t");
+    }
+}
@@ -17,6 +17,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub mod builder_generator;
 pub mod init_scripts;
 pub mod transaction_scripts;
 
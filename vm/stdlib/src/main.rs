@@ -8,6 +8,7 @@ use std::{
     io::Write,
     path::{Path, PathBuf},
 };
+use stdlib::builder_generator::{generate_rust_builders, GENERATED_BUILDERS_PATH};
 use stdlib::{
     build_stdlib, build_stdlib_doc, build_transaction_script_doc, compile_script,
     filter_move_files, save_binary, INIT_SCRIPTS, STAGED_EXTENSION, STAGED_OUTPUT_PATH,
@@ -58,6 +59,11 @@ fn main() {
     compile_scripts(Path::new(INIT_SCRIPTS));
     compile_scripts(Path::new(TRANSACTION_SCRIPTS));
 
+    // Generate the transaction builders of the scripts
+    let builders =
+        generate_rust_builders(Path::new(TRANSACTION_SCRIPTS), txn_scripts_path.as_path()).unwrap();
+    save_binary(Path::new(GENERATED_BUILDERS_PATH), builders.as_bytes());
+
     // Generate documentation
     std::fs::remove_dir_all(&STD_LIB_DOC_DIR).unwrap_or(());
     std::fs::create_dir_all(&STD_LIB_DOC_DIR).unwrap();
@@ -24,12 +24,19 @@ const STAGED_TXN_SCRIPTS_DIR: Dir = include_dir!("staged/transaction_scripts");
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum StdlibScript {
     AcceptToken,
+    AddToScriptWhitelist,
+    CancelUpgradePlan,
     CreateAccount,
     EmptyScript,
     Mint,
     PeerToPeer,
     PeerToPeerWithMetadata,
     PublishSharedEd2551PublicKey,
+    RemoveFromScriptWhitelist,
+    SetGasSchedule,
+    SetPublishingOption,
+    SubmitUpgradePlan,
+    UpdateModuleUpgradeStrategy,
     // ...add new scripts here
 }
 
@@ -40,12 +47,19 @@ impl StdlibScript {
         use StdlibScript::*;
         vec![
             AcceptToken,
+            AddToScriptWhitelist,
+            CancelUpgradePlan,
             CreateAccount,
             EmptyScript,
             Mint,
             PeerToPeer,
             PeerToPeerWithMetadata,
             PublishSharedEd2551PublicKey,
+            RemoveFromScriptWhitelist,
+            SetGasSchedule,
+            SetPublishingOption,
+            SubmitUpgradePlan,
+            UpdateModuleUpgradeStrategy,
             // ...add new scripts here
         ]
     }
@@ -59,6 +73,15 @@ impl StdlibScript {
             .collect()
     }
 
+    /// Return the hashes of all the standard library scripts with their names, the hashes are
+    /// the entries of the whitelist in a `VMPublishingOption::Locked`
+    pub fn hashes() -> Vec<(Self, HashValue)> {
+        StdlibScript::all()
+            .into_iter()
+            .map(|script| (script, script.hash()))
+            .collect()
+    }
+
     /// Return a lowercase-underscore style name for this script
     pub fn name(self) -> String {
         self.to_string()
@@ -134,12 +157,19 @@ impl fmt::Display for StdlibScript {
             "{}",
             match self {
                 AcceptToken => "accept_token",
+                AddToScriptWhitelist => "add_to_script_whitelist",
+                CancelUpgradePlan => "cancel_upgrade_plan",
                 CreateAccount => "create_account",
                 EmptyScript => "empty_script",
                 Mint => "mint",
                 PeerToPeer => "peer_to_peer",
                 PeerToPeerWithMetadata => "peer_to_peer_with_metadata",
                 PublishSharedEd2551PublicKey => "publish_shared_ed25519_public_key",
+                RemoveFromScriptWhitelist => "remove_from_script_whitelist",
+                SetGasSchedule => "set_gas_schedule",
+                SetPublishingOption => "set_publishing_option",
+                SubmitUpgradePlan => "submit_upgrade_plan",
+                UpdateModuleUpgradeStrategy => "update_module_upgrade_strategy",
             }
         )
     }
@@ -0,0 +1,9 @@
+script {
+use 0x1::PackageTxnManager;
+
+// Cancel the pending upgrade plan of the sender's code space.
+// Aborts if the sender's module upgrade strategy is not two phase upgrade, or no plan exists.
+fun main(account: &signer) {
+    PackageTxnManager::cancel_upgrade_plan(account)
+}
+}
@@ -0,0 +1,13 @@
+script {
+use 0x1::VMConfig;
+
+// Remove `script_hash`, the sha3-256 hash of a script, from the whitelist of the locked
+// publishing option of the on-chain VMConfig.
+// Aborts if the sender is not the genesis account, the publishing option is not locked, or the
+// whitelist does not contain `script_hash`.
+// The committed generated genesis predates `VMConfig::remove_from_script_whitelist`, chains
+// started from them need a stdlib upgrade (`dev upgrade_stdlib`) before executing the script.
+fun main(account: &signer, script_hash: vector<u8>) {
+    VMConfig::remove_from_script_whitelist(account, script_hash)
+}
+}
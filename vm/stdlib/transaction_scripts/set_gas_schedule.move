@@ -0,0 +1,10 @@
+script {
+use 0x1::VMConfig;
+
+// Set the instruction and native schedules of the on-chain gas schedule, both are the SCS bytes
+// of the cost vector of the `CostTable`.
+// Aborts if the sender is not the genesis account.
+fun main(account: &signer, instruction_schedule: vector<u8>, native_schedule: vector<u8>) {
+    VMConfig::set_gas_schedule(account, instruction_schedule, native_schedule)
+}
+}
@@ -0,0 +1,10 @@
+script {
+use 0x1::VMConfig;
+
+// Set the publishing option of the on-chain VMConfig to `publishing_option`, the SCS bytes of
+// a `VMPublishingOption`, e.g. a `Locked` option with the whitelist of script hashes.
+// Aborts if the sender is not the genesis account.
+fun main(account: &signer, publishing_option: vector<u8>) {
+    VMConfig::set_publishing_option(account, publishing_option)
+}
+}
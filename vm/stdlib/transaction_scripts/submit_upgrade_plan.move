@@ -0,0 +1,10 @@
+script {
+use 0x1::PackageTxnManager;
+
+// Submit an upgrade plan of the sender's code space, the package with `package_hash` can be
+// deployed once the block height reaches `active_after_height`.
+// Aborts if the sender's module upgrade strategy is not two phase upgrade, or a plan already exists.
+fun main(account: &signer, package_hash: vector<u8>, active_after_height: u64) {
+    PackageTxnManager::submit_upgrade_plan(account, package_hash, active_after_height)
+}
+}
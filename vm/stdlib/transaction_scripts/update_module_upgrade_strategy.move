@@ -0,0 +1,9 @@
+script {
+use 0x1::PackageTxnManager;
+
+// Update the module upgrade strategy of the sender's code space to `strategy`, the strategy can
+// only be raised: 0 arbitrary, 1 two phase upgrade, 2 only new module, 3 freeze.
+fun main(account: &signer, strategy: u8) {
+    PackageTxnManager::update_module_upgrade_strategy(account, strategy)
+}
+}
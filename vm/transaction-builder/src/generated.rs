@@ -0,0 +1,258 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is generated from the stdlib transaction scripts by running `cargo run` in vm/stdlib,
+// do not edit it by hand.
+
+use crate::StdlibScript;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::language_storage::TypeTag;
+use starcoin_vm_types::transaction::{Script, TransactionArgument};
+
+/// Encode the `accept_token` script.
+pub fn encode_accept_token_script(
+    token_type: TypeTag,
+) -> Script {
+    Script::new(
+        StdlibScript::AcceptToken.compiled_bytes().into_vec(),
+        vec![
+            token_type,
+        ],
+        vec![],
+    )
+}
+
+/// Encode the `add_to_script_whitelist` script.
+///
+/// Add `script_hash`, the sha3-256 hash of a script, to the whitelist of the locked publishing
+/// option of the on-chain VMConfig, so the script is allowed to execute.
+/// Aborts if the sender is not the genesis account, the publishing option is not locked, or the
+/// whitelist already contains `script_hash`.
+/// The committed generated genesis predates `VMConfig::add_to_script_whitelist`, chains
+/// started from them need a stdlib upgrade (`dev upgrade_stdlib`) before executing the script.
+pub fn encode_add_to_script_whitelist_script(
+    script_hash: Vec<u8>,
+) -> Script {
+    Script::new(
+        StdlibScript::AddToScriptWhitelist.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8Vector(script_hash),
+        ],
+    )
+}
+
+/// Encode the `cancel_upgrade_plan` script.
+///
+/// Cancel the pending upgrade plan of the sender's code space.
+/// Aborts if the sender's module upgrade strategy is not two phase upgrade, or no plan exists.
+pub fn encode_cancel_upgrade_plan_script() -> Script {
+    Script::new(
+        StdlibScript::CancelUpgradePlan.compiled_bytes().into_vec(),
+        vec![],
+        vec![],
+    )
+}
+
+/// Encode the `create_account` script.
+pub fn encode_create_account_script(
+    token_type: TypeTag,
+    fresh_address: AccountAddress,
+    auth_key_prefix: Vec<u8>,
+    initial_amount: u128,
+) -> Script {
+    Script::new(
+        StdlibScript::CreateAccount.compiled_bytes().into_vec(),
+        vec![
+            token_type,
+        ],
+        vec![
+            TransactionArgument::Address(fresh_address),
+            TransactionArgument::U8Vector(auth_key_prefix),
+            TransactionArgument::U128(initial_amount),
+        ],
+    )
+}
+
+/// Encode the `empty_script` script.
+pub fn encode_empty_script() -> Script {
+    Script::new(
+        StdlibScript::EmptyScript.compiled_bytes().into_vec(),
+        vec![],
+        vec![],
+    )
+}
+
+/// Encode the `mint` script.
+pub fn encode_mint_script(
+    token_type: TypeTag,
+    payee: AccountAddress,
+    auth_key_prefix: Vec<u8>,
+    amount: u128,
+) -> Script {
+    Script::new(
+        StdlibScript::Mint.compiled_bytes().into_vec(),
+        vec![
+            token_type,
+        ],
+        vec![
+            TransactionArgument::Address(payee),
+            TransactionArgument::U8Vector(auth_key_prefix),
+            TransactionArgument::U128(amount),
+        ],
+    )
+}
+
+/// Encode the `peer_to_peer` script.
+pub fn encode_peer_to_peer_script(
+    token_type: TypeTag,
+    payee: AccountAddress,
+    auth_key_prefix: Vec<u8>,
+    amount: u128,
+) -> Script {
+    Script::new(
+        StdlibScript::PeerToPeer.compiled_bytes().into_vec(),
+        vec![
+            token_type,
+        ],
+        vec![
+            TransactionArgument::Address(payee),
+            TransactionArgument::U8Vector(auth_key_prefix),
+            TransactionArgument::U128(amount),
+        ],
+    )
+}
+
+/// Encode the `peer_to_peer_with_metadata` script.
+pub fn encode_peer_to_peer_with_metadata_script(
+    token_type: TypeTag,
+    payee: AccountAddress,
+    auth_key_prefix: Vec<u8>,
+    amount: u128,
+    metadata: Vec<u8>,
+    metadata_signature: Vec<u8>,
+) -> Script {
+    Script::new(
+        StdlibScript::PeerToPeerWithMetadata.compiled_bytes().into_vec(),
+        vec![
+            token_type,
+        ],
+        vec![
+            TransactionArgument::Address(payee),
+            TransactionArgument::U8Vector(auth_key_prefix),
+            TransactionArgument::U128(amount),
+            TransactionArgument::U8Vector(metadata),
+            TransactionArgument::U8Vector(metadata_signature),
+        ],
+    )
+}
+
+/// Encode the `publish_shared_ed25519_public_key` script.
+///
+/// (1) Rotate the authentication key of the sender to `public_key`
+/// (2) Publish a resource containing a 32-byte ed25519 public key and the rotation capability
+///     of the sender under the sender's address.
+/// Aborts if the sender already has a `SharedEd25519PublicKey` resource.
+/// Aborts if the length of `new_public_key` is not 32.
+pub fn encode_publish_shared_ed25519_public_key_script(
+    public_key: Vec<u8>,
+) -> Script {
+    Script::new(
+        StdlibScript::PublishSharedEd2551PublicKey.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8Vector(public_key),
+        ],
+    )
+}
+
+/// Encode the `remove_from_script_whitelist` script.
+///
+/// Remove `script_hash`, the sha3-256 hash of a script, from the whitelist of the locked
+/// publishing option of the on-chain VMConfig.
+/// Aborts if the sender is not the genesis account, the publishing option is not locked, or the
+/// whitelist does not contain `script_hash`.
+/// The committed generated genesis predates `VMConfig::remove_from_script_whitelist`, chains
+/// started from them need a stdlib upgrade (`dev upgrade_stdlib`) before executing the script.
+pub fn encode_remove_from_script_whitelist_script(
+    script_hash: Vec<u8>,
+) -> Script {
+    Script::new(
+        StdlibScript::RemoveFromScriptWhitelist.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8Vector(script_hash),
+        ],
+    )
+}
+
+/// Encode the `set_gas_schedule` script.
+///
+/// Set the instruction and native schedules of the on-chain gas schedule, both are the SCS bytes
+/// of the cost vector of the `CostTable`.
+/// Aborts if the sender is not the genesis account.
+pub fn encode_set_gas_schedule_script(
+    instruction_schedule: Vec<u8>,
+    native_schedule: Vec<u8>,
+) -> Script {
+    Script::new(
+        StdlibScript::SetGasSchedule.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8Vector(instruction_schedule),
+            TransactionArgument::U8Vector(native_schedule),
+        ],
+    )
+}
+
+/// Encode the `set_publishing_option` script.
+///
+/// Set the publishing option of the on-chain VMConfig to `publishing_option`, the SCS bytes of
+/// a `VMPublishingOption`, e.g. a `Locked` option with the whitelist of script hashes.
+/// Aborts if the sender is not the genesis account.
+pub fn encode_set_publishing_option_script(
+    publishing_option: Vec<u8>,
+) -> Script {
+    Script::new(
+        StdlibScript::SetPublishingOption.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8Vector(publishing_option),
+        ],
+    )
+}
+
+/// Encode the `submit_upgrade_plan` script.
+///
+/// Submit an upgrade plan of the sender's code space, the package with `package_hash` can be
+/// deployed once the block height reaches `active_after_height`.
+/// Aborts if the sender's module upgrade strategy is not two phase upgrade, or a plan already exists.
+pub fn encode_submit_upgrade_plan_script(
+    package_hash: Vec<u8>,
+    active_after_height: u64,
+) -> Script {
+    Script::new(
+        StdlibScript::SubmitUpgradePlan.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8Vector(package_hash),
+            TransactionArgument::U64(active_after_height),
+        ],
+    )
+}
+
+/// Encode the `update_module_upgrade_strategy` script.
+///
+/// Update the module upgrade strategy of the sender's code space to `strategy`, the strategy can
+/// only be raised: 0 arbitrary, 1 two phase upgrade, 2 only new module, 3 freeze.
+pub fn encode_update_module_upgrade_strategy_script(
+    strategy: u8,
+) -> Script {
+    Script::new(
+        StdlibScript::UpdateModuleUpgradeStrategy.compiled_bytes().into_vec(),
+        vec![],
+        vec![
+            TransactionArgument::U8(strategy),
+        ],
+    )
+}
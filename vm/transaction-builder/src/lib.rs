@@ -3,12 +3,15 @@
 
 use anyhow::Result;
 use starcoin_config::ChainNetwork;
+use starcoin_crypto::hash::PlainCryptoHash;
 use starcoin_logger::prelude::*;
 use starcoin_vm_types::access::ModuleAccess;
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config;
 use starcoin_vm_types::account_config::stc_type_tag;
+use starcoin_vm_types::gas_schedule::CostTable;
 use starcoin_vm_types::language_storage::TypeTag;
+use starcoin_vm_types::on_chain_config::{VMPublishingOption, SCRIPT_HASH_LENGTH};
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 use starcoin_vm_types::transaction::{
     Module, Package, RawUserTransaction, Script, SignedUserTransaction, Transaction,
@@ -18,6 +21,12 @@ use stdlib::init_scripts::InitScript;
 pub use stdlib::transaction_scripts::{CompiledBytes, StdlibScript};
 pub use stdlib::{stdlib_modules, StdLibOptions};
 
+/// The builders generated from the stdlib transaction scripts.
+#[rustfmt::skip]
+mod generated;
+
+pub use generated::*;
+
 pub const DEFAULT_EXPIRATION_TIME: u64 = 40_000;
 pub const DEFAULT_MAX_GAS_AMOUNT: u64 = 20000;
 
@@ -113,18 +122,14 @@ pub fn raw_peer_to_peer_txn(
     token_type: TypeTag,
     expiration_timstamp_secs: u64,
 ) -> RawUserTransaction {
-    let mut args: Vec<TransactionArgument> = Vec::new();
-    args.push(TransactionArgument::Address(receiver));
-    args.push(TransactionArgument::U8Vector(receiver_auth_key_prefix));
-    args.push(TransactionArgument::U128(transfer_amount));
-
     RawUserTransaction::new(
         sender,
         seq_num,
-        TransactionPayload::Script(Script::new(
-            StdlibScript::PeerToPeer.compiled_bytes().into_vec(),
-            vec![token_type],
-            args,
+        TransactionPayload::Script(encode_peer_to_peer_script(
+            token_type,
+            receiver,
+            receiver_auth_key_prefix,
+            transfer_amount,
         )),
         max_gas,
         gas_price,
@@ -143,47 +148,47 @@ pub fn raw_accept_token_txn(
     RawUserTransaction::new(
         sender,
         seq_num,
-        TransactionPayload::Script(Script::new(
-            StdlibScript::AcceptToken.compiled_bytes().into_vec(),
-            vec![token_type],
-            vec![],
-        )),
+        TransactionPayload::Script(encode_accept_token_script(token_type)),
         max_gas,
         gas_price,
         expiration_timstamp_secs,
     )
 }
 
-pub fn encode_create_account_script(
-    account_address: &AccountAddress,
-    auth_key_prefix: Vec<u8>,
-    initial_balance: u64,
-) -> Script {
-    Script::new(
-        StdlibScript::CreateAccount.compiled_bytes().into_vec(),
-        vec![],
-        vec![
-            TransactionArgument::Address(*account_address),
-            TransactionArgument::U8Vector(auth_key_prefix),
-            TransactionArgument::U64(initial_balance),
-        ],
-    )
+/// Encode the script to set the instruction and native schedules of the on-chain gas schedule to
+/// the tables of `gas_schedule`, the transaction must be sent by the genesis account. The gas
+/// constants are not changed.
+///
+/// `VMConfig::set_gas_schedule` is not in the stdlib of the committed generated genesis, chains
+/// started from them need a stdlib upgrade (`dev upgrade_stdlib`) before executing the script.
+pub fn encode_set_gas_schedule_script_by_cost_table(gas_schedule: &CostTable) -> Result<Script> {
+    Ok(encode_set_gas_schedule_script(
+        scs::to_bytes(&gas_schedule.instruction_table)?,
+        scs::to_bytes(&gas_schedule.native_table)?,
+    ))
 }
 
-pub fn encode_transfer_script(
-    recipient: &AccountAddress,
-    auth_key_prefix: Vec<u8>,
-    amount: u128,
+/// Encode the script to lock the publishing option, only the scripts with hash in `whitelist`
+/// are allowed to execute. Use `StdlibScript::whitelist()` to allow all the stdlib scripts. The
+/// whitelist can then be changed on chain by the `add_to_script_whitelist` and
+/// `remove_from_script_whitelist` scripts, chains started from the committed generated genesis
+/// need a stdlib upgrade (`dev upgrade_stdlib`) before executing them.
+pub fn encode_lock_publishing_option_script(
+    whitelist: Vec<[u8; SCRIPT_HASH_LENGTH]>,
+) -> Result<Script> {
+    Ok(encode_set_publishing_option_script(scs::to_bytes(
+        &VMPublishingOption::Locked(whitelist),
+    )?))
+}
+
+/// Encode the script to submit the upgrade plan of `package`, the package can be deployed once
+/// the block height reaches `active_after_height`. The sender's module upgrade strategy must be
+/// `ModuleUpgradeStrategy::TwoPhase`.
+pub fn encode_submit_upgrade_plan_script_by_package(
+    package: &Package,
+    active_after_height: u64,
 ) -> Script {
-    Script::new(
-        StdlibScript::PeerToPeer.compiled_bytes().into_vec(),
-        vec![stc_type_tag()],
-        vec![
-            TransactionArgument::Address(*recipient),
-            TransactionArgument::U8Vector(auth_key_prefix),
-            TransactionArgument::U128(amount),
-        ],
-    )
+    encode_submit_upgrade_plan_script(package.crypto_hash().to_vec(), active_after_height)
 }
 
 pub fn peer_to_peer_txn_sent_as_association(
@@ -194,7 +199,12 @@ pub fn peer_to_peer_txn_sent_as_association(
     expiration_timstamp_secs: u64,
 ) -> SignedUserTransaction {
     crate::create_signed_txn_with_association_account(
-        TransactionPayload::Script(encode_transfer_script(&recipient, auth_key_prefix, amount)),
+        TransactionPayload::Script(encode_peer_to_peer_script(
+            stc_type_tag(),
+            recipient,
+            auth_key_prefix,
+            amount,
+        )),
         seq_num,
         DEFAULT_MAX_GAS_AMOUNT,
         1,
@@ -294,3 +304,28 @@ pub fn build_stdlib_package(
     }
     Ok(package)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_publishing_option(script: &Script) -> VMPublishingOption {
+        match script.args() {
+            [TransactionArgument::U8Vector(bytes)] => scs::from_bytes(bytes).unwrap(),
+            args => panic!("Unexpected script args: {:?}", args),
+        }
+    }
+
+    #[stest::test]
+    fn test_lock_publishing_option() {
+        let stdlib_whitelist = StdlibScript::whitelist();
+        let locked = decode_publishing_option(
+            &encode_lock_publishing_option_script(stdlib_whitelist.clone()).unwrap(),
+        );
+        assert_eq!(locked, VMPublishingOption::Locked(stdlib_whitelist));
+        assert!(StdlibScript::all()
+            .into_iter()
+            .all(|script| locked.is_allowed_script(&script.compiled_bytes().into_vec())));
+        assert!(!locked.is_allowed_script(b"custom script"));
+    }
+}
@@ -0,0 +1,495 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The JSON ABI of Move modules and scripts, generated from the compiled bytecode.
+//! The bytecode does not keep the parameter names, the parameters are named by their position
+//! (`arg0`, `arg1`, ...) unless the names are provided from the source.
+
+use crate::access::ModuleAccess;
+use crate::account_address::AccountAddress;
+use crate::file_format::{
+    CompiledModule, CompiledScript, Kind, ModuleHandle, SignatureToken, StructFieldInformation,
+    StructHandle, StructHandleIndex,
+};
+use crate::identifier::Identifier;
+use crate::language_storage::ModuleId;
+use crate::transaction::TransactionArgument;
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The file extension of the JSON ABI file.
+pub const ABI_EXTENSION: &str = "abi";
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeABI {
+    Bool,
+    U8,
+    U64,
+    U128,
+    Address,
+    Signer,
+    Vector(Box<TypeABI>),
+    Struct(StructTypeABI),
+    TypeParameter(u16),
+    Reference(Box<TypeABI>),
+    MutableReference(Box<TypeABI>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StructTypeABI {
+    pub address: AccountAddress,
+    pub module: String,
+    pub name: String,
+    pub type_arguments: Vec<TypeABI>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KindABI {
+    All,
+    Resource,
+    Copyable,
+}
+
+impl From<Kind> for KindABI {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::All => KindABI::All,
+            Kind::Resource => KindABI::Resource,
+            Kind::Copyable => KindABI::Copyable,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TypeParameterABI {
+    pub name: String,
+    pub kind: KindABI,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentABI {
+    pub name: String,
+    pub type_abi: TypeABI,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FieldABI {
+    pub name: String,
+    pub type_abi: TypeABI,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StructABI {
+    pub name: String,
+    pub is_resource: bool,
+    pub type_parameters: Vec<TypeParameterABI>,
+    /// The fields of the struct, `None` for a native struct.
+    pub fields: Option<Vec<FieldABI>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FunctionABI {
+    pub name: String,
+    pub type_parameters: Vec<TypeParameterABI>,
+    pub parameters: Vec<ArgumentABI>,
+    pub returns: Vec<TypeABI>,
+}
+
+/// The ABI of a module, only the public functions are included.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ModuleABI {
+    pub address: AccountAddress,
+    pub name: String,
+    pub structs: Vec<StructABI>,
+    pub functions: Vec<FunctionABI>,
+}
+
+/// The ABI of a script, the leading `&signer` parameters are filled by the VM with the
+/// transaction sender, the rest are the transaction arguments.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScriptABI {
+    pub type_parameters: Vec<TypeParameterABI>,
+    pub parameters: Vec<ArgumentABI>,
+}
+
+/// The tables a `SignatureToken` refers to, shared by modules and scripts.
+struct TypeResolver<'a> {
+    struct_handles: &'a [StructHandle],
+    module_handles: &'a [ModuleHandle],
+    identifiers: &'a [Identifier],
+    address_identifiers: &'a [AccountAddress],
+}
+
+impl<'a> TypeResolver<'a> {
+    fn struct_type(&self, idx: StructHandleIndex, type_arguments: Vec<TypeABI>) -> TypeABI {
+        let struct_handle = &self.struct_handles[idx.0 as usize];
+        let module_handle = &self.module_handles[struct_handle.module.0 as usize];
+        TypeABI::Struct(StructTypeABI {
+            address: self.address_identifiers[module_handle.address.0 as usize],
+            module: self.identifiers[module_handle.name.0 as usize].to_string(),
+            name: self.identifiers[struct_handle.name.0 as usize].to_string(),
+            type_arguments,
+        })
+    }
+
+    fn type_abi(&self, token: &SignatureToken) -> TypeABI {
+        match token {
+            SignatureToken::Bool => TypeABI::Bool,
+            SignatureToken::U8 => TypeABI::U8,
+            SignatureToken::U64 => TypeABI::U64,
+            SignatureToken::U128 => TypeABI::U128,
+            SignatureToken::Address => TypeABI::Address,
+            SignatureToken::Signer => TypeABI::Signer,
+            SignatureToken::Vector(token) => TypeABI::Vector(Box::new(self.type_abi(token))),
+            SignatureToken::Struct(idx) => self.struct_type(*idx, vec![]),
+            SignatureToken::StructInstantiation(idx, tokens) => self.struct_type(
+                *idx,
+                tokens.iter().map(|token| self.type_abi(token)).collect(),
+            ),
+            SignatureToken::Reference(token) => TypeABI::Reference(Box::new(self.type_abi(token))),
+            SignatureToken::MutableReference(token) => {
+                TypeABI::MutableReference(Box::new(self.type_abi(token)))
+            }
+            SignatureToken::TypeParameter(idx) => TypeABI::TypeParameter(*idx),
+        }
+    }
+
+    fn types_abi(&self, tokens: &[SignatureToken]) -> Vec<TypeABI> {
+        tokens.iter().map(|token| self.type_abi(token)).collect()
+    }
+}
+
+fn type_parameters_abi(kinds: &[Kind]) -> Vec<TypeParameterABI> {
+    kinds
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| TypeParameterABI {
+            name: format!("T{}", i),
+            kind: (*kind).into(),
+        })
+        .collect()
+}
+
+fn arguments_abi(types: Vec<TypeABI>) -> Vec<ArgumentABI> {
+    types
+        .into_iter()
+        .enumerate()
+        .map(|(i, type_abi)| ArgumentABI {
+            name: format!("arg{}", i),
+            type_abi,
+        })
+        .collect()
+}
+
+impl ModuleABI {
+    pub fn new(module: &CompiledModule) -> Self {
+        let resolver = TypeResolver {
+            struct_handles: module.struct_handles(),
+            module_handles: module.module_handles(),
+            identifiers: module.identifiers(),
+            address_identifiers: module.address_identifiers(),
+        };
+        let structs = module
+            .struct_defs()
+            .iter()
+            .map(|struct_def| {
+                let struct_handle = module.struct_handle_at(struct_def.struct_handle);
+                let fields = match &struct_def.field_information {
+                    StructFieldInformation::Native => None,
+                    StructFieldInformation::Declared(fields) => Some(
+                        fields
+                            .iter()
+                            .map(|field| FieldABI {
+                                name: module.identifier_at(field.name).to_string(),
+                                type_abi: resolver.type_abi(&field.signature.0),
+                            })
+                            .collect(),
+                    ),
+                };
+                StructABI {
+                    name: module.identifier_at(struct_handle.name).to_string(),
+                    is_resource: struct_handle.is_nominal_resource,
+                    type_parameters: type_parameters_abi(&struct_handle.type_parameters),
+                    fields,
+                }
+            })
+            .collect();
+        let functions = module
+            .function_defs()
+            .iter()
+            .filter(|fun_def| fun_def.is_public)
+            .map(|fun_def| {
+                let fun_handle = module.function_handle_at(fun_def.function);
+                FunctionABI {
+                    name: module.identifier_at(fun_handle.name).to_string(),
+                    type_parameters: type_parameters_abi(&fun_handle.type_parameters),
+                    parameters: arguments_abi(
+                        resolver.types_abi(&module.signature_at(fun_handle.parameters).0),
+                    ),
+                    returns: resolver.types_abi(&module.signature_at(fun_handle.return_).0),
+                }
+            })
+            .collect();
+        let module_handle = module.self_handle();
+        Self {
+            address: *module.address_identifier_at(module_handle.address),
+            name: module.identifier_at(module_handle.name).to_string(),
+            structs,
+            functions,
+        }
+    }
+
+    pub fn module_id(&self) -> Result<ModuleId> {
+        Ok(ModuleId::new(
+            self.address,
+            Identifier::new(self.name.as_str())?,
+        ))
+    }
+
+    pub fn find_function(&self, name: &str) -> Option<&FunctionABI> {
+        self.functions.iter().find(|fun| fun.name == name)
+    }
+
+    pub fn find_struct(&self, name: &str) -> Option<&StructABI> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+}
+
+impl ScriptABI {
+    pub fn new(script: &CompiledScript) -> Self {
+        let inner = script.as_inner();
+        let resolver = TypeResolver {
+            struct_handles: &inner.struct_handles,
+            module_handles: &inner.module_handles,
+            identifiers: &inner.identifiers,
+            address_identifiers: &inner.address_identifiers,
+        };
+        Self {
+            type_parameters: type_parameters_abi(&inner.type_parameters),
+            parameters: arguments_abi(
+                resolver.types_abi(&inner.signatures[inner.parameters.0 as usize].0),
+            ),
+        }
+    }
+
+    /// Replace the positional parameter names with `names`, e.g. the names from the source.
+    pub fn with_parameter_names(mut self, names: Vec<String>) -> Result<Self> {
+        ensure!(
+            names.len() == self.parameters.len(),
+            "Expect {} parameter names, but got {}.",
+            self.parameters.len(),
+            names.len()
+        );
+        for (parameter, name) in self.parameters.iter_mut().zip(names) {
+            parameter.name = name;
+        }
+        Ok(self)
+    }
+
+    /// Replace the positional type parameter names with `names`, e.g. the names from the source.
+    pub fn with_type_parameter_names(mut self, names: Vec<String>) -> Result<Self> {
+        ensure!(
+            names.len() == self.type_parameters.len(),
+            "Expect {} type parameter names, but got {}.",
+            self.type_parameters.len(),
+            names.len()
+        );
+        for (type_parameter, name) in self.type_parameters.iter_mut().zip(names) {
+            type_parameter.name = name;
+        }
+        Ok(self)
+    }
+
+    /// The parameters passed as transaction arguments, excluding the leading `&signer`s.
+    pub fn transaction_parameters(&self) -> &[ArgumentABI] {
+        let signers = self
+            .parameters
+            .iter()
+            .take_while(|parameter| {
+                parameter.type_abi == TypeABI::Reference(Box::new(TypeABI::Signer))
+            })
+            .count();
+        &self.parameters[signers..]
+    }
+
+    /// Type check and convert the JSON `args` to transaction arguments. `args` is either an
+    /// object of the arguments by parameter name, or an array of the arguments in order.
+    pub fn encode_args(&self, args: &Value) -> Result<Vec<TransactionArgument>> {
+        let parameters = self.transaction_parameters();
+        let values: Vec<&Value> = match args {
+            Value::Object(map) => {
+                for name in map.keys() {
+                    ensure!(
+                        parameters.iter().any(|parameter| &parameter.name == name),
+                        "Unknown argument {}",
+                        name
+                    );
+                }
+                parameters
+                    .iter()
+                    .map(|parameter| {
+                        map.get(&parameter.name)
+                            .ok_or_else(|| format_err!("Missing argument {}", parameter.name))
+                    })
+                    .collect::<Result<_>>()?
+            }
+            Value::Array(values) => {
+                ensure!(
+                    values.len() == parameters.len(),
+                    "Expect {} arguments, but got {}.",
+                    parameters.len(),
+                    values.len()
+                );
+                values.iter().collect()
+            }
+            _ => bail!("Arguments must be a JSON object or array, but got {}", args),
+        };
+        parameters
+            .iter()
+            .zip(values)
+            .map(|(parameter, value)| {
+                encode_arg(&parameter.type_abi, value)
+                    .map_err(|e| format_err!("Invalid argument {}: {}", parameter.name, e))
+            })
+            .collect()
+    }
+}
+
+/// Numbers are accepted as JSON numbers or strings, a string keeps the precision of u128.
+fn parse_number<T>(value: &Value) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let s = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => bail!("expect a number, but got {}", value),
+    };
+    s.parse::<T>().map_err(|e| format_err!("{}: {}", s, e))
+}
+
+fn encode_arg(type_abi: &TypeABI, value: &Value) -> Result<TransactionArgument> {
+    Ok(match type_abi {
+        TypeABI::Bool => TransactionArgument::Bool(
+            value
+                .as_bool()
+                .ok_or_else(|| format_err!("expect a bool, but got {}", value))?,
+        ),
+        TypeABI::U8 => TransactionArgument::U8(parse_number(value)?),
+        TypeABI::U64 => TransactionArgument::U64(parse_number(value)?),
+        TypeABI::U128 => TransactionArgument::U128(parse_number(value)?),
+        TypeABI::Address => {
+            let s = value
+                .as_str()
+                .ok_or_else(|| format_err!("expect an address string, but got {}", value))?;
+            let s = if s.starts_with("0x") {
+                s.to_string()
+            } else {
+                format!("0x{}", s)
+            };
+            TransactionArgument::Address(AccountAddress::from_hex_literal(s.as_str())?)
+        }
+        TypeABI::Vector(element) if element.as_ref() == &TypeABI::U8 => {
+            TransactionArgument::U8Vector(match value {
+                Value::String(s) => hex::decode(s.strip_prefix("0x").unwrap_or(s))?,
+                Value::Array(values) => values
+                    .iter()
+                    .map(parse_number::<u8>)
+                    .collect::<Result<_>>()?,
+                _ => bail!("expect a hex string or an array of u8, but got {}", value),
+            })
+        }
+        _ => bail!("type {:?} can not be a transaction argument", type_abi),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn peer_to_peer_abi() -> ScriptABI {
+        ScriptABI {
+            type_parameters: vec![TypeParameterABI {
+                name: "T0".to_string(),
+                kind: KindABI::All,
+            }],
+            parameters: vec![
+                ArgumentABI {
+                    name: "account".to_string(),
+                    type_abi: TypeABI::Reference(Box::new(TypeABI::Signer)),
+                },
+                ArgumentABI {
+                    name: "payee".to_string(),
+                    type_abi: TypeABI::Address,
+                },
+                ArgumentABI {
+                    name: "auth_key_prefix".to_string(),
+                    type_abi: TypeABI::Vector(Box::new(TypeABI::U8)),
+                },
+                ArgumentABI {
+                    name: "amount".to_string(),
+                    type_abi: TypeABI::U128,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_encode_args() {
+        let abi = peer_to_peer_abi();
+        let expected = vec![
+            TransactionArgument::Address(AccountAddress::from_hex_literal("0x1").unwrap()),
+            TransactionArgument::U8Vector(vec![1, 2]),
+            TransactionArgument::U128(340_282_366_920_938_463_463_374_607_431_768_211_455),
+        ];
+        let named = json!({
+            "payee": "0x1",
+            "auth_key_prefix": "0x0102",
+            "amount": "340282366920938463463374607431768211455",
+        });
+        assert_eq!(abi.encode_args(&named).unwrap(), expected);
+        let positional = json!(["1", [1, 2], "340282366920938463463374607431768211455"]);
+        assert_eq!(abi.encode_args(&positional).unwrap(), expected);
+
+        assert!(abi
+            .encode_args(&json!({"payee": "0x1", "auth_key_prefix": "0x0102"}))
+            .is_err());
+        assert!(abi
+            .encode_args(&json!({"payee": true, "auth_key_prefix": "0x0102", "amount": 1}))
+            .is_err());
+        assert!(abi
+            .encode_args(&json!({"payee": "0x1", "auth_key_prefix": "0x0102", "amount": -1}))
+            .is_err());
+        assert!(abi
+            .encode_args(
+                &json!({"payee": "0x1", "auth_key_prefix": "0x0102", "amount": 1, "other": 1})
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_with_parameter_names() {
+        let abi = peer_to_peer_abi();
+        assert!(abi.clone().with_parameter_names(vec![]).is_err());
+        let abi = abi
+            .with_parameter_names(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+                "d".to_string(),
+            ])
+            .unwrap();
+        assert_eq!(
+            abi.transaction_parameters()
+                .iter()
+                .map(|parameter| parameter.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "c", "d"]
+        );
+    }
+}
@@ -5,12 +5,16 @@ pub mod account;
 pub mod association_capability;
 pub mod balance;
 pub mod key_rotation_capability;
+pub mod module_upgrade_strategy;
 pub mod token_info;
+pub mod transaction_fee;
 pub mod withdraw_capability;
 
 pub use account::*;
 pub use association_capability::*;
 pub use balance::*;
 pub use key_rotation_capability::*;
+pub use module_upgrade_strategy::*;
 pub use token_info::*;
+pub use transaction_fee::*;
 pub use withdraw_capability::*;
@@ -0,0 +1,149 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Error, Result};
+use move_core_types::move_resource::MoveResource;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// The module upgrade strategy of an account's code space, see `PackageTxnManager.move`.
+/// The strategy of an account can only be raised.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ModuleUpgradeStrategy {
+    /// Modules can be upgraded by any package txn of the maintainer.
+    Arbitrary = 0,
+    /// A package can only be deployed after its upgrade plan is active.
+    TwoPhase = 1,
+    /// Only new modules can be published.
+    NewModule = 2,
+    /// No modules can be published or upgraded.
+    Freeze = 3,
+}
+
+impl ModuleUpgradeStrategy {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Arbitrary,
+            Self::TwoPhase,
+            Self::NewModule,
+            Self::Freeze,
+        ]
+    }
+}
+
+impl Default for ModuleUpgradeStrategy {
+    fn default() -> Self {
+        ModuleUpgradeStrategy::Arbitrary
+    }
+}
+
+impl From<ModuleUpgradeStrategy> for u8 {
+    fn from(strategy: ModuleUpgradeStrategy) -> Self {
+        strategy as u8
+    }
+}
+
+impl TryFrom<u8> for ModuleUpgradeStrategy {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            0 => Self::Arbitrary,
+            1 => Self::TwoPhase,
+            2 => Self::NewModule,
+            3 => Self::Freeze,
+            _ => bail!("Invalid module upgrade strategy {}", value),
+        })
+    }
+}
+
+impl fmt::Display for ModuleUpgradeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Arbitrary => "arbitrary",
+                Self::TwoPhase => "two-phase",
+                Self::NewModule => "new-module",
+                Self::Freeze => "freeze",
+            }
+        )
+    }
+}
+
+impl FromStr for ModuleUpgradeStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        for strategy in Self::all() {
+            if strategy.to_string() == s {
+                return Ok(strategy);
+            }
+        }
+        bail!("Unknown module upgrade strategy {}", s)
+    }
+}
+
+/// Struct that represents a ModuleUpgradeStrategy resource, if the resource not exist, the
+/// strategy of the account is `Arbitrary`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModuleUpgradeStrategyResource {
+    strategy: u8,
+}
+
+impl ModuleUpgradeStrategyResource {
+    pub fn strategy(&self) -> Result<ModuleUpgradeStrategy> {
+        ModuleUpgradeStrategy::try_from(self.strategy)
+    }
+}
+
+impl MoveResource for ModuleUpgradeStrategyResource {
+    const MODULE_NAME: &'static str = "PackageTxnManager";
+    const STRUCT_NAME: &'static str = "ModuleUpgradeStrategy";
+}
+
+/// The upgrade plan of a two phase upgrade, the package with `package_hash` can be deployed once
+/// the block height reaches `active_after_height`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UpgradePlan {
+    package_hash: Vec<u8>,
+    active_after_height: u64,
+}
+
+impl UpgradePlan {
+    pub fn package_hash(&self) -> Result<HashValue> {
+        Ok(HashValue::from_slice(self.package_hash.as_slice())?)
+    }
+
+    pub fn active_after_height(&self) -> u64 {
+        self.active_after_height
+    }
+
+    pub fn is_active(&self, block_height: u64) -> bool {
+        self.active_after_height <= block_height
+    }
+}
+
+/// Struct that represents a TwoPhaseUpgrade resource.
+/// A Move `Option` is a vector with at most one element, which has the same SCS layout as a Rust
+/// `Option`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TwoPhaseUpgradeResource {
+    plan: Option<UpgradePlan>,
+}
+
+impl TwoPhaseUpgradeResource {
+    pub fn plan(&self) -> Option<&UpgradePlan> {
+        self.plan.as_ref()
+    }
+}
+
+impl MoveResource for TwoPhaseUpgradeResource {
+    const MODULE_NAME: &'static str = "PackageTxnManager";
+    const STRUCT_NAME: &'static str = "TwoPhaseUpgrade";
+}
@@ -0,0 +1,55 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    access_path::AccessPath,
+    account_config::constants::{genesis_address, stc_type_tag, CORE_CODE_ADDRESS},
+};
+use move_core_types::{
+    language_storage::{StructTag, TypeTag},
+    move_resource::MoveResource,
+};
+use serde::{Deserialize, Serialize};
+
+/// The transaction fees collected in a token, held under the genesis account.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionFeeResource {
+    fee: u128,
+}
+
+impl TransactionFeeResource {
+    pub fn new(fee: u128) -> Self {
+        Self { fee }
+    }
+
+    pub fn fee(&self) -> u128 {
+        self.fee
+    }
+
+    pub fn struct_tag_for_currency(currency_typetag: TypeTag) -> StructTag {
+        StructTag {
+            address: CORE_CODE_ADDRESS,
+            name: TransactionFeeResource::struct_identifier(),
+            module: TransactionFeeResource::module_identifier(),
+            type_params: vec![currency_typetag],
+        }
+    }
+
+    pub fn resource_path_for(currency_typetag: TypeTag) -> AccessPath {
+        AccessPath::new(
+            genesis_address(),
+            AccessPath::resource_access_vec(&TransactionFeeResource::struct_tag_for_currency(
+                currency_typetag,
+            )),
+        )
+    }
+}
+
+impl MoveResource for TransactionFeeResource {
+    const MODULE_NAME: &'static str = "TransactionFee";
+    const STRUCT_NAME: &'static str = "TransactionFee";
+
+    fn type_params() -> Vec<TypeTag> {
+        vec![stc_type_tag()]
+    }
+}
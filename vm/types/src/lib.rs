@@ -118,6 +118,7 @@ pub mod bytecode_verifier {
     };
 }
 
+pub mod abi;
 pub mod access_path;
 pub mod account_config;
 pub mod block_metadata;
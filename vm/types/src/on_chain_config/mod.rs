@@ -139,3 +139,63 @@ pub fn access_path_for_config(address: AccountAddress, config_name: Identifier)
         }),
     )
 }
+
+/// Returns the `ConfigID` of the config changed by a `Config::ConfigChangeEvent<ConfigValue>`
+/// event with `type_tag`, or `None` if it is not a change event of a registered config.
+pub fn config_id_of_change_event(type_tag: &TypeTag) -> Option<ConfigID> {
+    let event_tag = match type_tag {
+        TypeTag::Struct(struct_tag) => struct_tag,
+        _ => return None,
+    };
+    if event_tag.address != CORE_CODE_ADDRESS
+        || event_tag.module.as_str() != "Config"
+        || event_tag.name.as_str() != "ConfigChangeEvent"
+    {
+        return None;
+    }
+    let config_tag = match event_tag.type_params.as_slice() {
+        [TypeTag::Struct(config_tag)] => config_tag,
+        _ => return None,
+    };
+    ON_CHAIN_CONFIG_REGISTRY.iter().copied().find(|config_id| {
+        AccountAddress::from_hex_literal(config_id.0).ok() == Some(config_tag.address)
+            && config_tag.module.as_str() == config_id.1
+            && config_tag.name.as_str() == config_id.1
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_change_event_tag(config_name: &str) -> TypeTag {
+        TypeTag::Struct(StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new("Config").unwrap(),
+            name: Identifier::new("ConfigChangeEvent").unwrap(),
+            type_params: vec![TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(config_name).unwrap(),
+                name: Identifier::new(config_name).unwrap(),
+                type_params: vec![],
+            })],
+        })
+    }
+
+    #[test]
+    fn test_config_id_of_change_event() {
+        assert_eq!(
+            config_id_of_change_event(&config_change_event_tag("VMConfig")),
+            Some(VMConfig::CONFIG_ID)
+        );
+        assert_eq!(
+            config_id_of_change_event(&config_change_event_tag("Version")),
+            Some(Version::CONFIG_ID)
+        );
+        assert_eq!(
+            config_id_of_change_event(&config_change_event_tag("Unknown")),
+            None
+        );
+        assert_eq!(config_id_of_change_event(&TypeTag::U64), None);
+    }
+}
@@ -16,6 +16,7 @@ use starcoin_vm_types::{
     write_set::{WriteOp, WriteSet},
 };
 use std::collections::btree_map::BTreeMap;
+use std::sync::Mutex;
 
 /// A local cache for a given a `StateView`. The cache is private to the Libra layer
 /// but can be used as a one shot cache for systems that need a simple `RemoteCache`
@@ -108,6 +109,107 @@ impl<'block> ConfigStorage for StateViewCache<'block> {
     }
 }
 
+/// A `StateView` which records the access paths read from the underlying `StateView`,
+/// used to find the conflicts between the transactions executed in parallel.
+pub struct RecordingStateView<'a> {
+    data_view: &'a dyn StateView,
+    reads: Mutex<Vec<AccessPath>>,
+}
+
+impl<'a> RecordingStateView<'a> {
+    pub fn new(data_view: &'a dyn StateView) -> Self {
+        Self {
+            data_view,
+            reads: Mutex::new(vec![]),
+        }
+    }
+
+    /// The access paths read, in the order of the first read.
+    pub fn into_reads(self) -> Vec<AccessPath> {
+        self.reads
+            .into_inner()
+            .expect("lock should not be poisoned")
+    }
+}
+
+impl<'a> StateView for RecordingStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
+        {
+            let mut reads = self.reads.lock().expect("lock should not be poisoned");
+            if !reads.contains(access_path) {
+                reads.push(access_path.clone());
+            }
+        }
+        self.data_view.get(access_path)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.data_view.is_genesis()
+    }
+}
+
+/// A cache like `StateViewCache`, but over a `Sync` `StateView`, so it can be shared by the
+/// threads executing transactions in parallel.
+pub struct SharedStateViewCache<'a, S> {
+    data_view: &'a S,
+    data_map: BTreeMap<AccessPath, Option<Vec<u8>>>,
+}
+
+impl<'a, S> SharedStateViewCache<'a, S>
+where
+    S: StateView + Sync,
+{
+    pub fn new(data_view: &'a S) -> Self {
+        Self {
+            data_view,
+            data_map: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn push_write_set(&mut self, write_set: &WriteSet) {
+        for (ref ap, ref write_op) in write_set.iter() {
+            match write_op {
+                WriteOp::Value(blob) => {
+                    self.data_map.insert(ap.clone(), Some(blob.clone()));
+                }
+                WriteOp::Deletion => {
+                    self.data_map.insert(ap.clone(), None);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, S> StateView for SharedStateViewCache<'a, S>
+where
+    S: StateView + Sync,
+{
+    fn get(&self, access_path: &AccessPath) -> anyhow::Result<Option<Vec<u8>>> {
+        match self.data_map.get(access_path) {
+            Some(opt_data) => Ok(opt_data.clone()),
+            None => self.data_view.get(access_path),
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.data_view.is_genesis()
+    }
+}
+
 // Adapter to convert a `StateView` into a `RemoteCache`.
 pub struct RemoteStorage<'a>(&'a dyn StateView);
 
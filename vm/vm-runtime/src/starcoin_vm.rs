@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::access_path_cache::AccessPathCache;
-use crate::data_cache::{RemoteStorage, StateViewCache};
+use crate::data_cache::{RecordingStateView, RemoteStorage, SharedStateViewCache, StateViewCache};
 use crate::metrics::TXN_EXECUTION_GAS_USAGE;
 use anyhow::{format_err, Error, Result};
 use move_vm_runtime::data_cache::TransactionEffects;
 use move_vm_runtime::session::Session;
 use move_vm_runtime::{data_cache::RemoteCache, move_vm::MoveVM};
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use starcoin_logger::prelude::*;
 use starcoin_move_compiler::check_compat_and_verify_module;
 use starcoin_types::{
@@ -23,16 +24,19 @@ use starcoin_types::{
 use starcoin_vm_types::access::ModuleAccess;
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::{
-    genesis_address, stc_type_tag, EPILOGUE_NAME, PROLOGUE_NAME,
+    genesis_address, stc_type_tag, TransactionFeeResource, EPILOGUE_NAME, PROLOGUE_NAME,
 };
 use starcoin_vm_types::contract_event::ContractEvent;
 use starcoin_vm_types::file_format::CompiledModule;
 use starcoin_vm_types::gas_schedule::{zero_cost_schedule, CostStrategy};
-use starcoin_vm_types::on_chain_config::{VMPublishingOption, INITIAL_GAS_SCHEDULE};
+use starcoin_vm_types::on_chain_config::{
+    config_id_of_change_event, VMPublishingOption, INITIAL_GAS_SCHEDULE,
+};
 use starcoin_vm_types::transaction::{Package, Script};
 use starcoin_vm_types::vm_status::KeptVMStatus;
 use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
 use starcoin_vm_types::{
+    access_path::AccessPath,
     errors::{self, IndexKind, Location},
     event::EventKey,
     gas_schedule::{self, CostTable, GasAlgebra, GasCarrier, GasUnits},
@@ -43,6 +47,7 @@ use starcoin_vm_types::{
     values::Value,
     vm_status::{convert_prologue_runtime_error, StatusCode, VMStatus},
 };
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -546,7 +551,7 @@ impl StarcoinVM {
     }
 
     fn execute_user_transaction(
-        &mut self,
+        &self,
         txn: SignedUserTransaction,
         remote_cache: &mut StateViewCache<'_>,
     ) -> (VMStatus, TransactionOutput) {
@@ -624,7 +629,6 @@ impl StarcoinVM {
         let mut gas_left = block_gas_limit.unwrap_or_default();
 
         let mut result = vec![];
-        //TODO load config by config change event.
         self.load_configs(&data_cache)?;
         let blocks = chunk_block_transactions(transactions);
         'outer: for block in blocks {
@@ -649,7 +653,11 @@ impl StarcoinVM {
                                     "Keep transaction gas used must not be zero"
                                 );
                             }
-                            data_cache.push_write_set(output.write_set())
+                            data_cache.push_write_set(output.write_set());
+                            // the following transactions should be executed with the new configs.
+                            if is_vm_config_changed(&output) {
+                                self.load_configs(&data_cache)?;
+                            }
                         }
                         result.push((status, output));
                     }
@@ -680,6 +688,139 @@ impl StarcoinVM {
         Ok(result)
     }
 
+    /// Execute a block transactions like `execute_block_transactions`, with the same outputs,
+    /// but the user transactions between block prologues are executed optimistically in parallel.
+    ///
+    /// Every user transaction is first executed speculatively on the state before them, with
+    /// the access paths it read recorded. Then the outputs are committed in order, a transaction
+    /// read any access path written by the previous committed transactions is re-executed on
+    /// the committed state. Writes to the transaction fee only add the fee, so they are merged
+    /// rather than re-executed. The transactions which publish packages may change the loaded
+    /// code, so the user transactions are executed sequentially if any of them is a package.
+    pub fn execute_block_transactions_parallel<S>(
+        &mut self,
+        state_view: &S,
+        transactions: Vec<Transaction>,
+        block_gas_limit: Option<u64>,
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>>
+    where
+        S: StateView + Sync,
+    {
+        if state_view.is_genesis() {
+            return self.execute_block_transactions(state_view, transactions, block_gas_limit);
+        }
+        let mut data_cache = SharedStateViewCache::new(state_view);
+
+        let check_gas = block_gas_limit.is_some();
+        // only used when check_gas
+        let mut gas_left = block_gas_limit.unwrap_or_default();
+
+        let mut result = vec![];
+        self.load_configs(&data_cache)?;
+        let fee_path = TransactionFeeResource::resource_path_for(DEFAULT_CURRENCY_TY.clone());
+        let blocks = chunk_block_transactions(transactions);
+        'outer: for block in blocks {
+            match block {
+                TransactionBlock::UserTransaction(txns) => {
+                    let sequential = txns
+                        .iter()
+                        .any(|txn| matches!(txn.payload(), TransactionPayload::Package(_)));
+                    let initial_fee = data_cache.get(&fee_path)?;
+                    let mut speculative_outputs = if sequential {
+                        vec![]
+                    } else {
+                        let vm = &*self;
+                        let base_view = &data_cache;
+                        txns.par_iter()
+                            .map(|txn| {
+                                let recording_view = RecordingStateView::new(base_view);
+                                let mut cache = StateViewCache::new(&recording_view);
+                                let (status, output) =
+                                    vm.execute_user_transaction(txn.clone(), &mut cache);
+                                (status, output, recording_view.into_reads())
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    .into_iter();
+
+                    // access paths written by the committed transactions of this chunk.
+                    let mut written = HashSet::new();
+                    for transaction in txns {
+                        let speculative =
+                            speculative_outputs
+                                .next()
+                                .and_then(|(status, output, reads)| {
+                                    merge_speculative_output(
+                                        status,
+                                        output,
+                                        &reads,
+                                        &written,
+                                        &fee_path,
+                                        initial_fee.as_deref(),
+                                        &data_cache,
+                                    )
+                                });
+                        let (status, output) = match speculative {
+                            Some(status_and_output) => status_and_output,
+                            None => {
+                                let mut cache = StateViewCache::new(&data_cache);
+                                self.execute_user_transaction(transaction, &mut cache)
+                            }
+                        };
+                        // only need to check for user transactions.
+                        if check_gas {
+                            match gas_left.checked_sub(output.gas_used()) {
+                                Some(l) => gas_left = l,
+                                None => break 'outer,
+                            }
+                        }
+
+                        if let TransactionStatus::Keep(_) = output.status() {
+                            debug_assert_ne!(
+                                output.gas_used(),
+                                0,
+                                "Keep transaction gas used must not be zero"
+                            );
+                            data_cache.push_write_set(output.write_set());
+                            written.extend(output.write_set().iter().map(|(ap, _)| ap.clone()));
+                            // the speculative outputs of the following transactions were
+                            // executed with the old configs, so re-execute them.
+                            if is_vm_config_changed(&output) {
+                                self.load_configs(&data_cache)?;
+                                speculative_outputs = vec![].into_iter();
+                            }
+                        }
+                        result.push((status, output));
+                    }
+                }
+                TransactionBlock::BlockPrologue(block_metadata) => {
+                    let (status, output) = {
+                        let mut cache = StateViewCache::new(&data_cache);
+                        match self.process_block_metadata(&mut cache, block_metadata) {
+                            Ok(output) => (VMStatus::Executed, output),
+                            Err(vm_status) => discard_error_vm_status(vm_status),
+                        }
+                    };
+                    debug_assert_eq!(
+                        output.gas_used(),
+                        0,
+                        "Block metadata transaction gas_used must be zero."
+                    );
+                    if let TransactionStatus::Keep(status) = output.status() {
+                        debug_assert_eq!(
+                            status,
+                            &KeptVMStatus::Executed,
+                            "Block metadata transaction keep status must been Executed."
+                        );
+                        data_cache.push_write_set(output.write_set())
+                    }
+                    result.push((status, output));
+                }
+            }
+        }
+        Ok(result)
+    }
+
     pub fn execute_transactions(
         &mut self,
         state_view: &dyn StateView,
@@ -738,6 +879,78 @@ impl StarcoinVM {
     }
 }
 
+/// Whether the transaction emitted a change event of the configs loaded by the VM.
+fn is_vm_config_changed(output: &TransactionOutput) -> bool {
+    output
+        .events()
+        .iter()
+        .any(|event| match config_id_of_change_event(event.type_tag()) {
+            Some(config_id) => config_id == VMConfig::CONFIG_ID || config_id == Version::CONFIG_ID,
+            None => false,
+        })
+}
+
+/// Check the speculative output of a transaction against the access paths `written` by the
+/// transactions committed before it, return the output to commit, or `None` if the transaction
+/// read stale state and should be re-executed.
+fn merge_speculative_output(
+    status: VMStatus,
+    output: TransactionOutput,
+    reads: &[AccessPath],
+    written: &HashSet<AccessPath>,
+    fee_path: &AccessPath,
+    initial_fee: Option<&[u8]>,
+    committed_view: &dyn StateView,
+) -> Option<(VMStatus, TransactionOutput)> {
+    let mut fee_stale = false;
+    for access_path in reads {
+        if !written.contains(access_path) {
+            continue;
+        }
+        if access_path == fee_path {
+            fee_stale = true;
+        } else {
+            return None;
+        }
+    }
+    if !fee_stale {
+        return Some((status, output));
+    }
+    // The fee was read from the initial state, add the fee paid by the transaction to the
+    // committed fee.
+    let fee_of = |blob: &[u8]| {
+        scs::from_bytes::<TransactionFeeResource>(blob)
+            .ok()
+            .map(|resource| resource.fee())
+    };
+    let initial_fee = fee_of(initial_fee?)?;
+    let committed_fee = fee_of(committed_view.get(fee_path).ok()??.as_slice())?;
+    let (write_set, events, gas_used, delta_size, txn_status) = output.into_inner();
+    let mut merged_write_set = vec![];
+    for (access_path, write_op) in write_set.iter() {
+        let write_op = match write_op {
+            WriteOp::Value(blob) if access_path == fee_path => {
+                let paid_fee = fee_of(blob.as_slice())?.checked_sub(initial_fee)?;
+                let fee = TransactionFeeResource::new(committed_fee.checked_add(paid_fee)?);
+                WriteOp::Value(scs::to_bytes(&fee).ok()?)
+            }
+            WriteOp::Deletion if access_path == fee_path => return None,
+            write_op => write_op.clone(),
+        };
+        merged_write_set.push((access_path.clone(), write_op));
+    }
+    Some((
+        status,
+        TransactionOutput::new(
+            WriteSetMut::new(merged_write_set).freeze().ok()?,
+            events,
+            gas_used,
+            delta_size,
+            txn_status,
+        ),
+    ))
+}
+
 pub enum TransactionBlock {
     UserTransaction(Vec<SignedUserTransaction>),
     BlockPrologue(BlockMetadata),
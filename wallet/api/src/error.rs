@@ -42,6 +42,7 @@ impl From<WalletError> for AccountServiceError {
                 format_err!("no private key data associate with address {}", a),
             ),
             WalletError::StoreError(e) => AccountServiceError::AccountError(e),
+            WalletError::SignerError(e) => AccountServiceError::AccountError(e),
         }
     }
 }
@@ -73,4 +74,6 @@ pub enum WalletError {
     AccountPrivateKeyMissing(AccountAddress),
     #[error("account vault store error, {0:?}")]
     StoreError(#[from] anyhow::Error),
+    #[error("signer error, {0:?}")]
+    SignerError(anyhow::Error),
 }
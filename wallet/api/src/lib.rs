@@ -3,11 +3,13 @@
 
 pub mod error;
 mod service;
+mod signer;
 mod store;
 mod types;
 mod wallet;
 
 pub use service::*;
+pub use signer::*;
 pub use store::*;
 pub use types::*;
 pub use wallet::*;
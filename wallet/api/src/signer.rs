@@ -0,0 +1,38 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::WalletResult;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::Ed25519PublicKey;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+
+/// An account which private key is held by a `Signer`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignerAccount {
+    pub address: AccountAddress,
+    pub public_key: Ed25519PublicKey,
+}
+
+impl SignerAccount {
+    pub fn new(address: AccountAddress, public_key: Ed25519PublicKey) -> Self {
+        Self {
+            address,
+            public_key,
+        }
+    }
+}
+
+/// Signer sign transactions on behalf of the accounts it holds.
+/// The private keys may live in the wallet process, or in a separate process, such as a remote signer.
+pub trait Signer: Send + Sync + std::fmt::Debug {
+    /// Return all accounts which can be signed by this signer.
+    fn accounts(&self) -> WalletResult<Vec<SignerAccount>>;
+
+    /// Sign the `raw_txn` with the private key of `signer_address`.
+    fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> WalletResult<SignedUserTransaction>;
+}
@@ -17,18 +17,20 @@ use std::ops::Add;
 use std::sync::{Mutex, RwLock};
 use std::time::Duration;
 use std::time::Instant;
-use wallet_api::{error::WalletError, Wallet, WalletAccount, WalletStore};
+use wallet_api::{error::WalletError, Signer, Wallet, WalletAccount, WalletStore};
 
 type KeyPair = starcoin_crypto::test_utils::KeyPair<Ed25519PrivateKey, Ed25519PublicKey>;
 pub type Result<T> = std::result::Result<T, WalletError>;
 
 /// Wallet base KeyStore
 /// encrypt account's key by a password.
+/// Accounts without local private key are signed by the external `Signer` if present.
 #[derive(Default, Debug)]
 pub struct KeyStoreWallet<TKeyStore> {
     store: TKeyStore,
     default_account: Mutex<Option<WalletAccount>>,
     key_cache: RwLock<KeyCache>,
+    signer: Option<Box<dyn Signer>>,
 }
 
 #[derive(Default, Debug, PartialEq, Eq)]
@@ -135,6 +137,11 @@ where
         if !self.contains(&signer_address)? {
             return Err(WalletError::AccountNotExist(signer_address));
         }
+        if let Some(signer) = self.signer.as_ref() {
+            if !self.has_local_key(&signer_address)? {
+                return signer.sign_txn(raw_txn, signer_address);
+            }
+        }
         match self.key_cache.write().unwrap().get_key(&signer_address) {
             None => Err(WalletError::AccountLocked(signer_address)),
             Some(k) => k
@@ -214,10 +221,39 @@ where
             store: keystore,
             default_account: Mutex::new(None),
             key_cache: RwLock::new(KeyCache::default()),
+            signer: None,
         };
         Ok(wallet)
     }
 
+    /// Create a wallet which sign by `signer` for accounts without local private key.
+    /// The signer's accounts are imported into the store, the first one become default if the store is empty.
+    pub fn new_with_signer(keystore: TKeyStore, signer: Box<dyn Signer>) -> Result<Self> {
+        let mut wallet = Self::new(keystore)?;
+        let mut is_default = wallet.store.get_accounts()?.is_empty();
+        for signer_account in signer.accounts()? {
+            if wallet.contains(&signer_account.address)? {
+                continue;
+            }
+            let account = WalletAccount::new(
+                signer_account.address,
+                signer_account.public_key,
+                is_default,
+            );
+            wallet.store.save_account(account)?;
+            is_default = false;
+        }
+        wallet.signer = Some(signer);
+        Ok(wallet)
+    }
+
+    fn has_local_key(&self, address: &AccountAddress) -> Result<bool> {
+        Ok(self
+            .store
+            .get_from_account(address, KEY_NAME_ENCRYPTED_PRIVATE_KEY)?
+            .is_some())
+    }
+
     fn save_account(
         &self,
         account: WalletAccount,
@@ -3,6 +3,7 @@
 
 pub mod file_wallet_store;
 pub mod keystore_wallet;
+pub mod remote_signer;
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,322 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A `Signer` which delegates signing to a separate signer process.
+//!
+//! The protocol is JSON-RPC 2.0, every param and result is the hex encoded SCS bytes of the value.
+//! Over ipc, each request and response is a single line terminated by `\n`.
+//! Over http, each request is a `POST` and the response is returned as the body.
+
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use wallet_api::{error::WalletError, Signer, SignerAccount, WalletResult};
+
+pub const JSONRPC_VERSION: &str = "2.0";
+/// Return all accounts of the signer, result is `Vec<SignerAccount>`.
+pub const METHOD_ACCOUNTS: &str = "signer.accounts";
+/// Sign a txn, params is `[AccountAddress, RawUserTransaction]`, result is `SignedUserTransaction`.
+pub const METHOD_SIGN_TXN: &str = "signer.sign_txn";
+
+/// The max size of a signer rpc request or response.
+pub const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignerRpcRequest {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub method: String,
+    pub params: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignerRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignerRpcResponse {
+    pub jsonrpc: String,
+    pub id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<SignerRpcError>,
+}
+
+impl SignerRpcResponse {
+    pub fn ok(id: u64, result: String) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn err(id: u64, code: i64, message: String) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: None,
+            error: Some(SignerRpcError { code, message }),
+        }
+    }
+}
+
+pub fn encode_param<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    Ok(hex::encode(scs::to_bytes(value)?))
+}
+
+pub fn decode_param<T>(param: &str) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let bytes = hex::decode(param.strip_prefix("0x").unwrap_or(param))?;
+    scs::from_bytes(bytes.as_slice())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SignerEndpoint {
+    /// Connect by unix domain socket file path.
+    IPC(PathBuf),
+    /// Connect by http address, such as `http://127.0.0.1:9890`.
+    HTTP(String),
+}
+
+impl FromStr for SignerEndpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ensure!(!s.is_empty(), "signer endpoint should not be empty");
+        if s.starts_with("http://") {
+            Ok(SignerEndpoint::HTTP(s.to_string()))
+        } else if s.starts_with("https://") {
+            bail!("https signer endpoint is not supported, use a local proxy instead.")
+        } else {
+            Ok(SignerEndpoint::IPC(PathBuf::from_str(s)?))
+        }
+    }
+}
+
+impl Display for SignerEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignerEndpoint::IPC(path) => write!(f, "{}", path.display()),
+            SignerEndpoint::HTTP(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Signer client which talk to a remote signer process by `SignerEndpoint`.
+/// Every call open a new connection, so the signer process can be restarted independently.
+#[derive(Debug)]
+pub struct RemoteSigner {
+    endpoint: SignerEndpoint,
+    timeout: Duration,
+    request_id: AtomicU64,
+}
+
+impl RemoteSigner {
+    pub fn new(endpoint: SignerEndpoint) -> Self {
+        Self {
+            endpoint,
+            timeout: DEFAULT_TIMEOUT,
+            request_id: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn endpoint(&self) -> &SignerEndpoint {
+        &self.endpoint
+    }
+
+    fn call<R>(&self, method: &str, params: Vec<String>) -> Result<R>
+    where
+        R: for<'de> Deserialize<'de>,
+    {
+        let id = self.request_id.fetch_add(1, Ordering::SeqCst);
+        let request = SignerRpcRequest {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            method: method.to_string(),
+            params,
+        };
+        let request = serde_json::to_vec(&request)?;
+        let response = match &self.endpoint {
+            SignerEndpoint::IPC(path) => self.send_ipc(path, request)?,
+            SignerEndpoint::HTTP(url) => self.send_http(url, request)?,
+        };
+        let response: SignerRpcResponse = serde_json::from_slice(response.as_slice())?;
+        ensure!(
+            response.id == id,
+            "signer response id mismatch, expect: {}, got: {}",
+            id,
+            response.id
+        );
+        if let Some(error) = response.error {
+            bail!("signer return error {}: {}", error.code, error.message);
+        }
+        let result = response
+            .result
+            .ok_or_else(|| format_err!("signer response without result"))?;
+        decode_param(result.as_str())
+    }
+
+    #[cfg(unix)]
+    fn send_ipc(&self, path: &Path, mut request: Vec<u8>) -> Result<Vec<u8>> {
+        let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        request.push(b'\n');
+        stream.write_all(request.as_slice())?;
+        stream.flush()?;
+        let mut response = Vec::new();
+        BufReader::new(stream)
+            .take(MAX_MESSAGE_SIZE as u64 + 1)
+            .read_until(b'\n', &mut response)?;
+        ensure!(
+            !response.is_empty(),
+            "signer closed connection without response"
+        );
+        Ok(response)
+    }
+
+    #[cfg(not(unix))]
+    fn send_ipc(&self, _path: &Path, _request: Vec<u8>) -> Result<Vec<u8>> {
+        bail!("ipc signer endpoint is only supported on unix.")
+    }
+
+    fn send_http(&self, url: &str, request: Vec<u8>) -> Result<Vec<u8>> {
+        let (host, path) = split_http_url(url)?;
+        let mut stream = TcpStream::connect(host)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+        let header = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            path,
+            host,
+            request.len()
+        );
+        stream.write_all(header.as_bytes())?;
+        stream.write_all(request.as_slice())?;
+        stream.flush()?;
+        let mut response = Vec::new();
+        stream
+            .take(MAX_MESSAGE_SIZE as u64 + 1)
+            .read_to_end(&mut response)?;
+        let header_end = response
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| format_err!("invalid http response from signer"))?;
+        let status_line = response[..header_end]
+            .split(|b| *b == b'\n')
+            .next()
+            .map(|line| String::from_utf8_lossy(line).to_string())
+            .unwrap_or_default();
+        ensure!(
+            status_line.split_whitespace().nth(1) == Some("200"),
+            "signer http request failed: {}",
+            status_line.trim()
+        );
+        Ok(response.split_off(header_end + 4))
+    }
+}
+
+/// Split `http://host:port/path` to (`host:port`, `/path`).
+fn split_http_url(url: &str) -> Result<(&str, &str)> {
+    let without_scheme = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format_err!("invalid http url: {}", url))?;
+    Ok(match without_scheme.find('/') {
+        Some(idx) => (&without_scheme[..idx], &without_scheme[idx..]),
+        None => (without_scheme, "/"),
+    })
+}
+
+impl Signer for RemoteSigner {
+    fn accounts(&self) -> WalletResult<Vec<SignerAccount>> {
+        self.call(METHOD_ACCOUNTS, vec![])
+            .map_err(WalletError::SignerError)
+    }
+
+    fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> WalletResult<SignedUserTransaction> {
+        let params = vec![
+            encode_param(&signer_address).map_err(WalletError::SignerError)?,
+            encode_param(&raw_txn).map_err(WalletError::SignerError)?,
+        ];
+        let signed_txn: SignedUserTransaction = self
+            .call(METHOD_SIGN_TXN, params)
+            .map_err(WalletError::SignerError)?;
+        // do not trust the remote signer, make sure it sign the txn we request.
+        if signed_txn.raw_txn() != &raw_txn {
+            return Err(WalletError::TransactionSignError(format_err!(
+                "signer return a txn different from request"
+            )));
+        }
+        signed_txn
+            .check_signature()
+            .map(|txn| txn.into_inner())
+            .map_err(WalletError::TransactionSignError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(
+            SignerEndpoint::from_str("http://127.0.0.1:9890").unwrap(),
+            SignerEndpoint::HTTP("http://127.0.0.1:9890".to_string())
+        );
+        assert_eq!(
+            SignerEndpoint::from_str("/tmp/signer.ipc").unwrap(),
+            SignerEndpoint::IPC(PathBuf::from("/tmp/signer.ipc"))
+        );
+        assert!(SignerEndpoint::from_str("https://127.0.0.1:9890").is_err());
+    }
+
+    #[test]
+    fn test_split_http_url() {
+        assert_eq!(
+            split_http_url("http://127.0.0.1:9890").unwrap(),
+            ("127.0.0.1:9890", "/")
+        );
+        assert_eq!(
+            split_http_url("http://127.0.0.1:9890/sign").unwrap(),
+            ("127.0.0.1:9890", "/sign")
+        );
+    }
+
+    #[test]
+    fn test_param_codec() {
+        let raw_txn = RawUserTransaction::mock();
+        let param = encode_param(&raw_txn).unwrap();
+        let raw_txn2: RawUserTransaction = decode_param(param.as_str()).unwrap();
+        assert_eq!(raw_txn, raw_txn2);
+    }
+}
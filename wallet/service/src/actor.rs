@@ -6,9 +6,14 @@ use crate::service::WalletServiceImpl;
 use actix::{Actor, Addr, Context, Handler};
 use anyhow::Result;
 use starcoin_config::NodeConfig;
+use starcoin_logger::prelude::*;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
-use starcoin_wallet_lib::{file_wallet_store::FileWalletStore, keystore_wallet::KeyStoreWallet};
+use starcoin_wallet_lib::{
+    file_wallet_store::FileWalletStore,
+    keystore_wallet::KeyStoreWallet,
+    remote_signer::{RemoteSigner, SignerEndpoint},
+};
 
 use starcoin_wallet_api::error::AccountServiceError;
 use starcoin_wallet_api::{ServiceResult, Wallet, WalletAccount, WalletAsyncService, WalletResult};
@@ -22,7 +27,14 @@ impl WalletActor {
     pub fn launch(config: Arc<NodeConfig>) -> Result<WalletActorRef> {
         let vault_config = &config.vault;
         let file_store = FileWalletStore::new(vault_config.dir());
-        let wallet = KeyStoreWallet::new(file_store)?;
+        let wallet = match vault_config.remote_signer.as_ref() {
+            Some(endpoint) => {
+                let endpoint: SignerEndpoint = endpoint.parse()?;
+                info!("Wallet use remote signer: {}", endpoint);
+                KeyStoreWallet::new_with_signer(file_store, Box::new(RemoteSigner::new(endpoint)))?
+            }
+            None => KeyStoreWallet::new(file_store)?,
+        };
         let actor = WalletActor {
             service: WalletServiceImpl::new(wallet),
         };
@@ -0,0 +1,352 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A stand-in signer process which serve the remote signer protocol of `starcoin_wallet_lib::remote_signer`.
+//! It keeps keys in memory and is mainly used for tests, a hardened signer should implement the same protocol.
+
+use anyhow::{format_err, Result};
+use starcoin_logger::prelude::*;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use starcoin_wallet_api::{Signer, SignerAccount, Wallet, WalletResult};
+use starcoin_wallet_lib::remote_signer::{
+    decode_param, encode_param, SignerRpcRequest, SignerRpcResponse, MAX_MESSAGE_SIZE,
+    METHOD_ACCOUNTS, METHOD_SIGN_TXN,
+};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SIGNER_ERROR: i64 = -32000;
+
+/// Adapt a `Wallet` which hold keys in-process to `Signer`.
+pub struct WalletSigner<W> {
+    wallet: W,
+}
+
+impl<W> std::fmt::Debug for WalletSigner<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletSigner").finish()
+    }
+}
+
+impl<W> WalletSigner<W>
+where
+    W: Wallet + Send + Sync,
+{
+    pub fn new(wallet: W) -> Self {
+        Self { wallet }
+    }
+}
+
+impl<W> Signer for WalletSigner<W>
+where
+    W: Wallet + Send + Sync,
+{
+    fn accounts(&self) -> WalletResult<Vec<SignerAccount>> {
+        Ok(self
+            .wallet
+            .get_accounts()?
+            .into_iter()
+            .map(|account| SignerAccount::new(account.address, account.public_key))
+            .collect())
+    }
+
+    fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> WalletResult<SignedUserTransaction> {
+        self.wallet.sign_txn(raw_txn, signer_address)
+    }
+}
+
+#[derive(Clone)]
+pub struct SignerServer {
+    signer: Arc<dyn Signer>,
+}
+
+impl SignerServer {
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self { signer }
+    }
+
+    /// Handle a json rpc request, always return a response, errors are encoded in the response.
+    pub fn handle_request(&self, request: &[u8]) -> SignerRpcResponse {
+        let request: SignerRpcRequest = match serde_json::from_slice(request) {
+            Ok(request) => request,
+            Err(e) => return SignerRpcResponse::err(0, PARSE_ERROR, e.to_string()),
+        };
+        let id = request.id;
+        match request.method.as_str() {
+            METHOD_ACCOUNTS => match self
+                .signer
+                .accounts()
+                .map_err(anyhow::Error::from)
+                .and_then(|accounts| encode_param(&accounts))
+            {
+                Ok(result) => SignerRpcResponse::ok(id, result),
+                Err(e) => SignerRpcResponse::err(id, SIGNER_ERROR, e.to_string()),
+            },
+            METHOD_SIGN_TXN => {
+                let params = match Self::decode_sign_params(&request.params) {
+                    Ok(params) => params,
+                    Err(e) => return SignerRpcResponse::err(id, INVALID_PARAMS, e.to_string()),
+                };
+                let (signer_address, raw_txn) = params;
+                match self
+                    .signer
+                    .sign_txn(raw_txn, signer_address)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|txn| encode_param(&txn))
+                {
+                    Ok(result) => SignerRpcResponse::ok(id, result),
+                    Err(e) => SignerRpcResponse::err(id, SIGNER_ERROR, e.to_string()),
+                }
+            }
+            method => {
+                SignerRpcResponse::err(id, METHOD_NOT_FOUND, format!("method {} not found", method))
+            }
+        }
+    }
+
+    fn decode_sign_params(params: &[String]) -> Result<(AccountAddress, RawUserTransaction)> {
+        if params.len() != 2 {
+            return Err(format_err!(
+                "expect 2 params for {}, got {}",
+                METHOD_SIGN_TXN,
+                params.len()
+            ));
+        }
+        Ok((decode_param(&params[0])?, decode_param(&params[1])?))
+    }
+
+    fn encode_response(response: &SignerRpcResponse) -> Vec<u8> {
+        serde_json::to_vec(response).expect("serialize signer response should success.")
+    }
+
+    /// Serve line delimited json rpc on the unix domain socket `path`.
+    #[cfg(unix)]
+    pub fn serve_ipc<P: AsRef<Path>>(self, path: P) -> Result<JoinHandle<()>> {
+        let listener = std::os::unix::net::UnixListener::bind(path.as_ref())?;
+        info!("Signer listen on ipc: {:?}", path.as_ref());
+        Ok(std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let server = self.clone();
+                        std::thread::spawn(move || {
+                            if let Err(e) = server.handle_ipc_connection(stream) {
+                                warn!("Handle signer ipc connection error: {:?}", e);
+                            }
+                        });
+                    }
+                    Err(e) => error!("Accept signer ipc connection error: {:?}", e),
+                }
+            }
+        }))
+    }
+
+    #[cfg(not(unix))]
+    pub fn serve_ipc<P: AsRef<Path>>(self, _path: P) -> Result<JoinHandle<()>> {
+        Err(format_err!("ipc signer is only supported on unix."))
+    }
+
+    #[cfg(unix)]
+    fn handle_ipc_connection(&self, stream: std::os::unix::net::UnixStream) -> Result<()> {
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+        loop {
+            let mut line = Vec::new();
+            if (&mut reader)
+                .take(MAX_MESSAGE_SIZE as u64 + 1)
+                .read_until(b'\n', &mut line)?
+                == 0
+            {
+                return Ok(());
+            }
+            if line.len() > MAX_MESSAGE_SIZE {
+                return Err(format_err!(
+                    "signer ipc request exceeds max size {}",
+                    MAX_MESSAGE_SIZE
+                ));
+            }
+            let mut response = Self::encode_response(&self.handle_request(line.as_slice()));
+            response.push(b'\n');
+            writer.write_all(response.as_slice())?;
+            writer.flush()?;
+        }
+    }
+
+    /// Serve json rpc over http on `address`, return the bound address.
+    pub fn serve_http(self, address: SocketAddr) -> Result<(SocketAddr, JoinHandle<()>)> {
+        let listener = TcpListener::bind(address)?;
+        let local_address = listener.local_addr()?;
+        info!("Signer listen on http: {}", local_address);
+        let handle = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let server = self.clone();
+                        std::thread::spawn(move || {
+                            if let Err(e) = server.handle_http_connection(stream) {
+                                warn!("Handle signer http connection error: {:?}", e);
+                            }
+                        });
+                    }
+                    Err(e) => error!("Accept signer http connection error: {:?}", e),
+                }
+            }
+        });
+        Ok((local_address, handle))
+    }
+
+    fn handle_http_connection(&self, stream: TcpStream) -> Result<()> {
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0usize;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(format_err!("connection closed before http header end"));
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(idx) = line.find(':') {
+                if line[..idx].eq_ignore_ascii_case("content-length") {
+                    content_length = line[idx + 1..].trim().parse()?;
+                    if content_length > MAX_MESSAGE_SIZE {
+                        return Err(format_err!(
+                            "signer http request body size {} exceeds max size {}",
+                            content_length,
+                            MAX_MESSAGE_SIZE
+                        ));
+                    }
+                }
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(body.as_mut_slice())?;
+        let response = Self::encode_response(&self.handle_request(body.as_slice()));
+        let header = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            response.len()
+        );
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(response.as_slice())?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_wallet_api::mock::{KeyPairWallet, MemWalletStore};
+    use starcoin_wallet_lib::keystore_wallet::KeyStoreWallet;
+    use starcoin_wallet_lib::remote_signer::{RemoteSigner, SignerEndpoint};
+
+    fn mock_signer() -> Result<(Arc<dyn Signer>, AccountAddress)> {
+        let wallet = KeyPairWallet::new()?;
+        let address = wallet
+            .get_default_account()?
+            .expect("KeyPairWallet should has default account")
+            .address;
+        Ok((Arc::new(WalletSigner::new(wallet)), address))
+    }
+
+    fn check_remote_signer(remote: RemoteSigner, address: AccountAddress) -> Result<()> {
+        let accounts = remote.accounts()?;
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].address, address);
+        let raw_txn = RawUserTransaction::mock_by_sender(address);
+        let txn = remote.sign_txn(raw_txn.clone(), address)?;
+        assert_eq!(txn.raw_txn(), &raw_txn);
+        let result = remote.sign_txn(
+            RawUserTransaction::mock_by_sender(AccountAddress::random()),
+            AccountAddress::random(),
+        );
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ipc_signer() -> Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let ipc_file = tmp_dir.path().join("signer.ipc");
+        let (signer, address) = mock_signer()?;
+        SignerServer::new(signer).serve_ipc(ipc_file.as_path())?;
+        check_remote_signer(RemoteSigner::new(SignerEndpoint::IPC(ipc_file)), address)
+    }
+
+    #[test]
+    fn test_http_signer() -> Result<()> {
+        let (signer, address) = mock_signer()?;
+        let (local_address, _handle) =
+            SignerServer::new(signer).serve_http("127.0.0.1:0".parse()?)?;
+        check_remote_signer(
+            RemoteSigner::new(SignerEndpoint::HTTP(format!("http://{}", local_address))),
+            address,
+        )
+    }
+
+    #[test]
+    fn test_http_signer_reject_oversized_body() -> Result<()> {
+        let (signer, _address) = mock_signer()?;
+        let (local_address, _handle) =
+            SignerServer::new(signer).serve_http("127.0.0.1:0".parse()?)?;
+        let mut stream = TcpStream::connect(local_address)?;
+        let header = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_MESSAGE_SIZE + 1
+        );
+        stream.write_all(header.as_bytes())?;
+        stream.flush()?;
+        // The server close the connection without reading the body or responding.
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        assert!(response.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_keystore_wallet_with_remote_signer() -> Result<()> {
+        let (signer, address) = mock_signer()?;
+        let (local_address, _handle) =
+            SignerServer::new(signer).serve_http("127.0.0.1:0".parse()?)?;
+        let remote = RemoteSigner::new(SignerEndpoint::HTTP(format!("http://{}", local_address)));
+        let wallet = KeyStoreWallet::new_with_signer(MemWalletStore::new(), Box::new(remote))?;
+        let default_account = wallet.get_default_account()?;
+        assert_eq!(
+            default_account.map(|account| account.address),
+            Some(address)
+        );
+        let raw_txn = RawUserTransaction::mock_by_sender(address);
+        // remote account do not need unlock.
+        let txn = wallet.sign_txn(raw_txn.clone(), address)?;
+        assert_eq!(txn.raw_txn(), &raw_txn);
+
+        // local account still sign by local key.
+        let local_account = wallet.create_account("pass")?;
+        assert!(!local_account.is_default);
+        let raw_txn = RawUserTransaction::mock_by_sender(local_account.address);
+        assert!(wallet
+            .sign_txn(raw_txn.clone(), local_account.address)
+            .is_err());
+        wallet.unlock_account(
+            local_account.address,
+            "pass",
+            std::time::Duration::from_secs(5),
+        )?;
+        wallet.sign_txn(raw_txn, local_account.address)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use starcoin_crypto::{PrivateKey, ValidCryptoMaterialStringExt};
+use starcoin_logger::prelude::*;
+use starcoin_signer::{SignerServer, WalletSigner};
+use starcoin_types::account_address;
+use starcoin_wallet_api::mock::KeyPairWallet;
+use starcoin_wallet_api::Wallet;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+#[derive(Debug, Clone, StructOpt)]
+#[structopt(name = "starcoin-signer", about = "Starcoin stand-in remote signer")]
+pub struct SignerOpt {
+    #[structopt(long, parse(from_os_str))]
+    /// Unix domain socket file path to listen.
+    pub ipc: Option<PathBuf>,
+    #[structopt(long)]
+    /// Http address to listen, such as 127.0.0.1:9890
+    pub http: Option<SocketAddr>,
+    #[structopt(long = "private-key")]
+    /// Hex encoded ed25519 private key to import, can repeat. A random default account is always generated.
+    pub private_keys: Vec<String>,
+}
+
+fn main() -> Result<()> {
+    let _logger_handle = starcoin_logger::init();
+    let opts: SignerOpt = SignerOpt::from_args();
+    let wallet = KeyPairWallet::new()?;
+    for private_key in &opts.private_keys {
+        let private_key = Ed25519PrivateKey::from_encoded_string(private_key)?;
+        let address = account_address::from_public_key(&private_key.public_key());
+        wallet.import_account(address, private_key.to_bytes().to_vec(), "")?;
+    }
+    for account in wallet.get_accounts()? {
+        info!(
+            "Signer account: {}, public key: {:?}",
+            account.address, account.public_key
+        );
+    }
+    let server = SignerServer::new(Arc::new(WalletSigner::new(wallet)));
+    let mut handles = vec![];
+    if let Some(ipc) = opts.ipc {
+        handles.push(server.clone().serve_ipc(ipc)?);
+    }
+    if let Some(http) = opts.http {
+        handles.push(server.serve_http(http)?.1);
+    }
+    if handles.is_empty() {
+        error!("Please set --ipc or --http for signer to listen.");
+        return Ok(());
+    }
+    for handle in handles {
+        let _ = handle.join();
+    }
+    Ok(())
+}